@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use crossbeam::thread;
+use rand::thread_rng;
 
-use crate::{data_manager::DataManager, 
+use crate::{constants::MAX_MIGRATION_FRACTION,
+    data_manager::DataManager, 
     demographics::Demographics, 
     objects::{
         actor_objects::{
@@ -10,7 +12,17 @@ use crate::{data_manager::DataManager,
             pop::Pop,
             state::State
         }, 
-        environmental_objects::market::{Market, MarketMessage, MarketMessageEnum},
+        environmental_objects::{
+            labor_market::{LaborChange, LaborRecord},
+            market::{Market, MarketHistory, MarketMessage, MarketMessageEnum},
+            migration::{MarketAppeal, MigrationRecord}
+        },
+        demographic_objects::{
+            conversion::{ConversionKind, ConversionPressures, ConversionRecord},
+            ideology_spread::IdeologyAppeal,
+            movement::Discontent,
+            pop_breakdown_table::{PBRow, PopBreakdownTable}
+        },
     }
 };
 
@@ -45,6 +57,13 @@ pub struct ActorManager {
     pub institutions: HashMap<usize, Institution>,
     /// The Substates in the Managed Markets
     pub states: HashMap<usize, State>,
+    /// The pops which moved between markets at the end of the last day.
+    pub migration_log: Vec<MigrationRecord>,
+    /// The people who changed culture or ideology during the last 
+    /// population phase.
+    pub conversion_log: Vec<ConversionRecord>,
+    /// The people hired or fired in the last labor market phase.
+    pub labor_log: Vec<LaborRecord>,
 }
 
 impl ActorManager {
@@ -52,16 +71,29 @@ impl ActorManager {
     /// Splits up the work based on the markets, threads each to their own 
     /// portion, and then waits on them to return.
     pub fn run_market_day(&mut self, 
-    data_manager: &DataManager, 
+    data_manager: &mut DataManager, 
     demographics: &Demographics, 
     _map: &mut ()) {
+        // markets only need to read the data, we update it after.
+        let data: &DataManager = data_manager;
+        // firms work with the skill of their workers.
+        self.share_worker_skills();
+        // note where everything is so we can route messages between markets.
+        let market_ids: HashSet<usize> = self.markets.keys().copied().collect();
+        let firm_markets: HashMap<usize, usize> = self.markets.values()
+            .flat_map(|market| market.firms.iter().map(|firm| (*firm, market.id)))
+            .collect();
         // get our thread scope, threads cannot leave here.
         thread::scope(|scope| {
             // get our thread holder we'll be getting our info back from.
             let mut threads = vec![];
-            // also get the many to many broadcaster
-            let (local_sender, 
-                local_reciever) 
+            // get the channel up from the markets to us, and the broadcaster
+            // from us down to the markets.
+            let (up_sender, 
+                up_reciever) 
+                    = barrage::bounded(1000);
+            let (down_sender, 
+                down_reciever) 
                     = barrage::bounded(1000);
             // for each market
             for market in self.markets.values_mut() {
@@ -90,14 +122,14 @@ impl ActorManager {
                     .expect("State Not Found!"));
                 }
                 // get a channel between us here and the 
-                let sender = local_sender.clone();
-                let mut reciever = local_reciever.clone();
+                let sender = up_sender.clone();
+                let mut reciever = down_reciever.clone();
                 // spin up the thread
                 threads.push(scope.spawn(move |_| {
                     market.run_market_day(
                         sender,
                         &mut reciever,
-                        data_manager, 
+                        data, 
                         demographics, 
                         &mut pops, 
                         &mut firms, 
@@ -109,13 +141,15 @@ impl ActorManager {
                         (pops, firms, insts, states)
                 }));
             }
+            // we don't read our own broadcasts, so don't hold them up.
+            drop(down_reciever);
             // alternate between checking for messages to pass up or around
             // or for additional information.
             let mut completed = HashSet::new();
             // loop until all of them return CloseMarket.
             while completed.len() != threads.len() {
                 // get the message sent, error if we disconnected prematurely.
-                let message = local_reciever.recv()
+                let message = up_reciever.recv()
                 .expect("Error! Market Threads Disconnected before closing.");
                 // get the message out and see if it's important
                 match message.message {
@@ -123,12 +157,18 @@ impl ActorManager {
                         // market is closing, mark it down and continue.
                         completed.insert(message.sender);
                     },
-                    _ => ()
+                    _ => {
+                        // otherwise pass it along to where it's going.
+                        if let Some(routed) = ActorManager::route_message(message, 
+                        &market_ids, &firm_markets) {
+                            down_sender.send(routed).expect("Problem, could not send.");
+                        }
+                    }
                 }
             }
             // Once all markets are closed, send down confirmations for them to shut down
             for id in completed {
-                local_sender.send(MarketMessage{ sender: 0, reciever: id, 
+                down_sender.send(MarketMessage{ sender: 0, reciever: id, 
                     message: MarketMessageEnum::ConfirmClose})
                     .expect("Problem, could not send.");
             }
@@ -154,9 +194,666 @@ impl ActorManager {
                     self.states.insert(0, state);
                 }
             }
-            // with all data back, do any movements between markets
-            // TODO do this later. Then pass up the changes to the runner and
-            // master so the master can share or transfer across runners.
         }).unwrap();
+        // with all data back, do any movements between markets
+        self.move_shipments(data_manager);
+        self.migration_log = self.migrate_pops(data_manager, demographics);
+        // TODO Then pass up the changes to the runner and
+        // master so the master can share or transfer across runners.
+    }
+
+    /// # Route Message
+    /// 
+    /// Figures out where a message sent up by a market should go. Messages
+    /// between firms go to the market containing the reciever, everything
+    /// else goes to the market it was addressed to.
+    /// 
+    /// If the market isn't one of ours, queries are bounced back to the 
+    /// sender with an empty answer so the asker isn't left waiting, and 
+    /// anything else is dropped.
+    /// 
+    /// TODO pass unroutable messages up to the runner once it exists.
+    pub fn route_message(message: MarketMessage, 
+    markets: &HashSet<usize>, 
+    firm_markets: &HashMap<usize, usize>) -> Option<MarketMessage> {
+        let reciever = match message.message {
+            MarketMessageEnum::CloseMarket |
+            MarketMessageEnum::ConfirmClose => return None,
+            MarketMessageEnum::FirmMessage { reciever, .. } => 
+                *firm_markets.get(&reciever)?,
+            _ => message.reciever,
+        };
+        if markets.contains(&reciever) {
+            return Some(MarketMessage { sender: message.sender, reciever, 
+                message: message.message });
+        }
+        // the reciever is unknown, send back what we can.
+        let bounce = match message.message {
+            MarketMessageEnum::PriceQuery { asker, product } => 
+                MarketMessageEnum::PriceResponse { asker, product, price: None },
+            MarketMessageEnum::TradeRequest { requester, product, .. } => 
+                MarketMessageEnum::TradeResponse { requester, product, 
+                    available: 0.0, price: None },
+            _ => return None,
+        };
+        Some(MarketMessage { sender: reciever, reciever: message.sender, 
+            message: bounce })
+    }
+
+    /// # Population Phase
+    /// 
+    /// Applies births and deaths to all of our pops over the days given. 
+    /// Pops which die out entirely are removed from their market and firm.
+    /// Then ideologies spread and people assimilate and convert, which is
+    /// logged in conversion_log.
+    /// 
+    /// Returns the total births and deaths.
+    pub fn population_phase(&mut self, demographics: &Demographics, 
+    days: f64) -> (usize, usize) {
+        let mut rng = thread_rng();
+        let mut births = 0;
+        let mut deaths = 0;
+        let mut extinct = vec![];
+        for pop in self.pops.values_mut() {
+            let (born, died) = pop.population_change(demographics, days, &mut rng);
+            births += born;
+            deaths += died;
+            if pop.count() == 0 {
+                extinct.push(pop.id);
+            }
+        }
+        for id in extinct {
+            let pop = self.pops.remove(&id).expect("Pop Not Found!");
+            if let Some(market) = self.markets.get_mut(&pop.market) {
+                market.pops.retain(|x| *x != id);
+            }
+            if let Some(firm) = pop.firm {
+                self.leave_firm(firm, id);
+            }
+        }
+        self.conversion_log = self.spread_ideologies(demographics, days);
+        let converted = self.convert_pops(demographics, days);
+        self.conversion_log.extend(converted);
+        (births, deaths)
+    }
+
+    /// # Spread Ideologies
+    /// 
+    /// Ideologies spread between the pops of each market, with people 
+    /// converting to ideologies whose followers are better off, faster the
+    /// more common those ideologies are.
+    pub fn spread_ideologies(&mut self, demographics: &Demographics, 
+    days: f64) -> Vec<ConversionRecord> {
+        let mut rng = thread_rng();
+        let mut log = vec![];
+        for market in self.markets.values() {
+            // get the appeal of each ideology before anyone converts.
+            let appeals = IdeologyAppeal::of_pops(market.pops.iter()
+                .filter_map(|id| self.pops.get(id)));
+            for pop_id in market.pops.iter() {
+                let pop = self.pops.get_mut(pop_id).expect("Pop Not Found!");
+                for (from, to, count) in pop.spread_ideologies(demographics,
+                &appeals, days, &mut rng) {
+                    log.push(ConversionRecord { pop: *pop_id, market: market.id,
+                        kind: ConversionKind::Ideology, from, to, count });
+                }
+            }
+        }
+        log
+    }
+
+    /// # Activate Desires
+    /// 
+    /// Decides which sporadic and periodic desires each pop needs on the 
+    /// day given.
+    pub fn activate_desires(&mut self, day: u64) {
+        let mut rng = thread_rng();
+        for pop in self.pops.values_mut() {
+            pop.property.activate_desires(day, &mut rng);
+        }
+    }
+
+    /// # Update Moods
+    /// 
+    /// After the market day, every pop's mood moves toward how they feel 
+    /// about the day, compared to the rest of their market.
+    pub fn update_moods(&mut self) {
+        for market in self.markets.values() {
+            let appeal = MarketAppeal::of_pops(market.pops.iter()
+                .filter_map(|id| self.pops.get(id)));
+            for pop_id in market.pops.iter() {
+                if let Some(pop) = self.pops.get_mut(pop_id) {
+                    pop.update_mood(&appeal);
+                }
+            }
+        }
+    }
+
+    /// # Practice Skills
+    /// 
+    /// Every pop practices the skill of their job over the days given.
+    pub fn practice_skills(&mut self, data: &DataManager, 
+    demographics: &Demographics, days: f64) {
+        for pop in self.pops.values_mut() {
+            pop.practice_skill(data, demographics, days);
+        }
     }
-}
\ No newline at end of file
+
+    /// # Convert Pops
+    /// 
+    /// People in each market assimilate into the dominant culture and 
+    /// convert to the dominant ideology, as well as those promoted by the 
+    /// states in the market.
+    pub fn convert_pops(&mut self, demographics: &Demographics, 
+    days: f64) -> Vec<ConversionRecord> {
+        let mut rng = thread_rng();
+        // get the pressures in each market before anyone converts.
+        let pressures: HashMap<usize, ConversionPressures> = self.markets.values()
+            .map(|market| (market.id, ConversionPressures::of_market(
+                &self.market_breakdown(market.id),
+                market.states.iter().filter_map(|id| self.states.get(id)))))
+            .collect();
+        let mut log = vec![];
+        for market in self.markets.values() {
+            let pressure = pressures.get(&market.id).expect("Market Not Found!");
+            for pop_id in market.pops.iter() {
+                let pop = self.pops.get_mut(pop_id).expect("Pop Not Found!");
+                for (kind, from, to, count) in pop.convert(demographics,
+                pressure, days, &mut rng) {
+                    log.push(ConversionRecord { pop: *pop_id, market: market.id,
+                        kind, from, to, count });
+                }
+            }
+        }
+        log
+    }
+
+    /// # Share Worker Skills
+    /// 
+    /// Tells each firm how much the skill of each of it's workers boosts 
+    /// their work, see Pop::skill_efficiency.
+    pub fn share_worker_skills(&mut self) {
+        for firm in self.firms.values_mut() {
+            let efficiency = firm.all_jobs()
+                .filter_map(|job| job.pop)
+                .filter_map(|pop| self.pops.get(&pop)
+                    .map(|x| (pop, x.skill_efficiency())))
+                .collect();
+            firm.worker_efficiency = efficiency;
+        }
+    }
+
+    /// # Market Technologies
+    /// 
+    /// The technologies known in each market, those required by the 
+    /// processes its firms are assigned to do.
+    pub fn market_technologies(&self, data: &DataManager) -> HashMap<usize, HashSet<usize>> {
+        let mut result = HashMap::new();
+        for market in self.markets.values() {
+            let known: HashSet<usize> = market.firms.iter()
+                .filter_map(|id| self.firms.get(id))
+                .flat_map(|firm| firm.jobs.iter())
+                .flat_map(|job| job.assignments.keys())
+                .filter_map(|process| data.processes.get(process))
+                .filter_map(|process| process.technology_requirement)
+                .collect();
+            result.insert(market.id, known);
+        }
+        result
+    }
+
+    /// # Discontent
+    /// 
+    /// Gets how many people of each culture and ideology in each market 
+    /// are restless (ended the day worse off or are in a foul mood), for 
+    /// forming movements.
+    pub fn discontent(&self) -> Vec<Discontent> {
+        let mut result = vec![];
+        for market in self.markets.values() {
+            let mut groups: HashMap<(Option<usize>, Option<usize>), Discontent> = HashMap::new();
+            for pop in market.pops.iter().filter_map(|id| self.pops.get(id)) {
+                let unsatisfied = pop.is_restless();
+                for row in pop.breakdown_table.table.iter() {
+                    let group = groups.entry((row.culture, row.ideology))
+                        .or_insert(Discontent { culture: row.culture, 
+                            ideology: row.ideology, market: market.id, 
+                            unsatisfied: 0, total: 0, firms: vec![], 
+                            states: market.states.clone() });
+                    group.total += row.count;
+                    if unsatisfied {
+                        group.unsatisfied += row.count;
+                        if let Some(firm) = pop.firm.filter(|x| !group.firms.contains(x)) {
+                            group.firms.push(firm);
+                        }
+                    }
+                }
+            }
+            result.extend(groups.into_values());
+        }
+        result
+    }
+
+    /// # Market Breakdown
+    /// 
+    /// Combines the breakdown tables of all the pops in the market given, 
+    /// for getting the makeup or division of the market as a whole.
+    pub fn market_breakdown(&self, market: usize) -> PopBreakdownTable {
+        let mut result = PopBreakdownTable { table: vec![], total: 0 };
+        if let Some(market) = self.markets.get(&market) {
+            for pop in market.pops.iter().filter_map(|id| self.pops.get(id)) {
+                for row in pop.breakdown_table.table.iter() {
+                    result.insert_pops(*row);
+                }
+            }
+        }
+        result
+    }
+
+    /// # Migrate Pops
+    /// 
+    /// Pops compare themselves to the people in neighboring markets and, if
+    /// one looks better after the cost of travel, part of them move to the
+    /// best of them. Unhappy pops are more willing to leave, content ones
+    /// less so.
+    /// 
+    /// If the whole pop leaves, it moves itself, otherwise the migrants split
+    /// off into a new pop in the destination. Either way, the migrants leave 
+    /// their firm behind.
+    /// 
+    /// Returns a log of who moved where.
+    pub fn migrate_pops(&mut self, data: &mut DataManager, 
+    demographics: &Demographics) -> Vec<MigrationRecord> {
+        // get how appealing each market is before anyone moves.
+        let appeals: HashMap<usize, MarketAppeal> = self.markets.values()
+            .map(|market| (market.id, MarketAppeal::of_pops(market.pops.iter()
+                .filter_map(|id| self.pops.get(id)))))
+            .collect();
+        // decide who wants to go where.
+        let mut moves = vec![];
+        for market in self.markets.values() {
+            for pop_id in market.pops.iter() {
+                let pop = self.pops.get(pop_id).expect("Pop Not Found!");
+                let current = MarketAppeal::of_pops(std::iter::once(pop));
+                let willingness = pop.migration_willingness();
+                let best = market.neighbors.iter()
+                    .filter_map(|(destination, connection)| appeals.get(destination)
+                        .map(|appeal| (*destination, 
+                            (current.migration_fraction(appeal, connection) * willingness)
+                                .min(MAX_MIGRATION_FRACTION))))
+                    .filter(|(_, fraction)| *fraction > 0.0)
+                    .max_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some((destination, fraction)) = best {
+                    moves.push((*pop_id, market.id, destination, fraction));
+                }
+            }
+        }
+        // then move them.
+        let mut log = vec![];
+        for (pop_id, origin, destination, fraction) in moves {
+            let pop = self.pops.get_mut(&pop_id).expect("Pop Not Found!");
+            let migrants = pop.breakdown_table.split_off(fraction);
+            if migrants.total == 0 {
+                continue;
+            }
+            let count = migrants.total;
+            let moved_id = if pop.count() == 0 { // everyone left, move the pop.
+                pop.breakdown_table = migrants;
+                pop.market = destination;
+                if let Some(firm) = pop.firm.take() {
+                    self.settle_salary(firm, pop_id, pop_id, 1.0, data);
+                    self.leave_firm(firm, pop_id);
+                }
+                self.markets.get_mut(&origin).expect("Market Not Found!")
+                    .pops.retain(|x| *x != pop_id);
+                pop_id
+            } else { // split off the migrants into their own pop.
+                let new_id = ActorManager::unused_pop_id(&self.pops, data);
+                let pop = self.pops.get_mut(&pop_id).expect("Pop Not Found!");
+                let new_pop = pop.split_migrants(migrants, new_id, destination,
+                    demographics);
+                let id = new_pop.id;
+                self.pops.insert(id, new_pop);
+                self.pay_split_off(pop_id, id, data);
+                id
+            };
+            self.markets.get_mut(&destination).expect("Market Not Found!")
+                .pops.push(moved_id);
+            log.push(MigrationRecord { pop: pop_id, migrants: moved_id, 
+                origin, destination, count });
+        }
+        log
+    }
+
+    /// # Labor Market Phase
+    /// 
+    /// Firms losing money lay off part of their workers, who split off 
+    /// into a new, unemployed, pop (or the whole pop leaves if they all go).
+    /// 
+    /// Then, in each market, pops which are unemployed or looking for work
+    /// see the best paying opening left, updating their wage estimate from 
+    /// it, and take it if it's better than what they make now. See 
+    /// Pop::job_switch_fraction. Drones and slaves don't look for work.
+    /// 
+    /// Those hired and fired are recorded in labor_log.
+    pub fn labor_market_phase(&mut self, data: &mut DataManager, 
+    demographics: &Demographics) {
+        let mut log = vec![];
+        // firms losing money let people go first.
+        let layoffs: Vec<(usize, usize, usize, f64)> = self.firms.values()
+            .flat_map(|firm| firm.layoffs().into_iter()
+                .map(|(index, pop, fraction)| (firm.id, index, pop, fraction)))
+            .collect();
+        for (firm_id, index, pop_id, fraction) in layoffs {
+            let pop = match self.pops.get_mut(&pop_id) {
+                Some(pop) => pop,
+                None => continue,
+            };
+            let fired = pop.breakdown_table.split_off(fraction);
+            if fired.total == 0 {
+                continue;
+            }
+            let count = fired.total;
+            let job = pop.job;
+            let workers = if pop.count() == 0 { // everyone was let go.
+                pop.breakdown_table = fired;
+                self.settle_salary(firm_id, pop_id, pop_id, 1.0, data);
+                let pop = self.pops.get_mut(&pop_id).expect("Pop Not Found!");
+                pop.firm = None;
+                pop.property.wage_estimate = 0.0;
+                if let Some(firm) = self.firms.get_mut(&firm_id) {
+                    firm.jobs[index].pop = None;
+                }
+                pop_id
+            } else {
+                let new_id = ActorManager::unused_pop_id(&self.pops, data);
+                let pop = self.pops.get_mut(&pop_id).expect("Pop Not Found!");
+                let new_pop = pop.split_off_table(fired, new_id, demographics);
+                self.markets.get_mut(&new_pop.market).expect("Market Not Found!")
+                    .pops.push(new_pop.id);
+                let id = new_pop.id;
+                self.pops.insert(id, new_pop);
+                self.pay_split_off(pop_id, id, data);
+                id
+            };
+            log.push(LaborRecord { pop: pop_id, workers, firm: firm_id, job, 
+                count, change: LaborChange::Fired });
+        }
+        // then those looking for work look over the openings.
+        let mut market_ids: Vec<usize> = self.markets.keys().copied().collect();
+        market_ids.sort();
+        for market_id in market_ids {
+            let market = self.markets.get(&market_id).expect("Market Not Found!");
+            let mut openings: Vec<_> = market.firms.iter()
+                .filter_map(|id| self.firms.get(id))
+                .flat_map(|firm| firm.openings())
+                .collect();
+            openings.sort_by(|a, b| b.wage.total_cmp(&a.wage));
+            let seekers: Vec<usize> = market.pops.iter()
+                .filter(|id| self.is_seeking_work(**id, demographics))
+                .copied()
+                .collect();
+            for pop_id in seekers {
+                let best = match openings.first() {
+                    Some(best) => *best,
+                    None => break, // no work left.
+                };
+                let pop = self.pops.get_mut(&pop_id).expect("Pop Not Found!");
+                pop.observe_wage_offer(best.wage);
+                let movers = pop.breakdown_table
+                    .split_off(pop.job_switch_fraction(&best));
+                if movers.total == 0 {
+                    continue;
+                }
+                openings.remove(0);
+                let count = movers.total;
+                let workers = if pop.count() == 0 { // the whole pop moves.
+                    pop.breakdown_table = movers;
+                    let old_firm = pop.firm;
+                    pop.take_job(&best);
+                    if let Some(firm) = old_firm {
+                        self.settle_salary(firm, pop_id, pop_id, 1.0, data);
+                        self.leave_firm(firm, pop_id);
+                    }
+                    pop_id
+                } else {
+                    let new_id = ActorManager::unused_pop_id(&self.pops, data);
+                    let pop = self.pops.get_mut(&pop_id).expect("Pop Not Found!");
+                    let mut new_pop = pop.split_off_table(movers, new_id, 
+                        demographics);
+                    new_pop.take_job(&best);
+                    let id = new_pop.id;
+                    self.pops.insert(id, new_pop);
+                    self.pay_split_off(pop_id, id, data);
+                    self.markets.get_mut(&market_id).expect("Market Not Found!")
+                        .pops.push(id);
+                    id
+                };
+                if let Some(firm) = self.firms.get_mut(&best.firm) {
+                    firm.jobs[best.index].pop = Some(workers);
+                }
+                log.push(LaborRecord { pop: pop_id, workers, firm: best.firm,
+                    job: best.job, count, change: LaborChange::Hired });
+            }
+        }
+        self.labor_log = log;
+    }
+
+    /// Whether the pop is looking for work, being either unemployed or 
+    /// unhappy with a job they're free to leave. Drones never look.
+    fn is_seeking_work(&self, pop: usize, demographics: &Demographics) -> bool {
+        let pop = match self.pops.get(&pop) {
+            Some(pop) => pop,
+            None => return false,
+        };
+        if pop.is_drone(demographics) {
+            return false;
+        }
+        let firm = match pop.firm {
+            Some(firm) => firm,
+            None => return true,
+        };
+        pop.is_looking_for_work() && self.firms.get(&firm)
+            .and_then(|firm| firm.jobs.iter().find(|x| x.pop == Some(pop.id)))
+            .is_none_or(|job| job.wage_type.can_change_jobs())
+    }
+
+    /// Gets a new pop id from the data manager, skipping any already in 
+    /// use by our pops.
+    fn unused_pop_id(pops: &HashMap<usize, Pop>, data: &mut DataManager) -> usize {
+        loop {
+            let id = data.new_pop_id();
+            if !pops.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    /// # Split Pop
+    /// 
+    /// Splits the fraction given off of the pop into a new pop in the same
+    /// market, returning it's id. The new pop has no firm. See Pop::split.
+    /// 
+    /// This, and ActorManager::split_pop_rows, are how pops should be 
+    /// split, as they give the new pop an id no other pop has.
+    /// 
+    /// If no one would be split off, it returns None.
+    pub fn split_pop(&mut self, pop: usize, fraction: f64, data: &mut DataManager,
+    demographics: &Demographics) -> Option<usize> {
+        let new_id = ActorManager::unused_pop_id(&self.pops, data);
+        let new_pop = self.pops.get_mut(&pop)?
+            .split(fraction, new_id, demographics);
+        self.add_split_pop(pop, new_pop, data)
+    }
+
+    /// # Split Pop Rows
+    /// 
+    /// Splits the rows given (up to as many as it has of each) off of the
+    /// pop into a new pop in the same market, returning it's id. See 
+    /// Pop::split_rows and ActorManager::split_pop.
+    pub fn split_pop_rows(&mut self, pop: usize, rows: &[PBRow], 
+    data: &mut DataManager, demographics: &Demographics) -> Option<usize> {
+        let new_id = ActorManager::unused_pop_id(&self.pops, data);
+        let new_pop = self.pops.get_mut(&pop)?
+            .split_rows(rows, new_id, demographics);
+        self.add_split_pop(pop, new_pop, data)
+    }
+
+    /// Adds a pop split off of another to it's market, unless no one was 
+    /// split off, paying them their share of any salary they're owed.
+    fn add_split_pop(&mut self, pop: usize, new_pop: Pop, 
+    data: &DataManager) -> Option<usize> {
+        if new_pop.count() == 0 {
+            return None;
+        }
+        let id = new_pop.id;
+        self.markets.get_mut(&new_pop.market)?.pops.push(id);
+        self.pops.insert(id, new_pop);
+        self.pay_split_off(pop, id, data);
+        Some(id)
+    }
+
+    /// # Merge Pops
+    /// 
+    /// Merges the second pop into the first, removing it from it's market
+    /// and any firm jobs it held. Any salary it was owed is paid to the 
+    /// pop it's merged into. See Pop::merge.
+    pub fn merge_pops(&mut self, into: usize, merged: usize, 
+    data: &DataManager, demographics: &Demographics) -> Result<(), String> {
+        let other = self.pops.get(&merged)
+            .ok_or(format!("Pop {} not found.", merged))?;
+        let pop = self.pops.get(&into)
+            .ok_or(format!("Pop {} not found.", into))?;
+        if !pop.can_merge(other) {
+            return Err(format!("Pop {} cannot be merged into Pop {}.", merged, into));
+        }
+        let other = self.pops.remove(&merged).expect("Pop Not Found!");
+        if let Some(market) = self.markets.get_mut(&other.market) {
+            market.pops.retain(|x| *x != merged);
+        }
+        if let Some(firm) = other.firm {
+            self.settle_salary(firm, merged, into, 1.0, data);
+            self.leave_firm(firm, merged);
+        }
+        self.pops.get_mut(&into).expect("Pop Not Found!")
+            .merge(other, demographics)
+    }
+
+    /// # Settle Salary
+    /// 
+    /// Has the firm pay the share given of the salary the pop has accrued 
+    /// to the payee, the pop itself or those who left it, at the prices of
+    /// the firm's market. Those leaving a firm should be paid this way 
+    /// before leaving. See Firm::settle_salary.
+    /// 
+    /// If the payee is gone nothing is paid, and the firm drops what it 
+    /// owed at it's next payroll.
+    fn settle_salary(&mut self, firm: usize, pop: usize, payee: usize, 
+    share: f64, data: &DataManager) {
+        if !self.pops.contains_key(&payee) || self.firms.get(&firm)
+        .is_none_or(|firm| firm.owed_salary(pop) <= 0.0) {
+            return;
+        }
+        let history = match self.markets.values()
+        .find(|market| market.firms.contains(&firm)) {
+            Some(market) => MarketHistory::create(market, data),
+            None => return,
+        };
+        let check = match self.firms.get_mut(&firm)
+        .and_then(|firm| firm.settle_salary(pop, share, &history)) {
+            Some(check) => check,
+            None => return,
+        };
+        let payee = self.pops.get_mut(&payee).expect("Pop Not Found!");
+        for (product, amount) in check.goods {
+            payee.property.unsafe_add_property(product, amount);
+        }
+        payee.recieve_pay_stub(check.wage, check.period);
+    }
+
+    /// Pays those split off of the pop, now the new pop, their share (by 
+    /// headcount) of any salary the pop's firm owes. The new pop no longer
+    /// works there.
+    fn pay_split_off(&mut self, pop: usize, new_pop: usize, data: &DataManager) {
+        let (firm, stayed) = match self.pops.get(&pop)
+        .and_then(|pop| pop.firm.map(|firm| (firm, pop.count()))) {
+            Some(found) => found,
+            None => return,
+        };
+        let left = self.pops.get(&new_pop).map_or(0, |x| x.count());
+        if left == 0 {
+            return;
+        }
+        let share = left as f64 / (stayed + left) as f64;
+        self.settle_salary(firm, pop, new_pop, share, data);
+    }
+
+    /// Removes the pop from it's job in the firm, leaving it open.
+    fn leave_firm(&mut self, firm: usize, pop: usize) {
+        if let Some(firm) = self.firms.get_mut(&firm) {
+            for job in firm.jobs.iter_mut()
+            .filter(|x| x.pop == Some(pop)) {
+                job.pop = None;
+            }
+        }
+    }
+
+    /// # Move Shipments
+    /// 
+    /// Moves all shipments between markets along by a day. Those which 
+    /// arrive are stocked in the destination market by the owner's firm 
+    /// there, to be sold. This is the owner itself if it's in that market,
+    /// otherwise one of it's children there, opening a new branch (see 
+    /// Firm::branch) if it has none. If the owner is gone, the goods are 
+    /// left in the destination market for anyone to pick up.
+    pub fn move_shipments(&mut self, data: &mut DataManager) {
+        let mut arrived = vec![];
+        for market in self.markets.values_mut() {
+            market.in_transit.retain_mut(|shipment| {
+                if shipment.advance_day() {
+                    arrived.push(shipment.clone());
+                    false
+                } else { true }
+            });
+        }
+        for shipment in arrived {
+            let destination = self.markets.get_mut(&shipment.destination)
+                .expect("Destination Market Not Found!");
+            let owner = match self.firms.get(&shipment.owner) {
+                Some(owner) => owner,
+                None => {
+                    *destination.resources.entry(shipment.product)
+                        .or_insert(0.0) += shipment.quantity;
+                    continue;
+                }
+            };
+            let local = destination.firms.iter()
+                .find(|id| **id == owner.id || 
+                    self.firms.get(id).is_some_and(|x| x.parent == Some(owner.id)))
+                .copied();
+            let reciever = match local {
+                Some(id) => id,
+                None => { // open a branch there.
+                    let id = ActorManager::unused_firm_id(&self.firms, data);
+                    let branch = owner.branch(id, destination.name.clone());
+                    destination.firms.push(id);
+                    self.firms.insert(id, branch);
+                    self.firms.get_mut(&shipment.owner)
+                        .expect("Owner checked above.").children.push(id);
+                    id
+                }
+            };
+            self.firms.get_mut(&reciever).expect("Firm Not Found!")
+                .recieve_shipment(shipment.product, shipment.quantity);
+        }
+    }
+
+    /// Gets a firm id which isn't in use yet.
+    fn unused_firm_id(firms: &HashMap<usize, Firm>, data: &mut DataManager) -> usize {
+        loop {
+            let id = data.new_firm_id();
+            if !firms.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+}
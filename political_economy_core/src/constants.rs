@@ -16,29 +16,19 @@ pub const TIME_PRODUCT_ID: usize = 0;
 /// 
 /// Current Uses:
 /// - Standard Buying Trip: 0.2 hr cost
+/// - Organizing an Auction or Reverse Auction: 1.0 hr cost
+/// - Bidding on an Auction or Reverse Auction: 0.05 hr cost
+/// - Handling goods moved in an exchange, given or recieved, by both sides: 
+///   (Mass * HANDLING_MASS_COST + Bulk * HANDLING_BULK_COST) per unit, 
+///   paid first from the Handling Time product, if the data has one.
+/// - Salesmanning Trip: 0.3 hr cost, the seller's equivalent to the Standard
+///   Buying Trip, made more expensive to encourage buyers to come to sellers.
 /// 
 /// ## Planned Expansion
 /// 
-/// Eventually, every exchange of goods will come with a handling cost
-/// that scales with the bulk of the good in question. 
-/// Exact rates would need to be determined, but something small for each
-/// would be best, in along the lines of (Mass * [0.01] + Bulk * [0.05]). 
-/// This part may need to be separated later into a 'Handling Time', which 
-/// focuses on this and allows for specialized handling jobs.
-/// 
 /// Selling Cost. This would be similar, but much smaller compared to Standard
 /// Buy trip. Used as a way to limit and encourage those who sell more limited
 /// variety of goods, though this may be just small or insignificant.
-/// 
-/// Auctions and Reverse Auctions are the biggest addition that would be added.
-/// These are bigger events, which have a singular buyer or seller state their
-/// order then sellers or buyers (whichever is opposite) get together to bid on
-/// the order. The one setting it up spends a decent amount of time on it,
-/// while the participants spend less time comparatively.
-/// 
-/// Salesmanning, This is the comparable to the standard buy trip, but with the
-/// person looking to sell rather than buy. Making this comparable to Standard
-/// Buy is reasonable, but making it more expensive may be better to encourage
 pub const SHOPPING_TIME_PRODUCT_ID: usize = 1;
 /// The ID for the Skill Discernment.
 pub const DISCERNMENT_PRODUCT_ID: usize = 2;
@@ -69,6 +59,120 @@ pub const TIER_RATIO: f64 = 0.9;
 
 /// The standard cost in time for an individual to go shopping.
 pub const SHOPPING_TIME_COST: f64 = 0.2;
+/// The cost in time for an individual to go out looking for a buyer.
+pub const SALESMANNING_TIME_COST: f64 = 0.3;
+/// The cost in time for an individual to organize an auction or reverse 
+/// auction.
+pub const AUCTION_ORGANIZER_TIME_COST: f64 = 1.0;
+/// The cost in time for an individual to bid on an auction or reverse
+/// auction.
+pub const AUCTION_BIDDER_TIME_COST: f64 = 0.05;
+/// How much a pop's surplus of a product must be worth (in AMV) per person
+/// before they auction it off rather than waiting for buyers to come to them.
+pub const AUCTION_SURPLUS_THRESHOLD: f64 = 10.0;
+/// The time cost per kg of a product handled in an exchange.
+pub const HANDLING_MASS_COST: f64 = 0.01;
+/// The time cost per m^3 of a product handled in an exchange.
+pub const HANDLING_BULK_COST: f64 = 0.05;
+
+/// The time cost per kg of a product moved one unit of distance between 
+/// markets.
+pub const TRANSPORT_MASS_COST: f64 = 0.001;
+/// The time cost per m^3 of a product moved one unit of distance between
+/// markets.
+pub const TRANSPORT_BULK_COST: f64 = 0.005;
+/// The distance a shipment moves in a day over land. Other connections 
+/// scale this by their speed.
+pub const TRAVEL_DISTANCE_PER_DAY: f64 = 30.0;
+/// The AMV per person lost for each day spent travelling when migrating.
+pub const MIGRATION_TRAVEL_COST: f64 = 1.0;
+/// How strongly a pop responds to a better market. The share of a pop 
+/// which leaves is this times the relative gain of moving.
+pub const MIGRATION_RATE: f64 = 0.1;
+/// The most of a pop which may leave in a single day.
+pub const MAX_MIGRATION_FRACTION: f64 = 0.05;
+/// How quickly ideologies spread between followers in a market. The share
+/// of followers who convert each day is this times the relative gain in 
+/// satisfaction and the share of the market following the target.
+pub const IDEOLOGY_SPREAD_RATE: f64 = 0.05;
+/// The most of an ideology's followers in a pop who may convert in a single day.
+pub const MAX_IDEOLOGY_SPREAD_FRACTION: f64 = 0.01;
+/// The share of people, per year, who convert to a culture or ideology 
+/// that makes up their whole market. Scales with the target's share and 
+/// state promotion.
+pub const CONVERSION_RATE: f64 = 0.02;
+/// How much faster people convert to a culture or ideology related to their own.
+pub const RELATED_CONVERSION_MULTIPLIER: f64 = 3.0;
+/// The share of a group who must be worse off for the group to hold a 
+/// grievance that day.
+pub const MOVEMENT_DISCONTENT_THRESHOLD: f64 = 0.5;
+/// How many days in a row a group must hold a grievance before forming a movement.
+pub const MOVEMENT_FORMATION_DAYS: u64 = 30;
+/// How many days a movement's grievance must go unanswered before it escalates.
+pub const MOVEMENT_ESCALATION_DAYS: u64 = 60;
+/// How much a pop's mood is driven by whether they ended the day better or
+/// worse off than they started it.
+pub const MOOD_TREND_WEIGHT: f64 = 0.4;
+/// How much a pop's mood is driven by how their gains compare to what they
+/// believed they could get.
+pub const MOOD_EXPECTATION_WEIGHT: f64 = 0.2;
+/// How much a pop's mood is driven by how well off they are compared to
+/// the rest of their market.
+pub const MOOD_COMPARISON_WEIGHT: f64 = 0.4;
+/// The share of the gap between a pop's mood and how they feel today which
+/// is closed each day.
+pub const MOOD_ADJUSTMENT_RATE: f64 = 0.1;
+/// The productivity bonus (or malus) of a pop at the best (or worst) mood.
+pub const MOOD_PRODUCTIVITY_EFFECT: f64 = 0.1;
+/// The mood at or below which a pop is restless, supporting movements 
+/// even on days they end better off.
+pub const MOOD_UNREST_THRESHOLD: f64 = -0.5;
+/// The mood at or below which a pop looks for a new job.
+pub const MOOD_JOB_SEARCH_THRESHOLD: f64 = -0.25;
+/// The share of an employed, but unhappy, pop which leaves for a better 
+/// paying job in a day.
+pub const JOB_SWITCH_FRACTION: f64 = 0.1;
+/// The share of a firm's workers laid off each day it loses money.
+pub const LAYOFF_FRACTION: f64 = 0.05;
+/// How far a pop's wage estimate moves toward the best job offer they see,
+/// or the daily wage they're paid, each day.
+pub const WAGE_ESTIMATE_ADJUSTMENT: f64 = 0.1;
+/// The hours of work in a worker's day. Wages for Daily, Contractor, and 
+/// Salary jobs are per worker per day, so are paid per this many hours.
+pub const WORK_DAY_HOURS: f64 = 8.0;
+/// How many days salaried workers go between paychecks.
+pub const SALARY_PAY_PERIOD: usize = 7;
+/// How far the hours a salaried worker is paid for move toward the hours
+/// they actually work each day.
+pub const SALARY_HOURS_ADJUSTMENT: f64 = 0.1;
+/// How much a firm raises or lowers it's price in a day, as a fraction of
+/// the price, when it sells out or fails to sell.
+pub const FIRM_PRICE_STEP: f64 = 0.05;
+/// The share of it's offered goods a firm must sell to consider itself 
+/// sold out and raise it's price.
+pub const FIRM_SELL_OUT_THRESHOLD: f64 = 0.9;
+/// The share of it's offered goods below which a firm lowers it's price.
+pub const FIRM_UNSOLD_THRESHOLD: f64 = 0.5;
+/// How far a firm's prices move toward the market price each day.
+pub const FIRM_MARKET_PRICE_WEIGHT: f64 = 0.2;
+/// The highest skill level a pop may reach.
+pub const MAX_SKILL_LEVEL: f64 = 10.0;
+/// How much a pop's highest skill level grows each year of practice, before
+/// diminishing returns and species learning modifiers are applied.
+pub const SKILL_LEARNING_RATE: f64 = 1.0;
+/// The bonus to process outputs for each level of skill a pop has.
+pub const SKILL_EFFICIENCY_PER_LEVEL: f64 = 0.05;
+/// Desires reduced to this amount or less are treated as gone, to avoid
+/// keeping around the remains of floating point errors.
+pub const DESIRE_EPSILON: f64 = 0.000001;
+/// The number of days in a year. Birth and mortality rates are per year.
+pub const DAYS_PER_YEAR: f64 = 365.0;
+/// The additional mortality, per year, of a pop with none of it's tier 0
+/// desires met. Scales down linearly as more of them are met.
+pub const STARVATION_MORTALITY_RATE: f64 = 1.0;
+/// The additional mortality, per year, of a pop holding everything their
+/// toxic desires ask for. Scales down linearly with their toxicity.
+pub const TOXIC_MORTALITY_RATE: f64 = 0.5;
 /// The Salability threshold for an item to be considered a currency.
 pub const SALABILITY_THRESHOLD: f64 = 0.75;
 /// The standard price movement step we use.
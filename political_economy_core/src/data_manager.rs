@@ -20,6 +20,7 @@ use crate::objects::{
             ProcessTag}, 
         product::{Product, ProductTag}, 
         process_node::ProcessNode, 
+        skill::{Skill, SkillGroup}, 
         technology::Technology, 
         technology_family::TechnologyFamily, 
         want::Want
@@ -89,9 +90,13 @@ pub struct DataManager {
     /// and it's variants, the values in the attached vector.
     pub product_classes: HashMap<usize, Vec<usize>>,
     // TODO Add in abstract to real product connections here.
-    //pub skill_groups: HashMap<usize, SkillGroup>,
-    //pub skills: HashMap<usize, Skill>,
+    pub skill_groups: HashMap<usize, SkillGroup>,
+    pub skills: HashMap<usize, Skill>,
     pub processes: HashMap<usize, Process>,
+    /// The optional Handling Time product. If loaded, handling costs are paid
+    /// from it before falling back to Shopping Time, allowing for 
+    /// specialized handling jobs.
+    pub handling_time_product: Option<usize>,
     // TODO Consider combining this with Processes (would still need to be set after loading)
     pub process_nodes: HashMap<usize, ProcessNode>,
     pub jobs: HashMap<usize, Job>,
@@ -122,6 +127,8 @@ pub struct DataManager {
     product_id: usize,
     process_id: usize,
     job_id: usize,
+    skill_id: usize,
+    skill_group_id: usize,
     species_id: usize,
     culture_id: usize,
     pop_id: usize,
@@ -140,7 +147,10 @@ impl DataManager {
             technology_families: HashMap::new(), 
             products: HashMap::new(), 
             product_classes: HashMap::new(),
+            skill_groups: HashMap::new(),
+            skills: HashMap::new(),
             processes: HashMap::new(),
+            handling_time_product: None,
             process_nodes: HashMap::new(),
             jobs: HashMap::new(),
             species: HashMap::new(),
@@ -156,6 +166,8 @@ impl DataManager {
             product_id: 0,
             process_id: 0,
             job_id: 0,
+            skill_id: 0,
+            skill_group_id: 0,
             species_id: 0,
             culture_id: 0,
             pop_id: 0,
@@ -1712,6 +1724,43 @@ impl DataManager {
         }
     }
 
+    /// Loads the test skills and skill groups, one skill for each of the
+    /// test jobs.
+    pub fn load_test_skills(&mut self) -> Result<(), String> {
+        let skills = [
+            (0, "Farming"),
+            (1, "Cotton Farming"),
+            (2, "Spinning"),
+            (3, "Weaving"),
+            (4, "Tailoring"),
+            (5, "Lumbering"),
+            (6, "Tool Making"),
+            (7, "Construction"),
+            (8, "Repair"),
+            (9, "Stone Gathering"),
+        ];
+        for (id, name) in skills {
+            let skill = Skill::new(id, name.into(), String::new(), None)?;
+            self.skills.insert(id, skill);
+        }
+        // farming, textiles, woodworking, and building
+        let groups: [(usize, &str, Vec<usize>); 4] = [
+            (0, "Agriculture", vec![0, 1]),
+            (1, "Textiles", vec![2, 3, 4]),
+            (2, "Woodworking", vec![5, 6]),
+            (3, "Building", vec![7, 8, 9]),
+        ];
+        for (id, name, members) in groups {
+            let mut group = SkillGroup::new(id, name.into(), String::new())?;
+            for member in members {
+                group.add_skill(self.skills.get_mut(&member).unwrap());
+            }
+            self.skill_groups.insert(id, group);
+        }
+
+        Ok(())
+    }
+
     pub fn load_test_jobs(&mut self) -> Result<(), String> {
         // Food, clothes, and shelter
         let mut subsistence_farmer = Job::new(
@@ -1902,6 +1951,7 @@ impl DataManager {
         self.load_test_wants()?;
         self.load_test_products()?;
         self.load_test_processes()?;
+        self.load_test_skills()?;
         self.load_test_jobs()?;
 
         Ok(())
@@ -1989,6 +2039,28 @@ impl DataManager {
         }
     }
 
+    pub fn new_skill_id(&mut self) -> usize {
+        loop {
+            if self.skills.contains_key(&self.skill_id) {
+                self.skill_id += 1;
+            }
+            else {
+                return self.skill_id;
+            }
+        }
+    }
+
+    pub fn new_skill_group_id(&mut self) -> usize {
+        loop {
+            if self.skill_groups.contains_key(&self.skill_group_id) {
+                self.skill_group_id += 1;
+            }
+            else {
+                return self.skill_group_id;
+            }
+        }
+    }
+
     pub fn new_species_id(&mut self) -> usize {
         loop {
             if self.species.contains_key(&self.species_id) {
@@ -2011,15 +2083,17 @@ impl DataManager {
         }
     }
 
+    /// # New Pop Id
+    /// 
+    /// Gets an unused pop id. Unlike other ids, it's reserved when given, 
+    /// as pops are split off and stored elsewhere (see ActorManager) 
+    /// before they could be added here.
     pub fn new_pop_id(&mut self) -> usize {
-        loop {
-            if self.pops.contains_key(&self.pop_id) {
-                self.pop_id += 1;
-            }
-            else {
-                return self.pop_id;
-            }
+        while self.pops.contains_key(&self.pop_id) {
+            self.pop_id += 1;
         }
+        self.pop_id += 1;
+        self.pop_id - 1
     }
 
     pub fn new_territory_id(&mut self) -> usize {
@@ -2044,15 +2118,17 @@ impl DataManager {
         }
     }
 
-    pub fn new_firm_i(&mut self) -> usize {
-        loop {
-            if self.firms.contains_key(&self.firm_id) {
-                self.firm_id += 1;
-            }
-            else {
-                return self.firm_id;
-            }
+    /// # New Firm Id
+    /// 
+    /// Gets an unused firm id. Like pop ids, it's reserved when given, as 
+    /// branches are opened and stored elsewhere (see ActorManager) before 
+    /// they could be added here.
+    pub fn new_firm_id(&mut self) -> usize {
+        while self.firms.contains_key(&self.firm_id) {
+            self.firm_id += 1;
         }
+        self.firm_id += 1;
+        self.firm_id - 1
     }
 }
 
@@ -3,33 +3,303 @@
 //! 
 //! These are stored for reference here.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use crate::constants::{MOVEMENT_DISCONTENT_THRESHOLD, MOVEMENT_ESCALATION_DAYS, 
+    MOVEMENT_FORMATION_DAYS};
 
 
 use crate::objects::{
     actor_objects::desire::Desire, 
-    data_objects::item::Item, 
+    data_objects::{item::Item, skill::Skill, technology::Technology}, 
     demographic_objects::{
-        culture::Culture, 
-        ideology::Ideology, 
-        species::Species 
+        culture::{Culture, CultureSubentry}, 
+        ideology::{Ideology, IdeologySubentry}, 
+        civilization::Civilization,
+        conversion::{ConversionKind, ConversionRecord},
+        movement::{Discontent, Movement, MovementEscalation},
+        pop_breakdown_table::PBRow,
+        species::{Species, SpeciesSubentry}
     }
 };
 
 /// Demographics is the data handler for our demographic data. It stores all of our
 /// shared population data, making it available for reading during most phases and
 /// listing and recording during the population change phase.
+#[derive(Default)]
 pub struct Demographics {
     /// Non-specific Data for Species.
     pub species: HashMap<usize, Species>,
-    // civilization
+    /// Non-specific data for Civilizations.
+    pub civilizations: HashMap<usize, Civilization>,
     /// Non-specific data for Cultures
     pub cultures: HashMap<usize, Culture>,
     /// Non-specific data for Ideology.
     pub ideology: HashMap<usize, Ideology>,
+    /// The total number of people who have converted, by the kind of 
+    /// conversion, what they left, and what they joined.
+    pub conversions: HashMap<(ConversionKind, Option<usize>, usize), usize>,
+    /// The active movements.
+    pub movements: HashMap<usize, Movement>,
+    /// The id to give the next movement formed. Ids of dissolved movements
+    /// are never reused.
+    pub next_movement_id: usize,
+    /// How many days in a row each culture and ideology group has held a 
+    /// grievance. Groups without a grievance are not listed.
+    pub grievances: HashMap<(Option<usize>, Option<usize>), u64>,
 }
 
 impl Demographics {
+    /// Creates an empty set of demographics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Record Conversions
+    /// 
+    /// Adds the conversions given to our running totals.
+    pub fn record_conversions(&mut self, records: &[ConversionRecord]) {
+        for record in records.iter() {
+            *self.conversions.entry((record.kind, record.from, record.to))
+                .or_insert(0) += record.count;
+        }
+    }
+
+    /// # Species Subentry
+    /// 
+    /// Gets the summarized species data for the row's species, cohort, and
+    /// subtype.
+    pub fn species_subentry(&self, row: &PBRow) -> &SpeciesSubentry {
+        self.species.get(&row.species)
+            .expect("Species Id Not Found!")
+            .get_subentry(row.species_cohort, row.species_subtype)
+            .expect("Invalid Cohort or Subtype!")
+    }
+
+    /// # Assign Market
+    /// 
+    /// Makes the market belong to the civilization given, removing it from 
+    /// any other. If None, the market belongs to no civilization.
+    pub fn assign_market(&mut self, market: usize, civilization: Option<usize>) {
+        for civ in self.civilizations.values_mut() {
+            civ.markets.remove(&market);
+        }
+        if let Some(id) = civilization {
+            self.civilizations.get_mut(&id)
+                .expect("Civilization Id Not Found!")
+                .markets.insert(market);
+        }
+    }
+
+    /// # Market Civilization
+    /// 
+    /// The civilization the market belongs to, if any.
+    pub fn market_civilization(&self, market: usize) -> Option<&Civilization> {
+        self.civilizations.values().find(|x| x.markets.contains(&market))
+    }
+
+    /// # Advance Epochs
+    /// 
+    /// Moves every civilization into the next epoch if they know enough of
+    /// its technologies.
+    pub fn advance_epochs(&mut self, technology: &HashMap<usize, Technology>) {
+        for civilization in self.civilizations.values_mut() {
+            civilization.advance_epoch(technology);
+        }
+    }
+
+    /// # Learn Technologies
+    /// 
+    /// Every civilization learns the technologies known in it's markets.
+    /// See Civilization::learn_technologies.
+    pub fn learn_technologies(&mut self, market_technologies: &HashMap<usize, HashSet<usize>>) {
+        for civilization in self.civilizations.values_mut() {
+            civilization.learn_technologies(market_technologies);
+        }
+    }
+
+    /// # Update Movements
+    /// 
+    /// Takes the discontent of each group in each market and updates 
+    /// grievances and movements with it.
+    /// 
+    /// Groups which are persistently discontent form movements. Movements
+    /// gather support from their group, escalate while their grievance goes
+    /// unanswered, and deescalate, eventually dissolving, once it has passed.
+    /// 
+    /// Groups without a culture or ideology share nothing to organize around
+    /// and are ignored.
+    pub fn update_movements(&mut self, reports: &[Discontent]) {
+        let mut groups: HashMap<(Option<usize>, Option<usize>), Vec<&Discontent>> = HashMap::new();
+        for report in reports.iter()
+        .filter(|x| x.culture.is_some() || x.ideology.is_some()) {
+            groups.entry((report.culture, report.ideology)).or_default().push(report);
+        }
+        // update grievances
+        for (key, group) in groups.iter() {
+            let unsatisfied: usize = group.iter().map(|x| x.unsatisfied).sum();
+            let total: usize = group.iter().map(|x| x.total).sum();
+            let days = self.grievances.entry(*key).or_insert(0);
+            if total > 0 && unsatisfied as f64 / total as f64 >= MOVEMENT_DISCONTENT_THRESHOLD {
+                *days += 1;
+            } else {
+                *days = 0;
+            }
+        }
+        self.grievances.retain(|key, days| groups.contains_key(key) && *days > 0);
+        // update existing movements
+        let mut dissolved = vec![];
+        for movement in self.movements.values_mut() {
+            let key = (movement.culture, movement.ideology);
+            movement.gather_support(groups.get(&key).map_or(&[], |x| &x[..]));
+            if self.grievances.contains_key(&key) {
+                movement.days_at_escalation += 1;
+                if movement.days_at_escalation >= MOVEMENT_ESCALATION_DAYS &&
+                movement.escalation != MovementEscalation::Revolt {
+                    movement.escalation = movement.escalation.escalate();
+                    movement.days_at_escalation = 0;
+                }
+            } else if let Some(lower) = movement.escalation.deescalate() {
+                movement.escalation = lower;
+                movement.days_at_escalation = 0;
+            } else {
+                dissolved.push(movement.id);
+            }
+        }
+        for id in dissolved {
+            self.movements.remove(&id);
+        }
+        // form new movements
+        for (key, days) in self.grievances.iter() {
+            if *days < MOVEMENT_FORMATION_DAYS || self.movements.values()
+            .any(|x| (x.culture, x.ideology) == *key) {
+                continue;
+            }
+            let id = self.next_movement_id;
+            self.next_movement_id += 1;
+            let mut movement = Movement { id, culture: key.0, ideology: key.1,
+                support: HashMap::new(), demands: vec![], 
+                escalation: MovementEscalation::Petition, days_at_escalation: 0 };
+            movement.gather_support(&groups[key]);
+            self.movements.insert(id, movement);
+        }
+    }
+
+    /// # Culture Subentry
+    /// 
+    /// Gets the summarized culture data for the row's culture, class, and
+    /// generation, if it has a culture.
+    pub fn culture_subentry(&self, row: &PBRow) -> Option<&CultureSubentry> {
+        row.culture.map(|id| self.cultures.get(&id)
+            .expect("Culture Id Not Found!")
+            .get_subentry(row.culture_class, row.culture_generation)
+            .expect("Invalid Class or Generation!"))
+    }
+
+    /// # Ideology Subentry
+    /// 
+    /// Gets the summarized ideology data for the row's ideology, wave, and
+    /// faction, if it has an ideology.
+    pub fn ideology_subentry(&self, row: &PBRow) -> Option<&IdeologySubentry> {
+        row.ideology.map(|id| self.ideology.get(&id)
+            .expect("Ideology Id Not Found!")
+            .get_subentry(row.ideology_wave, row.ideology_faction)
+            .expect("Invalid Wave or Faction!"))
+    }
+
+    /// # Can Work
+    /// 
+    /// Whether the people in the row are allowed to take the job given,
+    /// based on their culture's class.
+    pub fn can_work(&self, row: &PBRow, job: usize) -> bool {
+        match row.culture {
+            Some(id) => self.cultures.get(&id)
+                .expect("Culture Id Not Found!")
+                .class_can_work(row.culture_class, job),
+            None => true,
+        }
+    }
+
+    /// # Skill Learning Rate
+    /// 
+    /// How quickly the people in the row learn the skill given, based on
+    /// their species, cohort, and subtype. The skill's modifier and the
+    /// modifiers of every group it belongs to are multiplied together, 
+    /// with missing modifiers counting as 1.0.
+    pub fn skill_learning_rate(&self, row: &PBRow, skill: &Skill) -> f64 {
+        let subentry = self.species_subentry(row);
+        let mut result = *subentry.skill_modifiers
+            .get(&(skill.id as u64)).unwrap_or(&1.0);
+        for group in skill.groups.iter() {
+            result *= subentry.skill_group_modifiers
+                .get(&(*group as u64)).unwrap_or(&1.0);
+        }
+        result.max(0.0)
+    }
+
+    /// # Advance Generations
+    /// 
+    /// Moves time forward for every culture, advancing their current 
+    /// generation as needed.
+    pub fn advance_generations(&mut self, days: f64) {
+        for culture in self.cultures.values_mut() {
+            culture.advance_generation(days);
+        }
+    }
+
+    /// # Row Desires
+    /// 
+    /// The desires of a single person in the row given, combining their
+    /// species (with cohort and subtype), culture (with class and generation),
+    /// and ideology (with wave and faction), each laid over the last. See 
+    /// Desire::overlay_desires.
+    pub fn row_desires(&self, row: &PBRow) -> Vec<Desire> {
+        let mut result = self.species_subentry(row).desires.clone();
+        // placeholder for civilization
+        if let Some(culture) = self.culture_subentry(row) {
+            Desire::overlay_desires(&mut result, &culture.desires);
+        }
+        if let Some(ideology) = self.ideology_subentry(row) {
+            Desire::overlay_desires(&mut result, &ideology.desires);
+        }
+        // add in movements
+        result
+    }
+
+    /// # Birth Rate
+    /// 
+    /// The yearly birth rate of the people in the row given. The species,
+    /// cohort, and subtype rate plus the culture (with class and generation)
+    /// and ideology modifiers, never below 0.
+    pub fn birth_rate(&self, row: &PBRow) -> f64 {
+        let mut result = self.species_subentry(row).birth_rate;
+        if let Some(culture) = self.culture_subentry(row) {
+            result += culture.birth_rate_modifier;
+        }
+        if let Some(id) = row.ideology {
+            result += self.ideology.get(&id)
+                .expect("Ideology Id Not Found!").birth_rate_modifier;
+        }
+        result.max(0.0)
+    }
+
+    /// # Mortality Rate
+    /// 
+    /// The yearly mortality rate of the people in the row given. The 
+    /// species, cohort, and subtype rate plus the culture (with class and
+    /// generation) and ideology modifiers, never below 0.
+    pub fn mortality_rate(&self, row: &PBRow) -> f64 {
+        let mut result = self.species_subentry(row).mortality_rate;
+        if let Some(culture) = self.culture_subentry(row) {
+            result += culture.mortality_rate_modifier;
+        }
+        if let Some(id) = row.ideology {
+            result += self.ideology.get(&id)
+                .expect("Ideology Id Not Found!").mortality_rate_modifier;
+        }
+        result.max(0.0)
+    }
+
     /// Species Loader.
     /// 
     /// Currently does not load from file, just loads hard coded data.
@@ -46,7 +316,9 @@ impl Demographics {
             base_productivity: 1.0,
             birth_rate: 0.02,
             mortality_rate: 0.01,
-            //data_table: vec![],
+            cohorts: vec![],
+            subtypes: vec![],
+            data_table: vec![],
         };
 
         // food desires, the only Required items for a species currently.
@@ -73,6 +345,7 @@ impl Demographics {
         };
         humie.desires.push(food);
         humie.desires.push(rest);
+        humie.update_data_table();
 
         self.species.insert(humie.id, humie);
 
@@ -93,7 +366,11 @@ impl Demographics {
             productivity_modifier: 1.0,
             birth_rate_modifier: 0.02,
             mortality_rate_modifier: 0.01,
-            //data_table: vec![],
+            classes: vec![],
+            generations: vec![],
+            current_generation: None,
+            generation_progress: 0.0,
+            data_table: vec![],
         };
 
         // ambrosia preference, 
@@ -156,6 +433,7 @@ impl Demographics {
         normie.desires.push(clothing);
         normie.desires.push(fashion);
         normie.desires.push(wealth);
+        normie.update_data_table();
 
         self.cultures.insert(normie.id, normie);
 
@@ -174,7 +452,11 @@ impl Demographics {
             mortality_rate_modifier: 0.01, 
             productivity_modifier: 1.0, 
             desires: vec![], 
-            relations: vec![] 
+            relations: vec![],
+            waves: vec![],
+            factions: vec![],
+            current_wave: None,
+            data_table: vec![],
         };
 
         // Suits
@@ -213,6 +495,9 @@ impl Demographics {
         casual.desires.push(suit);
         casual.desires.push(cabin);
         casual.desires.push(cotton_boll);
+        casual.update_data_table();
+
+        self.ideology.insert(casual.id, casual);
 
         Ok("No Problemo".into())
     }
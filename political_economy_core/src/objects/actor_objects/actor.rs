@@ -1,9 +1,11 @@
+use std::collections::VecDeque;
+
 use barrage::{Sender, Receiver};
 
 use crate::{data_manager::DataManager, demographics::Demographics};
 use crate::objects::environmental_objects::market::MarketHistory;
 
-use super::actor_message::ActorMessage;
+use super::{actor_message::ActorMessage, seller::Seller};
 
 
 
@@ -17,4 +19,88 @@ pub trait Actor {
         data: &DataManager,
         demos: &Demographics,
         history: &MarketHistory);
-}
\ No newline at end of file
+}
+
+/// # Message Backlog
+/// 
+/// Sending and waiting on messages for actors which set aside the messages 
+/// for them that come while they're busy. Implementors only need to give 
+/// access to their backlog.
+pub trait MessageBacklog: Seller {
+    /// The messages for us which we haven't gotten to yet.
+    fn backlog(&mut self) -> &mut VecDeque<ActorMessage>;
+
+    /// # Push Message
+    /// 
+    /// Sends a message, catching up on (and backlogging) messages for us 
+    /// whenever the queue is full, so we never block the queue ourselves.
+    /// 
+    /// ## Panics
+    /// 
+    /// If the send fails due to a disconnect.
+    fn push_message(&mut self, rx: &Receiver<ActorMessage>, 
+    tx: &Sender<ActorMessage>, msg: ActorMessage) {
+        loop {
+            self.msg_catchup(rx);
+            match tx.try_send(msg) {
+                Ok(_) => break,
+                Err(barrage::TrySendError::Disconnected(_)) => 
+                    panic!("Unexpected Disconnect"),
+                Err(barrage::TrySendError::Full(_)) => (),
+            }
+        }
+    }
+
+    /// # Message Catchup
+    /// 
+    /// Consumes everything in the queue, putting anything for us in the 
+    /// backlog. This keeps the broadcast queue from backing up.
+    fn msg_catchup(&mut self, rx: &Receiver<ActorMessage>) {
+        while let Some(msg) = rx.try_recv().expect("Unexpected Disconnect") {
+            if msg.for_me(self.actor_info()) {
+                self.backlog().push_back(msg);
+            }
+        }
+    }
+
+    /// Gets the next message for us, from the backlog first, then waiting
+    /// on the queue.
+    fn next_message(&mut self, rx: &Receiver<ActorMessage>) -> ActorMessage {
+        if let Some(msg) = self.backlog().pop_front() {
+            return msg;
+        }
+        loop {
+            let msg = rx.recv().expect("Unexpected Disconnect.");
+            if msg.for_me(self.actor_info()) {
+                return msg;
+            }
+        }
+    }
+
+    /// # Specific Wait
+    /// 
+    /// Waits for a message for us of the same kind as any in find, checking
+    /// the backlog first. Anything else for us is put onto the backlog.
+    /// 
+    /// Only the kind of message is checked, so find may be filled with 
+    /// dummy values.
+    fn specific_wait(&mut self, rx: &Receiver<ActorMessage>, 
+    find: &[ActorMessage]) -> ActorMessage {
+        let matches = |msg: &ActorMessage| find.iter()
+            .any(|x| std::mem::discriminant(x) == std::mem::discriminant(msg));
+        if let Some(idx) = self.backlog().iter().position(matches) {
+            return self.backlog().remove(idx)
+                .expect("Somehow walked off end of backlog.");
+        }
+        loop {
+            let msg = rx.recv().expect("Unexpected Disconnect.");
+            if !msg.for_me(self.actor_info()) {
+                continue;
+            }
+            if matches(&msg) {
+                return msg;
+            }
+            self.backlog().push_back(msg);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::objects::data_objects::item::Item;
+
 /// Actor Message is a message which can be passed between
 /// two actor threads.
 ///
@@ -43,6 +45,111 @@ pub enum ActorMessage {
     SellOrder { sender: ActorInfo, product: usize, quantity: f64,
         amv: f64 },
 
+    /// # Bid Order
+    /// 
+    /// A buy order sent to a market which exchanges through an order book.
+    /// The price is per unit, in units of the market's settlement currency.
+    /// 
+    /// The market replies with any OrderFilled messages, followed by a
+    /// BidClosed once the bid has been matched as far as it can be.
+    BidOrder { sender: ActorInfo, product: usize, quantity: f64,
+        price: f64 },
+    /// # Order Filled
+    /// 
+    /// Sent by an order book market to both buyer and seller when a bid and
+    /// an ask have been matched. The buyer gives quantity * price of the 
+    /// currency and gains the product, the seller does the reverse.
+    OrderFilled { buyer: ActorInfo, seller: ActorInfo, product: usize,
+        quantity: f64, currency: usize, price: f64 },
+    /// # Bid Closed
+    /// 
+    /// Sent by an order book market after a BidOrder has been matched against
+    /// all available asks. Any unfilled remainder is cancelled rather than
+    /// left in the book. Includes the total quantity filled.
+    BidClosed { buyer: ActorInfo, product: usize, filled: f64 },
+
+    /// # Open Auction
+    ///
+    /// Sent by an organizer to the market to open an auction of their
+    /// product, or a reverse auction for a product they wish to buy.
+    ///
+    /// Reserve is the worst unit price, in the currency, the organizer will
+    /// accept. The lowest for an auction, the highest for a reverse auction.
+    OpenAuction { organizer: ActorInfo, kind: AuctionKind, product: usize,
+        quantity: f64, currency: usize, reserve: f64 },
+    /// # Auction Opened
+    ///
+    /// Broadcast by the market to everyone once an auction has been opened,
+    /// giving it's id so bids can be placed on it.
+    AuctionOpened { auction: usize, organizer: ActorInfo, kind: AuctionKind,
+        product: usize, quantity: f64, currency: usize, reserve: f64 },
+    /// # Auction Bid
+    ///
+    /// A bid on an open auction, sent to the market. The price is per unit
+    /// in the auction's currency.
+    AuctionBid { auction: usize, bidder: ActorInfo, price: f64 },
+    /// # Close Auction
+    ///
+    /// Sent by the organizer to the market when they are done taking bids.
+    CloseAuction { auction: usize, organizer: ActorInfo },
+    /// # Auction Closed
+    ///
+    /// Broadcast by the market to everyone once an auction closes. The 
+    /// organizer and winner (if any) then exchange quantity of the product 
+    /// for quantity * price of the currency, while the losing bidders 
+    /// release what they held for their bids.
+    AuctionClosed { auction: usize, organizer: ActorInfo,
+        winner: Option<ActorInfo>, kind: AuctionKind, product: usize,
+        quantity: f64, currency: usize, price: f64 },
+    /// # Auction Not Found
+    ///
+    /// Sent by the market to an organizer who tried to close an auction
+    /// which is not open in it, or which they did not organize.
+    AuctionNotFound { auction: usize, organizer: ActorInfo },
+
+    /// # Ship Goods
+    /// 
+    /// Sent by a merchant or trader firm to it's market, asking to ship the
+    /// product to the neighboring destination market.
+    ShipGoods { sender: ActorInfo, destination: usize, product: usize,
+        quantity: f64 },
+    /// # Shipment Sent
+    /// 
+    /// Sent by the market once a shipment is on it's way. The sender should
+    /// remove the goods from it's property and pay the transport cost.
+    ShipmentSent { sender: ActorInfo, destination: usize, product: usize,
+        quantity: f64, cost: f64 },
+    /// # Shipment Refused
+    /// 
+    /// Sent by the market if the shipment could not be made, either because
+    /// the sender can't trade between markets, the destination is not a 
+    /// neighbor, or the goods cannot be moved there.
+    ShipmentRefused { sender: ActorInfo, destination: usize, product: usize },
+
+    /// # Query Price
+    /// 
+    /// Sent to our market, asking what the product sells for in another 
+    /// market. The market forwards it along.
+    QueryPrice { sender: ActorInfo, market: usize, product: usize },
+    /// # Price Info
+    /// 
+    /// The answer to a QueryPrice. Price is None if the market has no price
+    /// for the product.
+    PriceInfo { reciever: ActorInfo, market: usize, product: usize,
+        price: Option<f64> },
+    /// # Request Trade
+    /// 
+    /// Sent to our market, asking another market how much of a product it
+    /// has available for the quantity we want.
+    RequestTrade { sender: ActorInfo, market: usize, product: usize, 
+        quantity: f64 },
+    /// # Trade Info
+    /// 
+    /// The answer to a RequestTrade, how much of the quantity requested is
+    /// available and at what price.
+    TradeInfo { reciever: ActorInfo, market: usize, product: usize, 
+        available: f64, price: Option<f64> },
+
     /// The find product message, recieved by the market.
     /// Contains the product id.
     /// Also includes the sender and their type so
@@ -64,6 +171,24 @@ pub enum ActorMessage {
     /// Contains the want Id, and the sender's info so a response can be
     /// sent back.
     FindWant { want: usize, sender: ActorInfo },
+    /// # Find Buyer
+    /// 
+    /// Sent by a seller who is salesmanning, asking the market to find 
+    /// them a buyer for the product.
+    /// 
+    /// The market returns FoundBuyer if successful, BuyerNotFound if not.
+    FindBuyer { product: usize, sender: ActorInfo },
+    /// # Found Buyer
+    /// 
+    /// Sent by the market to both seller and buyer in response to FindBuyer.
+    /// The seller then opens the deal as though the buyer had come to them,
+    /// and the buyer responds as they would to any seller.
+    FoundBuyer { seller: ActorInfo, buyer: ActorInfo, product: usize },
+    /// # Buyer Not Found
+    /// 
+    /// Sent by the market if it recieved a FindBuyer, but has no one to 
+    /// send the seller to.
+    BuyerNotFound { product: usize, seller: ActorInfo },
     /// Returned from an attempt to buy an item and unable to
     /// find said item at all.
     /// Returns all of the information from the Find Product so the buyer can
@@ -216,6 +341,9 @@ pub enum ActorMessage {
     /// job changes (promotions, demotions, transfers).
     FirmToEmployee { firm: ActorInfo, employee: ActorInfo,
         action: FirmEmployeeAction },
+    /// A message between a parent firm and one of it's children. If the 
+    /// reciever is in another market, it is routed there by the markets.
+    FirmToFirm { sender: usize, reciever: usize, action: FirmHierarchyAction },
     /// A message from an employee to a firm.
     EmployeeToFirm { employee: ActorInfo, firm: ActorInfo,
         action: FirmEmployeeAction },
@@ -275,9 +403,37 @@ impl ActorMessage {
             ActorMessage::StartDay => true,
             ActorMessage::Finished { sender } => me == *sender,
             ActorMessage::AllFinished => true,
+            ActorMessage::BidOrder { .. } => false, // for market, sent by me
+            ActorMessage::OrderFilled { buyer, seller,
+                .. } => *buyer == me || *seller == me, // from market to both sides
+            ActorMessage::BidClosed { buyer,
+                .. } => *buyer == me, // from market to buyer
+            ActorMessage::OpenAuction { .. } => false, // for market, sent by me
+            ActorMessage::AuctionOpened { .. } => true, // from market, to everyone
+            ActorMessage::AuctionBid { .. } => false, // for market, sent by me
+            ActorMessage::CloseAuction { .. } => false, // for market, sent by me
+            ActorMessage::AuctionClosed { .. } => true, // from market, to everyone
+            ActorMessage::AuctionNotFound { organizer,
+                .. } => *organizer == me, // from market to organizer
+            ActorMessage::ShipGoods { .. } => false, // for market, sent by me
+            ActorMessage::ShipmentSent { sender,
+                .. } => *sender == me, // from market to shipper
+            ActorMessage::ShipmentRefused { sender,
+                .. } => *sender == me, // from market to shipper
+            ActorMessage::QueryPrice { .. } => false, // for market, sent by me
+            ActorMessage::PriceInfo { reciever,
+                .. } => *reciever == me, // from market to asker
+            ActorMessage::RequestTrade { .. } => false, // for market, sent by me
+            ActorMessage::TradeInfo { reciever,
+                .. } => *reciever == me, // from market to requester
             ActorMessage::FindProduct { .. } => false, // for market, sent by me
             ActorMessage::FindClass { .. } => false, // for market, sent by me,
             ActorMessage::FindWant { .. } => false, // for market, sent by me
+            ActorMessage::FindBuyer { .. } => false, // for market, sent by me
+            ActorMessage::FoundBuyer { seller,
+                buyer, .. } => *seller == me || *buyer == me, // from market, created by FindBuyer
+            ActorMessage::BuyerNotFound { seller,
+                .. } => *seller == me, // from market to seller
             ActorMessage::FoundProduct { seller,
                 buyer, .. } => {
                     *seller == me || *buyer == me
@@ -305,6 +461,8 @@ impl ActorMessage {
                 action: _ } => *employee == me, // reciever
             ActorMessage::EmployeeToFirm { employee: _, firm,
                 action: _ } => *firm == me, // firm can recieve
+            ActorMessage::FirmToFirm { reciever, 
+                .. } => ActorInfo::Firm(*reciever) == me, // parent or child firm
 
             ActorMessage::SellOrder { sender: _, product: _,
                 quantity: _, amv: _ } => false,
@@ -337,7 +495,7 @@ impl ActorMessage {
 }
 
 /// The actions which a can be sent between firms and employees
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FirmEmployeeAction {
     /// Work day has finished and gotten what it needs from it's pops, move
     /// along.
@@ -353,6 +511,13 @@ pub enum FirmEmployeeAction {
     /// requested item(s). Intended primarily to end
     /// FirmEmployeeAction::RequestEverything logic.
     RequestSent,
+    /// Used by drone pops to ask their owner to provide an item for their
+    /// desires, as they cannot shop for themselves.
+    RequestProvisions {item: Item, amount: f64},
+    /// Sent by the firm with an employee's pay, telling them what it was 
+    /// worth in AMV and how many days it covers. The wage may be negative 
+    /// for those sharing in the firm's losses.
+    PayStub {wage: f64, period: usize},
 
     // TODO consider removing these.
     /// The firm has hired more people into this pop.
@@ -364,8 +529,69 @@ pub enum FirmEmployeeAction {
     TransferTo,
 }
 
-/// Information about an actor in a nice package.
+/// The actions which can be sent between a parent firm and it's children.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FirmHierarchyAction {
+    /// The child reports a product it sells and the price it asks for it.
+    Report { product: usize, price: f64 },
+    /// The parent tells the child what price to sell the product at.
+    SetPrice { product: usize, price: f64 },
+    /// The parent has heard the report and leaves the price to the child.
+    Acknowledge,
+}
+
+/// Which side of an auction the organizer is on.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionKind {
+    /// The organizer is selling, the highest bid wins.
+    Auction,
+    /// The organizer is buying, the lowest bid wins.
+    ReverseAuction,
+}
+
+/// # Auction Terms
+///
+/// The terms of an auction, what is being auctioned and for what.
+///
+/// Reserve is the worst unit price, in the currency, the organizer will
+/// accept. The lowest for an auction, the highest for a reverse auction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuctionTerms {
+    pub kind: AuctionKind,
+    pub product: usize,
+    pub quantity: f64,
+    pub currency: usize,
+    pub reserve: f64,
+}
+
+impl AuctionTerms {
+    /// # Organizer Gives
+    ///
+    /// The product and amount the organizer must put up to back the
+    /// auction. The product for an auction, the currency at the reserve
+    /// for a reverse auction.
+    pub fn organizer_gives(&self) -> (usize, f64) {
+        match self.kind {
+            AuctionKind::Auction => (self.product, self.quantity),
+            AuctionKind::ReverseAuction => (self.currency, self.quantity * self.reserve),
+        }
+    }
+
+    /// # Bidder Gives
+    ///
+    /// The product and amount a bidder must put up to back a bid at the
+    /// unit price given. The currency for an auction, the product for a
+    /// reverse auction.
+    pub fn bidder_gives(&self, price: f64) -> (usize, f64) {
+        match self.kind {
+            AuctionKind::Auction => (self.currency, self.quantity * price),
+            AuctionKind::ReverseAuction => (self.product, self.quantity),
+        }
+    }
+}
+
+/// Information about an actor in a nice package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ActorInfo {
     Firm(usize),
     Pop(usize),
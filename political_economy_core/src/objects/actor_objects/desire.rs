@@ -3,6 +3,8 @@
 use core::fmt;
 use std::error::Error;
 
+use rand::Rng;
+
 use crate::objects::data_objects::item::Item;
 
 /// Desires
@@ -93,6 +95,22 @@ impl Desire {
         true
     }
 
+    /// # Overlay Desires
+    /// 
+    /// Adds the overlay desires to the base, as a cohort, class, or wave
+    /// does over it's parent. Matching desires are added together, and any 
+    /// which fall to 0 or below are removed.
+    pub fn overlay_desires(base: &mut Vec<Desire>, overlay: &[Desire]) {
+        for desire in overlay.iter() {
+            if let Some(existing) = base.iter_mut().find(|x| x.is_match(desire)) {
+                existing.amount += desire.amount;
+            } else if desire.amount > 0.0 {
+                base.push(desire.clone());
+            }
+        }
+        base.retain(|x| x.amount > 0.0);
+    }
+
     /// Adds the given value to our satisfaction, capping at our maximum and
     /// returning the excess.
     /// 
@@ -328,6 +346,16 @@ impl Desire {
         Some(self.steps() as f64 * self.amount)
     }
 
+    /// # Clamp Satisfaction
+    /// 
+    /// Caps our satisfaction at the total we desire, for after the amount
+    /// desired has been reduced. Infinite desires have no cap.
+    pub fn clamp_satisfaction(&mut self) {
+        if let Some(total) = self.total_desire() {
+            self.satisfaction = self.satisfaction.min(total);
+        }
+    }
+
     /// Calculates the total satisfaction of the desire, returning it
     /// measured in the number of tiers satisfied.
     /// 
@@ -481,6 +509,30 @@ impl Desire {
         let total_needed = ((steps+1) as f64) * self.amount;
         (total_needed - self.satisfaction).max(0.0)
     }
+
+    /// # Is Toxic
+    /// 
+    /// Whether the desire is marked with DesireTag::Toxic.
+    pub fn is_toxic(&self) -> bool {
+        self.tags.iter().any(|x| matches!(x, DesireTag::Toxic))
+    }
+
+    /// # Is Active On
+    /// 
+    /// Whether the desire is needed on the day given.
+    /// 
+    /// Sporadic desires roll for it, Periodic desires check where the day 
+    /// falls in their cycle. All others are always active. If a desire has
+    /// multiple of these tags, all of them must agree.
+    pub fn is_active_on(&self, day: u64, rng: &mut impl Rng) -> bool {
+        self.tags.iter().all(|tag| match tag {
+            DesireTag::Sporadic(chance) => *chance <= 1 || 
+                rng.gen_range(0..*chance) == 0,
+            DesireTag::Periodic { offset, cycle } => *cycle == 0 || 
+                day % cycle == offset % cycle,
+            DesireTag::Toxic => true,
+        })
+    }
 }
 
 /// The tags a desire can be marked by, modifying how the desire is viewed.
@@ -492,11 +544,12 @@ pub enum DesireTag{
     /// satisfied, it instead increases their chance of sickness and death.
     Toxic,
     /// The desire is not needed every day, instead they only desire it
-    /// occasionally. How likely they are on any given ady is equal to
-    /// the value inside it.
+    /// occasionally. They have a 1 in N chance of needing it on any given
+    /// day, where N is the value inside it.
     Sporadic(u64),
     /// The item is not needed consistently, but instead periodically and
-    /// consistently. The value given is how often they need 
+    /// consistently. They need it every cycle days, starting offset days
+    /// into the cycle.
     Periodic { offset: u64, cycle: u64 },
 }
 
@@ -1,13 +1,23 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use barrage::{Sender, Receiver};
+use itertools::Itertools;
 
-use crate::{data_manager::DataManager, demographics::Demographics, objects::environmental_objects::market::MarketHistory};
+use crate::{constants::{FIRM_MARKET_PRICE_WEIGHT, FIRM_PRICE_STEP, 
+        FIRM_SELL_OUT_THRESHOLD, FIRM_UNSOLD_THRESHOLD, SALARY_HOURS_ADJUSTMENT,
+        SALARY_PAY_PERIOD, TIME_PRODUCT_ID, WORK_DAY_HOURS}, 
+    data_manager::DataManager, demographics::Demographics, 
+    objects::{data_objects::{item::Item, process::ProcessSectionTag, 
+            product::ProductTag},
+        environmental_objects::{labor_market::JobOpening, 
+            market::{ExchangeMode, MarketHistory}}}};
 
 use super::{seller::Seller, 
     buyer::Buyer, 
-    firm_job::FirmJob, 
-    actor::Actor, actor_message::{ActorMessage, ActorInfo, ActorType}};
+    firm_job::{FirmJob, WageType}, 
+    property::Property,
+    actor::{Actor, MessageBacklog}, actor_message::{ActorMessage, ActorInfo, ActorType, 
+        FirmEmployeeAction, FirmHierarchyAction, OfferResult}};
 
 /// Firms are the productive actors of our system.
 /// 
@@ -61,19 +71,1294 @@ pub struct Firm {
     pub ownership: Vec<FirmJob>,
     /// The prices of the products the firm sells.
     /// Stores the ID of the product and the price in AMV it seeks from
-    /// the market.
-    pub prices: HashSet<usize, f64>,
+    /// the market. Products without a price are sold at the market price.
+    pub prices: HashMap<usize, f64>,
+    /// The prices our parent firm has set for products we sell. These are 
+    /// used over our own, and aren't adjusted by our sales.
+    pub parent_prices: HashMap<usize, f64>,
     /// The property owned or otherwise managed by the firm.
     /// If the firm is not Disorganized or otherwise a distinct entity from
     /// the pop, this is where all of it's inputs and capital is stored.
-    pub property: HashSet<usize, f64>,
-    _firm_outputs: Vec<usize>,
+    /// 
+    /// Firms have no desires. Instead, their process_plan holds the 
+    /// iterations planned for the day, and the inputs and capital for it
+    /// are held in product reserve. What's unreserved is free to pay, 
+    /// trade, or sell.
+    pub property: Property,
+    /// The firm's profit over the last day, in AMV, what it sold less what
+    /// it bought and paid out.
+    pub profit: f64,
+    /// How much the firm's worth grew over the last day, in AMV. This is 
+    /// what it holds (at market prices) at the close, less what it held 
+    /// once the work day began, so what it made and kept counts too. See 
+    /// Firm::holdings_value. Firms losing worth lay off workers rather 
+    /// than hiring.
+    pub profitability: f64,
+    /// Messages for us which we haven't gotten to yet.
+    pub backlog: VecDeque<ActorMessage>,
+    /// Salesmen who came to us while we were busy, and the product they 
+    /// sell, waiting for us to hear them out once we're idle.
+    pub salesmen: VecDeque<(ActorInfo, usize)>,
+    /// The products the firm has made, and so offers for sale.
+    firm_outputs: Vec<usize>,
+    /// How much of each product we offered for sale today.
+    pub offered: HashMap<usize, f64>,
+    /// The hours (time) each employee (pop) gave us today.
+    pub hours_worked: HashMap<usize, f64>,
+    /// What each LossSharing employee (pop) handed over at the start of 
+    /// the day, besides their time. What's left of it is handed back 
+    /// before we finish, see Firm::return_contributions.
+    pub contributions: HashMap<usize, HashMap<Item, f64>>,
+    /// The units of output each employee's (pop's) job made today.
+    output_by_pop: HashMap<usize, f64>,
+    /// The bonus each employee's (pop's) skill gives the outputs of the 
+    /// processes they work. See Pop::skill_efficiency.
+    pub worker_efficiency: HashMap<usize, f64>,
+    /// The hours each salaried employee (pop) is paid for, moving slowly
+    /// toward what they actually work.
+    salary_hours: HashMap<usize, f64>,
+    /// The salary owed to each salaried employee (pop) since they were 
+    /// last paid, in AMV, and the days it's for.
+    accrued_salary: HashMap<usize, (f64, usize)>,
+    /// How many days the firm has run. Salaries are paid on every 
+    /// SALARY_PAY_PERIOD-th day.
+    pub day: usize,
+}
+
+/// # Paycheck
+/// 
+/// What an employee is paid in a day, as figured by Firm::payroll.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paycheck {
+    /// The employee (pop) paid.
+    pub pop: usize,
+    /// The goods paid, as (product, amount).
+    pub goods: Vec<(usize, f64)>,
+    /// What the pay is worth, in AMV. Negative for those sharing in a loss.
+    pub wage: f64,
+    /// How many days the pay covers.
+    pub period: usize,
 }
 
 impl Firm {
+    /// # New Firm
+    /// 
+    /// Creates a new, empty firm of the lowest rank, without any jobs, 
+    /// property, parent, or children.
+    /// 
+    /// The firm must have a name.
+    pub fn new(id: usize, name: String, variant_name: String, 
+    firm_kind: FirmKind, ownership_type: OwnershipStructure, 
+    profit_structure: ProfitStructure, 
+    organization_strucutre: OrganizationalStructure) -> Result<Self, String> {
+        if name.trim().is_empty() {
+            return Result::Err(String::from("Firm must have a name."));
+        }
+        let mut property = Property::new(vec![]);
+        property.is_disorganized = matches!(ownership_type, 
+            OwnershipStructure::SelfEmployed);
+        Ok(Self {
+            id,
+            name,
+            variant_name,
+            firm_kind,
+            firm_rank: FirmRank::Firm,
+            ownership_type,
+            profit_structure,
+            organization_strucutre,
+            children: vec![],
+            parent: None,
+            jobs: vec![],
+            management: vec![],
+            ownership: vec![],
+            prices: HashMap::new(),
+            parent_prices: HashMap::new(),
+            property,
+            profit: 0.0,
+            profitability: 0.0,
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new(),
+            firm_outputs: vec![],
+            offered: HashMap::new(),
+            hours_worked: HashMap::new(),
+            contributions: HashMap::new(),
+            output_by_pop: HashMap::new(),
+            worker_efficiency: HashMap::new(),
+            salary_hours: HashMap::new(),
+            accrued_salary: HashMap::new(),
+            day: 0,
+        })
+    }
+
+    /// # Branch
+    /// 
+    /// Opens a branch of this firm, a child of the lowest rank with the same
+    /// kind and structure, but without jobs or property, to act for us in 
+    /// another market. The variant name should say where it is.
+    /// 
+    /// The branch is not added to our children, that's up to the caller.
+    pub fn branch(&self, id: usize, variant_name: String) -> Firm {
+        let mut branch = Firm::new(id, self.name.clone(), variant_name, 
+            self.firm_kind, self.ownership_type.clone(), 
+            self.profit_structure.clone(), self.organization_strucutre.clone())
+            .expect("Parent firm has a name.");
+        branch.parent = Some(self.id);
+        branch
+    }
+
     pub fn get_name(&self) -> String {
         format!("{}({})", self.name, self.variant_name)
     }
+
+    /// # Openings
+    /// 
+    /// The firm's open jobs, best paying first. Firms losing worth don't 
+    /// hire.
+    pub fn openings(&self) -> Vec<JobOpening> {
+        if self.profitability < 0.0 {
+            return vec![];
+        }
+        JobOpening::of_jobs(self.id, &self.jobs)
+    }
+
+    /// # Layoffs
+    /// 
+    /// If the firm is losing worth, the share of the pop in each of it's 
+    /// jobs it lets go, by job index and pop. See WageType::layoff_fraction.
+    pub fn layoffs(&self) -> Vec<(usize, usize, f64)> {
+        if self.profitability >= 0.0 {
+            return vec![];
+        }
+        self.jobs.iter()
+            .enumerate()
+            .filter_map(|(index, job)| job.pop
+                .map(|pop| (index, pop, job.wage_type.layoff_fraction())))
+            .filter(|(_, _, fraction)| *fraction > 0.0)
+            .collect()
+    }
+
+    /// All of the firm's jobs, workers, then management, then ownership.
+    pub fn all_jobs(&self) -> impl Iterator<Item = &FirmJob> {
+        self.jobs.iter()
+            .chain(self.management.iter())
+            .chain(self.ownership.iter())
+    }
+
+    /// # Employees
+    /// 
+    /// The pops working for the firm and how they are paid, in the order of 
+    /// all_jobs.
+    pub fn employees(&self) -> Vec<(usize, WageType)> {
+        self.all_jobs()
+            .filter_map(|job| job.pop.map(|pop| (pop, job.wage_type)))
+            .collect()
+    }
+
+    /// The products the firm has made and offers for sale.
+    pub fn outputs(&self) -> &[usize] {
+        &self.firm_outputs
+    }
+
+    /// # Price Of
+    /// 
+    /// The AMV unit price the firm asks for a product. If it has not set a
+    /// price, it asks the market price.
+    pub fn price_of(&self, product: usize, history: &MarketHistory) -> f64 {
+        self.parent_prices.get(&product)
+            .or_else(|| self.prices.get(&product)).copied()
+            .unwrap_or_else(|| history.get_product_price(&product, 1.0))
+    }
+
+    /// Adds products to the firm's property, negative values remove them.
+    fn add_products(&mut self, products: &HashMap<usize, f64>) {
+        for (&product, &amount) in products.iter() {
+            self.property.unsafe_add_property(product, amount);
+        }
+    }
+
+    /// # Holdings Value
+    /// 
+    /// What everything we hold is worth at market prices, reserved or not,
+    /// except time, which is used up by the end of the day.
+    pub fn holdings_value(&self, history: &MarketHistory) -> f64 {
+        self.property.property.iter()
+            .filter(|(product, _)| **product != TIME_PRODUCT_ID)
+            .map(|(product, info)| info.total_property * 
+                history.get_product_price(product, 0.0))
+            .sum()
+    }
+
+    /// How much of a product we have which isn't reserved.
+    fn available(&self, product: usize) -> f64 {
+        self.property.property.get(&product)
+            .map(|x| x.available()).unwrap_or(0.0)
+    }
+
+    /// # Plan Processes
+    /// 
+    /// Plans the day's processes from the assignments of the jobs with a 
+    /// pop working them, recording the iterations in our property's 
+    /// process_plan and what they should take in and put out in it's 
+    /// product_expectations. Capital is not expected, as it isn't used up.
+    pub fn plan_processes(&mut self, data: &DataManager) {
+        let mut plan: HashMap<usize, f64> = HashMap::new();
+        for job in self.all_jobs().filter(|x| x.pop.is_some()) {
+            for (&process, assignment) in job.assignments.iter() {
+                if data.processes.contains_key(&process) {
+                    *plan.entry(process).or_insert(0.0) += assignment.iterations;
+                }
+            }
+        }
+        let mut expectations = HashMap::new();
+        for (process, iterations) in plan.iter() {
+            for part in data.processes[process].process_parts.iter() {
+                let sign = match part.part {
+                    ProcessSectionTag::Capital => continue,
+                    ProcessSectionTag::Input => -1.0,
+                    ProcessSectionTag::Output => 1.0,
+                };
+                if let Item::Product(id) = part.item {
+                    *expectations.entry(id).or_insert(0.0) += 
+                        sign * part.amount * iterations;
+                }
+            }
+        }
+        self.property.process_plan = plan;
+        self.property.product_expectations = expectations;
+    }
+
+    /// # Input Targets
+    /// 
+    /// How much of each product the firm needs to complete it's process 
+    /// plan, both inputs and capital. 
+    /// 
+    /// Time is not included, it comes from the employees, as are class and
+    /// want items, which cannot be bought directly.
+    pub fn input_targets(&self, data: &DataManager) -> HashMap<usize, f64> {
+        let mut result = HashMap::new();
+        for (process, iterations) in self.property.process_plan.iter() {
+            for part in data.processes[process].process_parts.iter()
+            .filter(|x| !x.part.is_output()) {
+                if let Item::Product(id) = part.item {
+                    if id != TIME_PRODUCT_ID {
+                        *result.entry(id).or_insert(0.0) += part.amount * iterations;
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// # Reserve Inputs
+    /// 
+    /// Releases all our reserves, then reserves what we have toward the 
+    /// inputs and capital of our process plan, time included. Whatever is
+    /// left unreserved is free to be paid, traded, or sold.
+    pub fn reserve_inputs(&mut self, data: &DataManager) {
+        for info in self.property.property.values_mut() {
+            info.reset_reserves();
+        }
+        let mut targets = self.input_targets(data);
+        for (process, iterations) in self.property.process_plan.iter() {
+            for part in data.processes[process].process_parts.iter()
+            .filter(|x| !x.part.is_output()) {
+                if part.item == Item::Product(TIME_PRODUCT_ID) {
+                    *targets.entry(TIME_PRODUCT_ID).or_insert(0.0) += 
+                        part.amount * iterations;
+                }
+            }
+        }
+        for (product, target) in targets {
+            if let Some(info) = self.property.property.get_mut(&product) {
+                info.shift_to_specific_reserve(target);
+            }
+        }
+    }
+
+    /// # Run Processes
+    /// 
+    /// Runs our process plan, up to the iterations planned, using what we
+    /// have reserved for it. Then, jobs with a pop working them but no
+    /// assignments run every process of their job, in job order, as much 
+    /// as they can with what's unreserved.
+    /// 
+    /// Inputs are consumed, capital is kept, and outputs are added to our 
+    /// property and noted as something we sell. Afterwards, we reserve 
+    /// for the plan again, keeping our capital and leftovers out of the 
+    /// day's trading.
+    /// 
+    /// The units of output made are credited to the pops whose jobs made 
+    /// them, planned processes being split by the iterations assigned. 
+    /// Their skill boosts the outputs by the same split.
+    /// 
+    /// Returns the products produced.
+    pub fn run_processes(&mut self, data: &DataManager) -> HashMap<usize, f64> {
+        let mut planned = vec![];
+        for (&process, &iterations) in self.property.process_plan.iter()
+        .sorted_by(|a, b| a.0.cmp(b.0)) {
+            if iterations <= 0.0 {
+                continue; // nothing planned, nothing to split.
+            }
+            let workers = self.all_jobs()
+                .filter_map(|job| match (job.pop, job.assignments.get(&process)) {
+                    (Some(pop), Some(assignment)) => 
+                        Some((pop, assignment.iterations / iterations)),
+                    _ => None,
+                })
+                .collect_vec();
+            planned.push((process, Some(iterations), workers));
+        }
+        for job in self.all_jobs()
+        .filter(|x| x.pop.is_some() && x.assignments.is_empty()) {
+            if let Some(job_info) = data.jobs.get(&job.job) {
+                let pop = job.pop.unwrap();
+                planned.extend(job_info.processes.iter()
+                    .map(|x| (*x, None, vec![(pop, 1.0)])));
+            }
+        }
+        self.output_by_pop.clear();
+        let mut produced = HashMap::new();
+        for (process, target, workers) in planned {
+            let process = match data.processes.get(&process) {
+                Some(process) => process,
+                None => continue,
+            };
+            let wants = self.property.want_store.iter()
+                .map(|(want, info)| (*want, info.total_current))
+                .collect();
+            // the skill of those working it, by their share of the work.
+            let efficiency = workers.iter()
+                .map(|(pop, share)| share * self.worker_efficiency.get(pop)
+                    .copied().unwrap_or(0.0))
+                .sum();
+            let results = process.do_process_with_property(&self.property.property, 
+                &wants, efficiency, target, false, data, target.is_some());
+            if results.iterations == 0.0 {
+                continue;
+            }
+            self.add_products(&results.input_output_products);
+            for (&want, &amount) in results.input_output_wants.iter() {
+                if amount < 0.0 {
+                    self.property.want_store.get_mut(&want)
+                        .expect("Want not found!").consume(-amount);
+                } else {
+                    self.property.add_want(want, &amount);
+                }
+            }
+            let mut made = 0.0;
+            for (&product, &amount) in results.input_output_products.iter()
+            .filter(|(_, amount)| **amount > 0.0) {
+                *produced.entry(product).or_insert(0.0) += amount;
+                made += amount;
+                if !self.firm_outputs.contains(&product) {
+                    self.firm_outputs.push(product);
+                }
+            }
+            for (pop, share) in workers {
+                *self.output_by_pop.entry(pop).or_insert(0.0) += made * share;
+            }
+        }
+        self.reserve_inputs(data);
+        produced
+    }
+
+    /// # Update Prices
+    /// 
+    /// Sets the price of everything we sell from how today's sales went
+    /// and the market price.
+    /// 
+    /// If we sold out (FIRM_SELL_OUT_THRESHOLD of what we offered) we 
+    /// raise our price by FIRM_PRICE_STEP, and if we sold less than 
+    /// FIRM_UNSOLD_THRESHOLD of it we lower it by as much. Either way, the
+    /// price then moves FIRM_MARKET_PRICE_WEIGHT of the way toward the 
+    /// market price, so we don't stray too far from what everyone else 
+    /// asks. Products we offered none of only drift toward the market.
+    /// 
+    /// Products our parent sets the price of are left alone.
+    pub fn update_prices(&mut self, history: &MarketHistory) {
+        for &product in self.firm_outputs.iter()
+        .filter(|x| !self.parent_prices.contains_key(x)) {
+            let current = self.price_of(product, history);
+            let offered = self.offered.get(&product).copied().unwrap_or(0.0);
+            let sold = self.property.property.get(&product)
+                .map(|x| x.spent).unwrap_or(0.0);
+            let mut price = current;
+            if offered > 0.0 {
+                if sold >= offered * FIRM_SELL_OUT_THRESHOLD {
+                    price *= 1.0 + FIRM_PRICE_STEP;
+                } else if sold < offered * FIRM_UNSOLD_THRESHOLD {
+                    price *= 1.0 - FIRM_PRICE_STEP;
+                }
+            }
+            let market = history.get_product_price(&product, current);
+            price += (market - price) * FIRM_MARKET_PRICE_WEIGHT;
+            self.prices.insert(product, price.max(0.0));
+        }
+    }
+
+    /// # Payroll
+    /// 
+    /// Figures out what each employee is paid today, based on the wage type
+    /// of their job. Wages are per worker per day, in AMV.
+    /// 
+    /// - Slaves are not paid, they are provided for instead.
+    /// - Daily and Contractor jobs are paid their wage for each 
+    ///   WORK_DAY_HOURS of time they gave us today.
+    /// - Productivity jobs are paid their wage for each unit of output their
+    ///   job made today.
+    /// - Salary jobs are paid for the hours they usually work, rather than 
+    ///   today's, which is owed daily but paid every SALARY_PAY_PERIOD days.
+    /// - LossSharing and ProfitSharing jobs split the day's profit, what 
+    ///   we made (at market prices) less what we spent and paid, equally.
+    ///   Should it be a loss, ProfitSharing jobs get nothing, while 
+    ///   LossSharing jobs take their share of it. As they hand everything
+    ///   over, their share is lost in what they gave us, so they're sent 
+    ///   nothing but the news.
+    /// 
+    /// Pay is taken from the job's wage units first, then from what the 
+    /// firm sells, then anything else it has unreserved (except time), at 
+    /// market prices and so long as the firm has it. Those we can't pay in 
+    /// full get what we have.
+    /// 
+    /// Salary records for those no longer working for us are dropped, as
+    /// they should have been paid on leaving. See Firm::settle_salary.
+    pub fn payroll(&mut self, produced: &HashMap<usize, f64>, 
+    history: &MarketHistory) -> Vec<Paycheck> {
+        let employed: HashSet<usize> = self.employees().into_iter()
+            .map(|(pop, _)| pop).collect();
+        self.salary_hours.retain(|pop, _| employed.contains(pop));
+        self.accrued_salary.retain(|pop, _| employed.contains(pop));
+        let mut checks = vec![];
+        let mut available: HashMap<usize, f64> = self.property.property.iter()
+            .map(|(product, info)| (*product, info.available()))
+            .collect();
+        let is_payday = self.day.is_multiple_of(SALARY_PAY_PERIOD);
+        let jobs = self.all_jobs()
+            .filter_map(|job| job.pop.map(|pop| 
+                (pop, job.wage_type, job.wage, job.wage_unit_priority.clone())))
+            .collect_vec();
+        let mut wages_paid = 0.0;
+        let mut sharers = vec![];
+        for (pop, wage_type, wage, priority) in jobs {
+            let hours = self.hours_worked.get(&pop).copied().unwrap_or(0.0);
+            let (owed, period) = match wage_type {
+                WageType::Slave => continue,
+                WageType::LossSharing |
+                WageType::ProfitSharing => {
+                    sharers.push((pop, wage_type, priority));
+                    continue;
+                },
+                WageType::Daily |
+                WageType::Contractor => (wage * hours / WORK_DAY_HOURS, 1),
+                WageType::Productivity => (wage * 
+                    self.output_by_pop.get(&pop).copied().unwrap_or(0.0), 1),
+                WageType::Salary => {
+                    let usual = self.salary_hours.entry(pop).or_insert(hours);
+                    *usual += (hours - *usual) * SALARY_HOURS_ADJUSTMENT;
+                    let owed = wage * *usual / WORK_DAY_HOURS;
+                    let accrued = self.accrued_salary.entry(pop)
+                        .or_insert((0.0, 0));
+                    accrued.0 += owed;
+                    accrued.1 += 1;
+                    if !is_payday {
+                        continue;
+                    }
+                    self.accrued_salary.remove(&pop).unwrap()
+                },
+            };
+            let (goods, paid) = self.pay_out(&mut available, &priority, owed, 
+                history);
+            wages_paid += paid;
+            checks.push(Paycheck { pop, goods, wage: paid, period });
+        }
+        if sharers.is_empty() {
+            return checks;
+        }
+        let made: f64 = produced.iter()
+            .map(|(product, amount)| amount * history.get_product_price(product, 0.0))
+            .sum();
+        let share = (self.profit - wages_paid + made) / sharers.len() as f64;
+        for (pop, wage_type, priority) in sharers {
+            let check = if share > 0.0 {
+                let (goods, paid) = self.pay_out(&mut available, &priority, 
+                    share, history);
+                Paycheck { pop, goods, wage: paid, period: 1 }
+            } else if wage_type == WageType::LossSharing {
+                Paycheck { pop, goods: vec![], wage: share, period: 1 }
+            } else {
+                Paycheck { pop, goods: vec![], wage: 0.0, period: 1 }
+            };
+            checks.push(check);
+        }
+        checks
+    }
+
+    /// The salary the pop has accrued since it's last payday, in AMV.
+    pub fn owed_salary(&self, pop: usize) -> f64 {
+        self.accrued_salary.get(&pop).map_or(0.0, |x| x.0)
+    }
+
+    /// # Settle Salary
+    /// 
+    /// Pays out the share given of the salary a pop has accrued since it's
+    /// last payday, for when that share of it's people leave us. The goods
+    /// are taken out of our property, as in Firm::payroll, and returned in
+    /// the paycheck for whoever left. The usual hours the pop is paid for
+    /// shrink by the same share, and are forgotten if everyone left.
+    /// 
+    /// Returns None if nothing was owed.
+    pub fn settle_salary(&mut self, pop: usize, share: f64, 
+    history: &MarketHistory) -> Option<Paycheck> {
+        let share = share.clamp(0.0, 1.0);
+        let (accrued, days) = self.accrued_salary.get(&pop).copied()?;
+        let owed = accrued * share;
+        if share == 1.0 {
+            self.accrued_salary.remove(&pop);
+            self.salary_hours.remove(&pop);
+        } else {
+            self.accrued_salary.insert(pop, (accrued - owed, days));
+            if let Some(hours) = self.salary_hours.get_mut(&pop) {
+                *hours *= 1.0 - share;
+            }
+        }
+        if owed <= 0.0 {
+            return None;
+        }
+        let priority = self.all_jobs()
+            .find(|job| job.pop == Some(pop))
+            .map(|job| job.wage_unit_priority.clone())
+            .unwrap_or_default();
+        let mut available: HashMap<usize, f64> = self.property.property.iter()
+            .map(|(product, info)| (*product, info.available()))
+            .collect();
+        let (goods, paid) = self.pay_out(&mut available, &priority, owed, history);
+        for (product, amount) in goods.iter() {
+            self.property.property.get_mut(product).expect("Paid with nothing!")
+                .remove(*amount);
+        }
+        Some(Paycheck { pop, goods, wage: paid, period: days })
+    }
+
+    /// Takes goods worth the AMV owed out of those available, in the order
+    /// given by Firm::payroll. Returns the goods taken and their AMV.
+    fn pay_out(&self, available: &mut HashMap<usize, f64>, priority: &[usize],
+    owed: f64, history: &MarketHistory) -> (Vec<(usize, f64)>, f64) {
+        let others = available.keys()
+            .filter(|x| **x != TIME_PRODUCT_ID && !self.firm_outputs.contains(x))
+            .copied().sorted().collect_vec();
+        let mut goods = vec![];
+        let mut remaining = owed;
+        for &product in priority.iter()
+        .chain(self.firm_outputs.iter())
+        .chain(others.iter()) {
+            if remaining <= 0.0 {
+                break;
+            }
+            let price = history.get_product_price(&product, 0.0);
+            let stock = available.get(&product).copied().unwrap_or(0.0);
+            if price <= 0.0 || stock <= 0.0 {
+                continue;
+            }
+            let amount = stock.min(remaining / price);
+            available.insert(product, stock - amount);
+            remaining -= amount * price;
+            goods.push((product, amount));
+        }
+        (goods, owed - remaining.max(0.0))
+    }
+
+    /// # Process Common Message
+    /// 
+    /// Deals with the messages we respond to at any point in the day, 
+    /// selling to buyers sent to us or through our asks on the order book,
+    /// recieving products and wants, and 
+    /// providing for employees who ask. Our children's reports are 
+    /// answered, and our parent's prices taken. Salesmen sent to us are 
+    /// heard out later, once we're idle.
+    /// 
+    /// Anything else is dropped.
+    fn process_common_msg(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory, 
+    msg: ActorMessage) {
+        match msg {
+            ActorMessage::FoundProduct { seller, buyer, product } 
+            if seller == self.actor_info() => {
+                self.standard_sell(rx, tx, history, product, buyer);
+            },
+            ActorMessage::FoundBuyer { seller, buyer, product } 
+            if buyer == self.actor_info() => {
+                self.salesmen.push_back((seller, product));
+            },
+            ActorMessage::SendProduct { product, amount, .. } => {
+                self.property.unsafe_add_property(product, amount);
+            },
+            ActorMessage::SendWant { want, amount, .. } |
+            ActorMessage::WantSplash { want, amount, .. } => {
+                self.property.add_want(want, &amount);
+            },
+            ActorMessage::OrderFilled { seller, product, quantity, currency, 
+            price, .. } if seller == self.actor_info() => {
+                // our resting ask was matched, hand over the goods.
+                let payment = quantity * price;
+                let exchange = HashMap::from([(product, -quantity), 
+                    (currency, payment)]);
+                self.add_products(&exchange);
+                self.property.record_exchange(exchange);
+                self.profit += payment * history.get_product_price(&currency, 1.0);
+            },
+            ActorMessage::EmployeeToFirm { employee, 
+            action: FirmEmployeeAction::RequestProvisions { item, amount }, .. } => {
+                self.provide(rx, tx, data, employee, item, amount);
+            },
+            ActorMessage::FirmToFirm { sender: child, 
+            action: FirmHierarchyAction::Report { product, .. }, .. } => {
+                let action = self.price_for_child(child, product);
+                self.push_message(rx, tx, ActorMessage::FirmToFirm { 
+                    sender: self.id, reciever: child, action });
+            },
+            ActorMessage::FirmToFirm { sender, 
+            action: FirmHierarchyAction::SetPrice { product, price }, .. } 
+            if self.parent == Some(sender) => {
+                self.parent_prices.insert(product, price);
+            },
+            _ => (),
+        }
+    }
+
+    /// # Price For Child
+    /// 
+    /// How we answer a child's report of a product it sells. Guilds and 
+    /// Franchises decide what their children charge, so they set the price
+    /// to their own, if they have one. Otherwise the price is left to the 
+    /// child.
+    pub fn price_for_child(&self, child: usize, product: usize) -> FirmHierarchyAction {
+        if !self.children.contains(&child) {
+            return FirmHierarchyAction::Acknowledge;
+        }
+        match (&self.organization_strucutre, self.prices.get(&product)) {
+            (OrganizationalStructure::Guild, Some(&price)) |
+            (OrganizationalStructure::Franchise, Some(&price)) => 
+                FirmHierarchyAction::SetPrice { product, price },
+            _ => FirmHierarchyAction::Acknowledge,
+        }
+    }
+
+    /// # Report To Parent
+    /// 
+    /// Tells our parent firm, wherever it is, what we sell and at what 
+    /// price, so it can set our prices if it's organized to.
+    pub fn report_to_parent(&mut self, rx: &Receiver<ActorMessage>, 
+    tx: &Sender<ActorMessage>, history: &MarketHistory) {
+        let parent = match self.parent {
+            Some(parent) => parent,
+            None => return,
+        };
+        for product in self.firm_outputs.clone() {
+            let price = self.price_of(product, history);
+            self.push_message(rx, tx, ActorMessage::FirmToFirm { sender: self.id, 
+                reciever: parent, action: FirmHierarchyAction::Report { product, price } });
+        }
+    }
+
+    /// # Provide
+    /// 
+    /// Sends what we have of an item to an employee who asked for it, up to
+    /// the amount asked for. Classes are provided from any products we have
+    /// in that class.
+    fn provide(&mut self, rx: &Receiver<ActorMessage>, tx: &Sender<ActorMessage>,
+    data: &DataManager, employee: ActorInfo, item: Item, amount: f64) {
+        for (given, amount) in self.property.provide(data, item, amount) {
+            let msg = match given {
+                Item::Want(want) => ActorMessage::SendWant { 
+                    sender: self.actor_info(), reciever: employee, want, amount },
+                Item::Product(product) => ActorMessage::SendProduct { 
+                    sender: self.actor_info(), reciever: employee, product, amount },
+                Item::Class(_) => continue, // classes are given as their products.
+            };
+            self.push_message(rx, tx, msg);
+        }
+    }
+
+    /// # Collect From Employees
+    /// 
+    /// Starts the work day, asking each employee for what they bring to 
+    /// work and waiting until all of it has arrived.
+    /// 
+    /// LossSharing employees are the firm as much as they work for it, so
+    /// they hand over everything, which we record as their contributions. 
+    /// Everyone else hands over their work time. The time each gives us is
+    /// recorded as the hours they worked.
+    pub fn collect_from_employees(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory) {
+        let mut waiting = HashMap::new();
+        for (pop, wage_type) in self.employees() {
+            let action = if wage_type == WageType::LossSharing {
+                FirmEmployeeAction::RequestEverything
+            } else {
+                FirmEmployeeAction::RequestTime
+            };
+            self.push_message(rx, tx, ActorMessage::FirmToEmployee { 
+                firm: self.actor_info(), employee: ActorInfo::Pop(pop), action });
+            waiting.insert(pop, action);
+        }
+        while !waiting.is_empty() {
+            let msg = self.next_message(rx);
+            match msg {
+                ActorMessage::SendProduct { sender: ActorInfo::Pop(pop), 
+                product: TIME_PRODUCT_ID, amount, .. } 
+                if waiting.contains_key(&pop) => {
+                    *self.hours_worked.entry(pop).or_insert(0.0) += amount;
+                    if waiting[&pop] == FirmEmployeeAction::RequestTime {
+                        waiting.remove(&pop);
+                    }
+                    self.process_common_msg(rx, tx, data, history, msg);
+                },
+                ActorMessage::SendProduct { sender: ActorInfo::Pop(pop), 
+                product, amount, .. } 
+                if waiting.get(&pop) == Some(&FirmEmployeeAction::RequestEverything) => {
+                    *self.contributions.entry(pop).or_default()
+                        .entry(Item::Product(product)).or_insert(0.0) += amount;
+                    self.process_common_msg(rx, tx, data, history, msg);
+                },
+                ActorMessage::SendWant { sender: ActorInfo::Pop(pop), 
+                want, amount, .. } 
+                if waiting.get(&pop) == Some(&FirmEmployeeAction::RequestEverything) => {
+                    *self.contributions.entry(pop).or_default()
+                        .entry(Item::Want(want)).or_insert(0.0) += amount;
+                    self.process_common_msg(rx, tx, data, history, msg);
+                },
+                ActorMessage::EmployeeToFirm { employee: ActorInfo::Pop(pop), 
+                action: FirmEmployeeAction::RequestSent, .. } => {
+                    waiting.remove(&pop);
+                },
+                _ => self.process_common_msg(rx, tx, data, history, msg),
+            }
+        }
+    }
+
+    /// # Return Contributions
+    /// 
+    /// Hands back to each LossSharing employee what's left of what they 
+    /// contributed today. Should we have less of something than was 
+    /// contributed, what we have is split between them by what each gave, 
+    /// so they share the loss.
+    /// 
+    /// Returns what was handed back is worth, at market prices.
+    pub fn return_contributions(&mut self, rx: &Receiver<ActorMessage>, 
+    tx: &Sender<ActorMessage>, history: &MarketHistory) -> f64 {
+        let mut given: HashMap<Item, f64> = HashMap::new();
+        for items in self.contributions.values() {
+            for (&item, &amount) in items.iter() {
+                *given.entry(item).or_insert(0.0) += amount;
+            }
+        }
+        let kept: HashMap<Item, f64> = given.iter()
+            .map(|(&item, &total)| {
+                let have = match item {
+                    Item::Product(product) => self.available(product),
+                    Item::Want(want) => self.property.want_store.get(&want)
+                        .map(|x| x.expendable()).unwrap_or(0.0),
+                    Item::Class(_) => 0.0,
+                };
+                (item, (have / total).min(1.0))
+            })
+            .collect();
+        let mut returned = 0.0;
+        for (pop, items) in std::mem::take(&mut self.contributions).into_iter()
+        .sorted_by(|a, b| a.0.cmp(&b.0)) {
+            let employee = ActorInfo::Pop(pop);
+            for (item, amount) in items.into_iter()
+            .sorted_by_key(|(item, _)| match item {
+                Item::Product(id) => (0, *id),
+                Item::Class(id) => (1, *id),
+                Item::Want(id) => (2, *id),
+            }) {
+                let amount = amount * kept[&item];
+                if amount <= 0.0 {
+                    continue;
+                }
+                let msg = match item {
+                    Item::Product(product) => {
+                        self.property.property.get_mut(&product)
+                            .expect("Product Not Found!").remove(amount);
+                        returned += amount * history.get_product_price(&product, 0.0);
+                        ActorMessage::SendProduct { sender: self.actor_info(), 
+                            reciever: employee, product, amount }
+                    },
+                    Item::Want(want) => {
+                        self.property.want_store.get_mut(&want)
+                            .expect("Want Not Found!").expend(amount);
+                        ActorMessage::SendWant { sender: self.actor_info(), 
+                            reciever: employee, want, amount }
+                    },
+                    Item::Class(_) => continue,
+                };
+                self.push_message(rx, tx, msg);
+            }
+        }
+        returned
+    }
+
+    /// # Buy Inputs
+    /// 
+    /// Tries to buy whatever we're missing to meet our input targets, in 
+    /// product order, reserving what we buy as it comes in.
+    pub fn buy_inputs(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory) {
+        self.reserve_inputs(data);
+        let targets = self.input_targets(data);
+        for (&product, &target) in targets.iter()
+        .sorted_by(|a, b| a.0.cmp(b.0)) {
+            let have = self.property.property.get(&product)
+                .map(|x| x.total_property).unwrap_or(0.0);
+            if target > have {
+                self.try_to_buy(rx, tx, data, history, product, target - have);
+                self.reserve_inputs(data);
+            }
+        }
+    }
+
+    /// # Try To Buy
+    /// 
+    /// Asks the market for a seller of the product, and tries to buy from 
+    /// them if one is found. Anything else sent to us in the meantime is
+    /// dealt with as normal.
+    fn try_to_buy(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory,
+    product: usize, target: f64) {
+        self.push_message(rx, tx, ActorMessage::FindProduct { product, 
+            sender: self.actor_info() });
+        loop {
+            let msg = self.next_message(rx);
+            match msg {
+                ActorMessage::ProductNotFound { product: missing, buyer } 
+                if missing == product && buyer == self.actor_info() => return,
+                ActorMessage::FoundProduct { buyer, seller, product: found } 
+                if found == product && buyer == self.actor_info() => {
+                    self.standard_buy(rx, tx, history, seller, target);
+                    return;
+                },
+                _ => self.process_common_msg(rx, tx, data, history, msg),
+            }
+        }
+    }
+
+    /// # Standard Buy
+    /// 
+    /// Makes a deal with the seller for up to the target of their product.
+    /// 
+    /// We pay with what we have unreserved, in product order, valued at 
+    /// market prices. If we can't cover the whole purchase, we 
+    /// buy as many whole units as we can afford.
+    /// 
+    /// Returns how much was bought.
+    pub fn standard_buy(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, history: &MarketHistory, seller: ActorInfo, 
+    target: f64) -> f64 {
+        let me = self.actor_info();
+        // only our seller starts the deal, anyone else waits their turn.
+        let mut others = vec![];
+        let result = loop {
+            let msg = self.specific_wait(rx, &[
+                ActorMessage::InStock { buyer: me, seller, product: 0, price: 0.0, 
+                    quantity: 0.0 },
+                ActorMessage::NotInStock { buyer: me, seller, product: 0 }
+            ]);
+            match msg {
+                ActorMessage::InStock { seller: from, .. } |
+                ActorMessage::NotInStock { seller: from, .. } if from == seller => break msg,
+                _ => others.push(msg),
+            }
+        };
+        for msg in others.into_iter().rev() {
+            self.backlog.push_front(msg);
+        }
+        let (product, price, stock) = match result {
+            ActorMessage::InStock { product, price, quantity, .. } => 
+                (product, price, quantity),
+            _ => return 0.0,
+        };
+        let mut quantity = target.min(stock);
+        // gather up what we can spare to pay for it.
+        let mut offer = HashMap::new();
+        let mut offer_amv = 0.0;
+        for (&offered, info) in self.property.property.iter()
+        .filter(|(id, _)| **id != TIME_PRODUCT_ID && **id != product)
+        .sorted_by(|a, b| a.0.cmp(b.0)) {
+            if offer_amv >= quantity * price {
+                break;
+            }
+            let unit_price = history.get_product_price(&offered, 0.0);
+            let spare = info.available();
+            if unit_price <= 0.0 || spare <= 0.0 {
+                continue;
+            }
+            let given = spare.min(((quantity * price - offer_amv) / unit_price).ceil());
+            offer.insert(offered, given);
+            offer_amv += given * unit_price;
+        }
+        if offer_amv < quantity * price {
+            quantity = (offer_amv / price).floor();
+        }
+        if quantity <= 0.0 {
+            self.push_message(rx, tx, ActorMessage::RejectPurchase { buyer: me, 
+                seller, product, price_opinion: OfferResult::TooExpensive });
+            return 0.0;
+        }
+        // send the offer
+        let mut followup = offer.len();
+        self.push_message(rx, tx, ActorMessage::BuyOffer { buyer: me, seller, 
+            product, price_opinion: OfferResult::Reasonable, quantity, followup });
+        for (&offer_product, &offer_quantity) in offer.iter()
+        .sorted_by(|a, b| a.0.cmp(b.0)) {
+            followup -= 1;
+            self.push_message(rx, tx, ActorMessage::BuyOfferFollowup { buyer: me, 
+                seller, product, offer_product, offer_quantity, followup });
+        }
+        let response = self.specific_wait(rx, &[
+            ActorMessage::SellerAcceptOfferAsIs { buyer: me, seller, product, 
+                offer_result: OfferResult::Incomplete },
+            ActorMessage::OfferAcceptedWithChange { buyer: me, seller, product, 
+                quantity: 0.0, followups: 0 },
+            ActorMessage::RejectOffer { buyer: me, seller, product },
+            ActorMessage::CloseDeal { buyer: me, seller, product },
+        ]);
+        let mut exchange: HashMap<usize, f64> = offer.iter()
+            .map(|(product, quantity)| (*product, -quantity))
+            .collect();
+        match response {
+            ActorMessage::SellerAcceptOfferAsIs { .. } => {
+                exchange.insert(product, quantity);
+            },
+            ActorMessage::OfferAcceptedWithChange { quantity, followups, .. } => {
+                exchange.insert(product, quantity);
+                for _ in 0..followups {
+                    if let ActorMessage::ChangeFollowup { return_product, 
+                    return_quantity, .. } = self.specific_wait(rx, &[
+                        ActorMessage::ChangeFollowup { buyer: me, seller, product, 
+                            return_product: 0, return_quantity: 0.0, followups: 0 }
+                    ]) {
+                        *exchange.entry(return_product).or_insert(0.0) += return_quantity;
+                        offer_amv -= return_quantity * 
+                            history.get_product_price(&return_product, 0.0);
+                    }
+                }
+            },
+            _ => return 0.0,
+        }
+        let bought = exchange[&product];
+        self.add_products(&exchange);
+        self.property.record_exchange(exchange);
+        self.property.record_purchase(product, offer_amv);
+        self.profit -= offer_amv;
+        self.push_message(rx, tx, ActorMessage::FinishDeal { buyer: me, seller, 
+            product });
+        bought
+    }
+
+    /// # Standard Sell
+    /// 
+    /// Sells to a buyer sent to us by the market.
+    /// 
+    /// We only sell what we make, in whole units, at our price. The buyer's 
+    /// offer is valued at market prices, and we accept it only if it covers
+    /// our price for what they ask for.
+    pub fn standard_sell(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, history: &MarketHistory, product: usize, 
+    buyer: ActorInfo) {
+        let me = self.actor_info();
+        let stock = if self.firm_outputs.contains(&product) {
+            self.available(product).floor()
+        } else { 0.0 };
+        if stock < 1.0 {
+            self.push_message(rx, tx, ActorMessage::NotInStock { buyer, seller: me, 
+                product });
+            return;
+        }
+        let price = self.price_of(product, history);
+        self.push_message(rx, tx, ActorMessage::InStock { buyer, seller: me, 
+            product, price, quantity: stock });
+        let response = self.specific_wait(rx, &[
+            ActorMessage::RejectPurchase { buyer, seller: me, product, 
+                price_opinion: OfferResult::Incomplete },
+            ActorMessage::BuyOffer { buyer, seller: me, product, 
+                price_opinion: OfferResult::Incomplete, quantity: 0.0, followup: 0 }
+        ]);
+        let (quantity, followups) = match response {
+            ActorMessage::BuyOffer { quantity, followup, .. } => (quantity, followup),
+            _ => return,
+        };
+        let mut exchange = HashMap::new();
+        for _ in 0..followups {
+            if let ActorMessage::BuyOfferFollowup { offer_product, offer_quantity, 
+            .. } = self.specific_wait(rx, &[
+                ActorMessage::BuyOfferFollowup { buyer, seller: me, product, 
+                    offer_product: 0, offer_quantity: 0.0, followup: 0 }
+            ]) {
+                *exchange.entry(offer_product).or_insert(0.0) += offer_quantity;
+            }
+        }
+        let offer_amv: f64 = exchange.iter()
+            .map(|(product, quantity)| history.get_product_price(product, 0.0) * quantity)
+            .sum();
+        if quantity > stock || offer_amv < quantity * price {
+            self.push_message(rx, tx, ActorMessage::RejectOffer { buyer, 
+                seller: me, product });
+            return;
+        }
+        exchange.insert(product, -quantity);
+        self.add_products(&exchange);
+        self.property.record_exchange(exchange);
+        self.profit += offer_amv;
+        self.push_message(rx, tx, ActorMessage::SellerAcceptOfferAsIs { buyer, 
+            seller: me, product, offer_result: OfferResult::Reasonable });
+    }
+
+    /// # Ship To Neighbors
+    /// 
+    /// Merchants and Traders look for goods which sell for more in a 
+    /// neighboring market than here, after the time it takes to move them,
+    /// picking the neighbor where each is worth the most. They buy what 
+    /// they can of those goods here, up to what they have the time to move
+    /// and that market doesn't already have for sale, then ship it along 
+    /// with what they already had.
+    /// 
+    /// Prices in our neighbors are asked of them via QueryPrice, and what
+    /// they have for sale via RequestTrade.
+    /// 
+    /// Returns what the goods shipped are worth here, as they are still 
+    /// ours while on the road.
+    pub fn ship_to_neighbors(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory) -> f64 {
+        let mut shipped = 0.0;
+        if !self.firm_kind.trades_between_markets() {
+            return shipped;
+        }
+        let time_price = history.get_product_price(&TIME_PRODUCT_ID, 0.0);
+        // what we have on hand, or could buy here.
+        let goods = self.property.property.keys().copied()
+            .chain(history.product_info.iter()
+                .filter(|(_, info)| info.offered > info.sold)
+                .map(|(&product, _)| product))
+            .filter(|product| *product != TIME_PRODUCT_ID)
+            .unique().sorted().collect_vec();
+        for product in goods {
+            let info = match data.products.get(&product)
+            .filter(|info| !info.tags.contains(&ProductTag::NonTransferrable)) {
+                Some(info) => info,
+                None => continue,
+            };
+            let here = history.get_product_price(&product, 0.0);
+            // find where it's worth the most after the trip, if anywhere.
+            let mut best: Option<(usize, f64, f64)> = None;
+            for (&market, connection) in history.neighbors.iter()
+            .sorted_by(|a, b| a.0.cmp(b.0)) {
+                let unit_cost = match connection.transport_cost(info, 1.0) {
+                    Some(cost) => cost,
+                    None => continue, // can't be moved there.
+                };
+                let margin = match self.query_price(rx, tx, market, product) {
+                    Some(there) => there - here - unit_cost * time_price,
+                    None => continue,
+                };
+                if margin > 0.0 && best.is_none_or(|(_, _, x)| margin > x) {
+                    best = Some((market, unit_cost, margin));
+                }
+            }
+            let (destination, unit_cost, _) = match best {
+                Some(best) => best,
+                None => continue, // not worth the trip.
+            };
+            let carried = if unit_cost > 0.0 {
+                (self.available(TIME_PRODUCT_ID) / unit_cost).floor()
+            } else { f64::INFINITY };
+            // no sense hauling what they already have.
+            let wanted = carried - self.request_trade(rx, tx, destination, 
+                product, carried);
+            let for_sale = history.product_info.get(&product)
+                .map(|info| info.offered - info.sold).unwrap_or(0.0);
+            let target = (wanted - self.available(product)).min(for_sale).floor();
+            if target >= 1.0 {
+                self.try_to_buy(rx, tx, data, history, product, target);
+            }
+            let quantity = self.available(product).min(wanted).floor();
+            if quantity >= 1.0 && 
+            self.ship(rx, tx, destination, product, quantity, quantity * unit_cost) {
+                shipped += quantity * here;
+            }
+        }
+        shipped
+    }
+
+    /// # Query Price
+    /// 
+    /// Asks another market for it's price of the product, waiting for the 
+    /// answer. None if they have no price for it.
+    fn query_price(&mut self, rx: &Receiver<ActorMessage>, tx: &Sender<ActorMessage>,
+    market: usize, product: usize) -> Option<f64> {
+        let me = self.actor_info();
+        self.push_message(rx, tx, ActorMessage::QueryPrice { sender: me, market, product });
+        match self.specific_wait(rx, &[
+            ActorMessage::PriceInfo { reciever: me, market, product, price: None }
+        ]) {
+            ActorMessage::PriceInfo { price, .. } => price,
+            _ => None,
+        }
+    }
+
+    /// # Request Trade
+    /// 
+    /// Asks another market how much of the quantity of a product it already
+    /// has for sale, waiting for the answer.
+    fn request_trade(&mut self, rx: &Receiver<ActorMessage>, tx: &Sender<ActorMessage>,
+    market: usize, product: usize, quantity: f64) -> f64 {
+        let me = self.actor_info();
+        self.push_message(rx, tx, ActorMessage::RequestTrade { sender: me, market, 
+            product, quantity });
+        match self.specific_wait(rx, &[
+            ActorMessage::TradeInfo { reciever: me, market, product, 
+                available: 0.0, price: None }
+        ]) {
+            ActorMessage::TradeInfo { available, .. } => available,
+            _ => 0.0,
+        }
+    }
+
+    /// # Ship
+    /// 
+    /// Takes the goods, and the time to move them, out of our property and 
+    /// asks the market to ship them. If it's sent we pay what it actually
+    /// cost, if refused we get everything back.
+    /// 
+    /// Returns whether it was sent.
+    fn ship(&mut self, rx: &Receiver<ActorMessage>, tx: &Sender<ActorMessage>,
+    destination: usize, product: usize, quantity: f64, cost: f64) -> bool {
+        let me = self.actor_info();
+        self.property.unsafe_add_property(product, -quantity);
+        self.property.unsafe_add_property(TIME_PRODUCT_ID, -cost);
+        self.push_message(rx, tx, ActorMessage::ShipGoods { sender: me, destination, 
+            product, quantity });
+        match self.specific_wait(rx, &[
+            ActorMessage::ShipmentSent { sender: me, destination, product, 
+                quantity, cost },
+            ActorMessage::ShipmentRefused { sender: me, destination, product }
+        ]) {
+            ActorMessage::ShipmentSent { cost: charged, .. } => {
+                self.property.unsafe_add_property(TIME_PRODUCT_ID, cost - charged);
+                true
+            },
+            _ => {
+                self.property.unsafe_add_property(product, quantity);
+                self.property.unsafe_add_property(TIME_PRODUCT_ID, cost);
+                false
+            }
+        }
+    }
+
+    /// # Recieve Shipment
+    /// 
+    /// Takes in goods shipped here, by us or our parent, which have arrived,
+    /// adding them to what we sell.
+    pub fn recieve_shipment(&mut self, product: usize, quantity: f64) {
+        self.property.unsafe_add_property(product, quantity);
+        if !self.firm_outputs.contains(&product) {
+            self.firm_outputs.push(product);
+        }
+    }
+
+    /// # Salesmanning
+    /// 
+    /// Goes out looking for buyers for what we make, one trip per output
+    /// with a whole unit left to sell, dealing with them via standard_sell.
+    /// 
+    /// Only done in bargaining markets, as in order book markets what we 
+    /// make already rests in the book.
+    pub fn salesmanning(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory) {
+        if history.exchange_mode != ExchangeMode::Bargaining {
+            return;
+        }
+        let me = self.actor_info();
+        for product in self.firm_outputs.clone() {
+            self.hear_out_salesmen(rx, tx, data, history);
+            if self.available(product) < 1.0 {
+                continue;
+            }
+            self.push_message(rx, tx, ActorMessage::FindBuyer { product, sender: me });
+            loop {
+                let msg = self.next_message(rx);
+                match msg {
+                    ActorMessage::BuyerNotFound { product: unsold, seller } 
+                    if unsold == product && seller == me => break,
+                    ActorMessage::FoundBuyer { seller, buyer, product: found } 
+                    if found == product && seller == me => {
+                        self.standard_sell(rx, tx, history, product, buyer);
+                        break;
+                    },
+                    _ => self.process_common_msg(rx, tx, data, history, msg),
+                }
+            }
+        }
+    }
+
+    /// # Hear Out Salesmen
+    /// 
+    /// Deals with each salesman who has come to us, including any still 
+    /// waiting in the backlog, buying only what we still need for our inputs.
+    /// 
+    /// Only called while we're idle, so a salesman's deal never nests 
+    /// inside another.
+    pub fn hear_out_salesmen(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, history: &MarketHistory) {
+        let me = self.actor_info();
+        loop {
+            self.msg_catchup(rx);
+            let (found, rest): (Vec<_>, Vec<_>) = self.backlog.drain(..)
+                .partition(|msg| matches!(msg, 
+                    ActorMessage::FoundBuyer { buyer, .. } if *buyer == me));
+            self.backlog = rest.into();
+            for msg in found {
+                if let ActorMessage::FoundBuyer { seller, product, .. } = msg {
+                    self.salesmen.push_back((seller, product));
+                }
+            }
+            let (seller, product) = match self.salesmen.pop_front() {
+                Some(salesman) => salesman,
+                None => return,
+            };
+            let have = self.property.property.get(&product)
+                .map(|x| x.total_property).unwrap_or(0.0);
+            let target = self.input_targets(data).get(&product)
+                .map_or(0.0, |target| (target - have).max(0.0));
+            self.standard_buy(rx, tx, history, seller, target);
+            self.reserve_inputs(data);
+        }
+    }
+
+    /// # Pay Workers
+    /// 
+    /// Sends out today's payroll, along with a pay stub to each employee 
+    /// paid so they know what it was worth, and counts it against our 
+    /// profit.
+    pub fn pay_workers(&mut self, rx: &Receiver<ActorMessage>, 
+    tx: &Sender<ActorMessage>, history: &MarketHistory, 
+    produced: &HashMap<usize, f64>) {
+        for check in self.payroll(produced, history) {
+            let employee = ActorInfo::Pop(check.pop);
+            for (product, amount) in check.goods {
+                self.property.property.get_mut(&product).expect("Paid with nothing!")
+                    .remove(amount);
+                self.profit -= amount * history.get_product_price(&product, 0.0);
+                self.push_message(rx, tx, ActorMessage::SendProduct { 
+                    sender: self.actor_info(), reciever: employee, 
+                    product, amount });
+            }
+            self.push_message(rx, tx, ActorMessage::FirmToEmployee { 
+                firm: self.actor_info(), employee, 
+                action: FirmEmployeeAction::PayStub { wage: check.wage, 
+                    period: check.period } });
+        }
+    }
 }
 
 impl Seller for Firm {
@@ -90,6 +1375,12 @@ impl Seller for Firm {
     }
 }
 
+impl MessageBacklog for Firm {
+    fn backlog(&mut self) -> &mut VecDeque<ActorMessage> {
+        &mut self.backlog
+    }
+}
+
 impl Buyer for Firm {}
 
 impl Actor for Firm {
@@ -98,37 +1389,104 @@ impl Actor for Firm {
     /// First waits to get the all clear on startup to enusre all other actors
     /// in it's market are running.
     /// 
-    /// Second, it puts up what it offers today up for sale.
+    /// Second, it collects the time (and for LossSharing employees, 
+    /// everything) it's employees bring to work, then lets them go to 
+    /// their free time.
+    /// 
+    /// Third, it plans it's processes from it's assignments, reserving what
+    /// it has and buying what it needs for them, then does those processes,
+    /// and pays it's workers from the results. Merchants and Traders then
+    /// ship whatever is worth more in a neighboring market there. Child 
+    /// firms report what they sell to their parent, and LossSharing 
+    /// employees are handed back what's left of what they gave us.
+    /// Anyone sent to us to buy in the meantime is sold to.
     /// 
-    /// Third, rotate between Buying what we need to work,
-    /// doing processes for the day, and completing transactions to (don't 
-    /// block on checking for selling, just go to the end of the current 
-    /// queue) sell what we're offering. Continue until all our processes 
-    /// are done, and we've bought (or failed to) buy everything we wanted.
+    /// Fourth, it puts up what it makes for sale, and goes salesmanning
+    /// for buyers of what's left.
     /// 
-    /// Fourth, continue checking and completing selling, while also 
-    /// cogitating with remaining time and labor. This means doing any
+    /// TODO Cogitating goes here, using remaining time and labor for any
     /// market research, corporate espionage, advanced calculation,  
-    /// business organization/messaging or the like. Complete this until 
-    /// all cogitating time is out.
+    /// business organization/messaging or the like.
     /// 
     /// Lastly, send out an ActorMessage::Finished to the market and enter 
-    /// a holding pattern, completing sales remaining and observing for 
-    /// messages.
+    /// a holding pattern, completing sales remaining, hearing out salesmen,
+    /// providing for employees who ask, answering our children's reports,
+    /// and observing for messages.
     /// 
-    /// Once we get the AllFinished message, complete any remaining cleanup, 
-    /// and close out.
+    /// Once we get the AllFinished message, we close out, with our profit 
+    /// and profitability recording how the day went, and our prices 
+    /// updated from our sales.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if it recieves any message before ActorMessage::StartDay.
     fn run_market_day(&mut self, 
         sender: &mut Sender<ActorMessage>,
-        _reciever: &mut Receiver<ActorMessage>,
-        _data: &DataManager,
+        reciever: &mut Receiver<ActorMessage>,
+        data: &DataManager,
         _demos: &Demographics,
-        _history: &MarketHistory) {
-        sender.send(ActorMessage::Finished { sender: self.actor_info() })
-            .expect("Channel Closed Unexpectedly!");
+        history: &MarketHistory) {
+        self.profit = 0.0;
+        self.day += 1;
+        self.offered.clear();
+        self.hours_worked.clear();
+        for info in self.property.property.values_mut() {
+            info.start_of_day();
+        }
+        match reciever.recv().expect("Channel Broke.") {
+            ActorMessage::StartDay => (),
+            _ => panic!("Firm Recieved something before Day Start. Don't do something before the day starts.")
+        }
+        // the work day
+        self.collect_from_employees(reciever, sender, data, history);
+        // what we're worth once the work day begins, to judge the day by.
+        let opening = self.holdings_value(history);
+        for (pop, _) in self.employees() {
+            self.push_message(reciever, sender, ActorMessage::FirmToEmployee { 
+                firm: self.actor_info(), employee: ActorInfo::Pop(pop), 
+                action: FirmEmployeeAction::WorkDayEnded });
+        }
+        // plan, buy, work, and pay
+        self.plan_processes(data);
+        self.buy_inputs(reciever, sender, data, history);
+        let produced = self.run_processes(data);
+        self.pay_workers(reciever, sender, history, &produced);
+        // merchants send what's worth more elsewhere on it's way.
+        let shipped = self.ship_to_neighbors(reciever, sender, data, history);
+        // let our parent know what we sell, it may set our prices.
+        self.report_to_parent(reciever, sender, history);
+        // hand back what's left of what our LossSharing employees gave.
+        let returned = self.return_contributions(reciever, sender, history);
+        // offer what we've got
+        for product in self.firm_outputs.clone() {
+            let quantity = self.available(product).floor();
+            if quantity >= 1.0 {
+                let amv = self.price_of(product, history);
+                self.offered.insert(product, quantity);
+                self.push_message(reciever, sender, ActorMessage::SellOrder { 
+                    sender: self.actor_info(), product, quantity, amv });
+            }
+        }
+        // go looking for buyers for what's left.
+        self.salesmanning(reciever, sender, data, history);
         // TODO idea, Firms hire retailers who handle the details of sales and then report their
         // results back to here. They are on separate threads. THis is a bad, crazy idea, but fuckit
         // it may just work.
+        self.push_message(reciever, sender, ActorMessage::Finished { 
+            sender: self.actor_info() });
+        loop {
+            self.hear_out_salesmen(reciever, sender, data, history);
+            let msg = self.next_message(reciever);
+            if let ActorMessage::AllFinished = msg {
+                break;
+            }
+            self.process_common_msg(reciever, sender, data, history, msg);
+        }
+        // close out, judging the day and setting tomorrow's prices from 
+        // today's sales.
+        self.profitability = self.holdings_value(history) + shipped + returned 
+            - opening;
+        self.update_prices(history);
     }
 }
 
@@ -162,7 +1520,7 @@ pub enum FirmRank {
 /// 
 /// This defines the overarching logic for how the firm will function, as well
 /// as enable or disable certain features.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FirmKind {
     /// The default or generic option, for those with little or no special
     /// logic.
@@ -202,8 +1560,15 @@ pub enum FirmKind {
     Military,
 }
 
+impl FirmKind {
+    /// Whether firms of this kind may ship goods between markets.
+    pub fn trades_between_markets(&self) -> bool {
+        matches!(self, FirmKind::Merchant | FirmKind::Trader)
+    }
+}
+
 /// How the profits of the firm are distributed.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ProfitStructure {
     /// The profits are distributed equally to all who own, work, or otherwise
     /// are attached to the firm. This is often for LossSharing, Disorganized,
@@ -223,7 +1588,7 @@ pub enum ProfitStructure {
 /// How the firm's ownership is structured.
 /// 
 /// Defines some features available to the firm.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum OwnershipStructure {
     /// It is not a structly organized firm, but instead a collection of
     /// small firms that are not working together. Think family farms.
@@ -360,7 +1725,7 @@ pub enum OwnershipStructure {
 
 /// An enum which defines how a firm organizes itself and it's children,
 /// as well as how tightly it and it's children are bound together.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum OrganizationalStructure {
     /// The firm is not organized at all, it is a collection of small
     /// business in a market.
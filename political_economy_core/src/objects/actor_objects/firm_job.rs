@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::constants::LAYOFF_FRACTION;
 
 /// A job stored in a firm.
 /// 
@@ -10,18 +11,27 @@ pub struct FirmJob {
     pub job: usize,
     /// The type of wage they recieve.
     pub wage_type: WageType,
-    /// the value of the wage given in AMV.
+    /// the value of the wage given in AMV, per worker per day, or per unit
+    /// of output for Productivity jobs. See Firm::payroll.
     pub wage: f64,
     /// The unit(s) of the wage,
     pub wage_unit_priority: Vec<usize>,
-    /// The exact assignments of the job.
+    /// The exact assignments of the job, by process (id). These are the 
+    /// processes the firm plans and buys inputs for.
     pub assignments: HashMap<usize, AssignmentInfo>,
-    /// The pop which is in this firm job.
-    pub pop: usize,
+    /// The pop which is in this firm job. None if the job is open.
+    pub pop: Option<usize>,
+}
+
+impl FirmJob {
+    /// Whether the job is open, with no pop working it.
+    pub fn is_open(&self) -> bool {
+        self.pop.is_none()
+    }
 }
 
 /// What kind of wage types are available for jobs.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WageType {
     /// The Pop does not recieve a wage, cannot find a new job, can be 
     /// bought or sold as property, and are fed by resources gathered by the
@@ -48,11 +58,41 @@ pub enum WageType {
     ProfitSharing
 }
 
+impl WageType {
+    /// # Can Change Jobs
+    /// 
+    /// Whether workers with this wage type may leave for another job. Only
+    /// slaves cannot.
+    pub fn can_change_jobs(&self) -> bool {
+        !matches!(self, WageType::Slave)
+    }
+
+    /// # Layoff Fraction
+    /// 
+    /// How much of the workers with this wage type are laid off each day
+    /// the firm loses money.
+    /// 
+    /// Slaves are sold rather than fired, and those sharing in the profits 
+    /// (or losses) are owners, so none of them are laid off. Contractors 
+    /// are let go twice as readily as everyone else.
+    pub fn layoff_fraction(&self) -> f64 {
+        match self {
+            WageType::Slave |
+            WageType::LossSharing |
+            WageType::ProfitSharing => 0.0,
+            WageType::Contractor => LAYOFF_FRACTION * 2.0,
+            WageType::Productivity |
+            WageType::Daily |
+            WageType::Salary => LAYOFF_FRACTION,
+        }
+    }
+}
+
 /// Assignment Information for Firm Jobs to record and upkeep their work.
 #[derive(Debug)]
 pub struct AssignmentInfo {
     /// how many iterations are being attempted.
-    _iterations: f64,
+    pub iterations: f64,
     /// how much progress is leftover from yesterday.
     _progress: f64,
 }
@@ -60,7 +100,7 @@ pub struct AssignmentInfo {
 impl AssignmentInfo {
     pub fn new(iterations: f64, progress: f64) -> Self { 
         Self { 
-            _iterations: iterations, 
+            iterations, 
             _progress: progress 
         }
     }
@@ -1,8 +1,10 @@
+use std::collections::VecDeque;
+
 use barrage::{Receiver, Sender};
 
-use crate::{data_manager::DataManager, demographics::Demographics, objects::environmental_objects::market::MarketHistory};
+use crate::{data_manager::DataManager, demographics::Demographics, objects::{data_objects::item::Item, environmental_objects::market::MarketHistory}};
 
-use super::{seller::Seller, actor_message::{ActorType, ActorInfo, ActorMessage}, buyer::Buyer, actor::Actor};
+use super::{seller::Seller, actor_message::{ActorType, ActorInfo, ActorMessage, FirmEmployeeAction}, buyer::Buyer, actor::{Actor, MessageBacklog}, property::Property};
 
 
 
@@ -14,10 +16,31 @@ pub struct Institution {
     pub id: usize,
     pub name: String,
     pub variant_name: String,
+    /// The goods and wants the institution holds, used to provide for the
+    /// drones it owns.
+    pub property: Property,
+    /// Messages for us which came while we were busy.
+    pub backlog: VecDeque<ActorMessage>,
 }
 
 impl Institution {
-
+    /// # Provide
+    /// 
+    /// Sends what we have of an item to a drone we own which asked for it, 
+    /// up to the amount asked for.
+    fn provide(&mut self, rx: &Receiver<ActorMessage>, tx: &Sender<ActorMessage>,
+    data: &DataManager, drone: ActorInfo, item: Item, amount: f64) {
+        for (given, amount) in self.property.provide(data, item, amount) {
+            let msg = match given {
+                Item::Want(want) => ActorMessage::SendWant { 
+                    sender: self.actor_info(), reciever: drone, want, amount },
+                Item::Product(product) => ActorMessage::SendProduct { 
+                    sender: self.actor_info(), reciever: drone, product, amount },
+                Item::Class(_) => continue, // classes are given as their products.
+            };
+            self.push_message(rx, tx, msg);
+        }
+    }
 }
 
 impl Seller for Institution {
@@ -34,6 +57,12 @@ impl Seller for Institution {
     }
 }
 
+impl MessageBacklog for Institution {
+    fn backlog(&mut self) -> &mut VecDeque<ActorMessage> {
+        &mut self.backlog
+    }
+}
+
 impl Buyer for Institution {
     
 }
@@ -41,18 +70,38 @@ impl Buyer for Institution {
 impl Actor for Institution {
     /// Run Market Day for Institution.
     /// 
-    /// This is a placeholder. Currently it just sends Finished, and ends
-    /// it there.
+    /// This is mostly a placeholder. Institutions do nothing of their own 
+    /// yet, so they send Finished at the start of the day, then provide for
+    /// the drones they own, recieving goods and wants sent to them, until 
+    /// the day ends.
     fn run_market_day(&mut self, 
         sender: &mut Sender<ActorMessage>,
-        _reciever: &mut Receiver<ActorMessage>,
-        _data: &DataManager,
+        reciever: &mut Receiver<ActorMessage>,
+        data: &DataManager,
         _demos: &Demographics,
         _history: &MarketHistory) {
         // TODO this function needs to be completed
-        // Send finished to keep things running, then gtfo.
-        sender.send(ActorMessage::Finished { 
-            sender: self.actor_info() 
-        }).expect("Failure!");
+        match reciever.recv().expect("Channel Broke.") {
+            ActorMessage::StartDay => (),
+            _ => panic!("Institution Recieved something before Day Start. Don't do something before the day starts.")
+        }
+        self.push_message(reciever, sender, ActorMessage::Finished { 
+            sender: self.actor_info() });
+        loop {
+            match self.next_message(reciever) {
+                ActorMessage::AllFinished => break,
+                ActorMessage::EmployeeToFirm { employee, 
+                action: FirmEmployeeAction::RequestProvisions { item, amount }, .. } => {
+                    self.provide(reciever, sender, data, employee, item, amount);
+                },
+                ActorMessage::SendProduct { product, amount, .. } => {
+                    self.property.unsafe_add_property(product, amount);
+                },
+                ActorMessage::SendWant { want, amount, .. } => {
+                    self.property.add_want(want, &amount);
+                },
+                _ => (),
+            }
+        }
     }
 }
\ No newline at end of file
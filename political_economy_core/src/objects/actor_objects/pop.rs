@@ -7,14 +7,17 @@ use std::{collections::{HashMap, HashSet, VecDeque}, fmt::Debug};
 
 use barrage::{Sender, Receiver};
 use itertools::Itertools;
+use rand::Rng;
 
 use crate::{
-    constants::{self, ACP_MAX_HARD_REDUCTION_FACTOR, ACP_MAX_SOFT_REDUCTION_FACTOR, ACP_MIN_REDUCTION_FACTOR, OVERSPEND_THRESHOLD, SHOPPING_TIME_PRODUCT_ID, TIME_PRODUCT_ID}, 
+    constants::{self, ACP_MAX_HARD_REDUCTION_FACTOR, ACP_MAX_SOFT_REDUCTION_FACTOR, ACP_MIN_REDUCTION_FACTOR, DAYS_PER_YEAR, JOB_SWITCH_FRACTION, MAX_SKILL_LEVEL, MOOD_ADJUSTMENT_RATE, MOOD_COMPARISON_WEIGHT, MOOD_EXPECTATION_WEIGHT, MOOD_JOB_SEARCH_THRESHOLD, MOOD_PRODUCTIVITY_EFFECT, MOOD_TREND_WEIGHT, MOOD_UNREST_THRESHOLD, OVERSPEND_THRESHOLD, SHOPPING_TIME_PRODUCT_ID, SKILL_EFFICIENCY_PER_LEVEL, SKILL_LEARNING_RATE, STARVATION_MORTALITY_RATE, TIME_PRODUCT_ID, TOXIC_MORTALITY_RATE, WAGE_ESTIMATE_ADJUSTMENT}, 
     data_manager::DataManager, 
     demographics::Demographics, 
     objects::{
+        actor_objects::desire::Desire,
         actor_objects::property::{
             DesireCoord,
+            Hold,
             TieredValue
         },
         data_objects::{
@@ -22,18 +25,26 @@ use crate::{
             product::ProductTag,
             want_info::WantInfo
         },
-        environmental_objects::market::MarketHistory,
+        environmental_objects::{labor_market::JobOpening, 
+            market::{ExchangeMode, MarketHistory}, migration::MarketAppeal},
+        demographic_objects::{
+            conversion::{ConversionKind, ConversionPressure, ConversionPressures},
+            ideology_spread::IdeologyAppeal,
+            reproduction::Clutch
+        },
         actor_objects::buyer::Buyer,
-        demographic_objects::pop_breakdown_table::PopBreakdownTable
+        demographic_objects::pop_breakdown_table::{PBRow, PopBreakdownTable}
     }
 };
 
 use super::{
-    actor::Actor, 
+    actor::{Actor, MessageBacklog}, 
     actor_message::{
         ActorInfo, 
         ActorMessage,
         ActorType, 
+        AuctionKind,
+        AuctionTerms,
         FirmEmployeeAction, 
         OfferResult
     }, 
@@ -57,16 +68,20 @@ pub struct Pop {
     pub id: usize,
     /// The job of the pop.
     pub job: usize,
-    /// Where the pop works.
-    pub firm: usize,
+    /// Where the pop works. None if they are not employed by any firm.
+    pub firm: Option<usize>,
+    /// Who owns the pop, should they be drones owned by someone other than
+    /// the firm they work for, such as an Institution. If None, their firm
+    /// (if any) owns them.
+    pub owner: Option<ActorInfo>,
     /// Which market they are in
     pub market: usize,
     /// The skill the pop uses.
-    // pub skill: usize,
+    pub skill: usize,
     /// The lower bound of their skill level.
-    // pub lower_skill_level: f64,
+    pub lower_skill_level: f64,
     /// the upper bound of their skill level spread.
-    // pub higher_skill_level: f64,
+    pub higher_skill_level: f64,
     /// The total desires and property of the pop.
     ///
     /// TODO Food For Thought. We include 2 infinite desires in all pops, wealth and Leisure, which act as sinks and help us balance our buy priorities. More thought is needed.
@@ -77,7 +92,11 @@ pub struct Pop {
     /// Whether the pop is selling or not.
     pub is_selling: bool,
 
-    // todo Mood related stuff goes here.
+    /// How the pop feels about their situation, from -1 (miserable) to 1 
+    /// (content), with 0 being indifferent.
+    /// 
+    /// This is updated after each market day, see Pop::update_mood.
+    pub mood: f64,
 
     /// The current total satisfaction of the pop.
     /// 
@@ -93,52 +112,66 @@ pub struct Pop {
     /// This updates after free_time().
     pub hypo_change: TieredValue,
 
+    /// Eggs laid by the pop which have yet to hatch.
+    pub clutches: Vec<Clutch>,
+
     /// Backlogs of messages, to help keep things clear.
     pub backlog: VecDeque<ActorMessage>,
+    /// Salesmen who came to us while we were busy, waiting for us to hear 
+    /// them out once we're idle.
+    pub salesmen: VecDeque<ActorInfo>,
 }
 
 // TODO #66 issue Alter to make testing easier through Inverting functions which depend on message passing. In particular, free_time, shopping_loop, try_to_buy, and standard_buy
 impl Pop {
     /// Takes the current population table, and updates desires to match the population
-    /// breakdown. This is a hard reset, so is advised to call only as needed.
-    ///
-    /// Does not take sub-groups of species, culture, ideology into account currently.
-    /// This will need to be updated when those are implemented.
-    pub fn update_desires(&mut self, demos: Demographics) {
-        // TODO when subgroups are added to these items, this will need to be updated to take them into account.
-        self.property.clear_desires();
-        // add in each species desires
-        for row in self.breakdown_table.species_makeup().iter() {
-            let species = demos.species.get(row.0).expect("Species Id Not Found!");
-            for desire in species.desires.iter() {
-                let upped_desire = desire.create_multiple(*row.1);
-                self.property.add_desire(&upped_desire);
-            }
-        }
-        // placeholder for civilization
-        // add in culture desires
-        for row in self.breakdown_table.culture_makeup().iter() {
-            if let Some(id) = row.0 {
-                let culture = demos.cultures.get(id).expect("Culture Id Not Found!");
-                for desire in culture.desires.iter() {
-                    let upped_desire = desire.create_multiple(*row.1);
-                    self.property.add_desire(&upped_desire);
+    /// breakdown.
+    ///
+    /// Each row's desires (species, cohort, subtype, culture, and ideology)
+    /// are multiplied by the row's count and combined. Desires we already
+    /// have keep their satisfaction, those no longer wanted are removed.
+    /// 
+    /// For small changes to the table, prefer add_row_desires and 
+    /// remove_row_desires.
+    pub fn update_desires(&mut self, demos: &Demographics) {
+        // get what our desires should be.
+        let mut target: Vec<Desire> = vec![];
+        for row in self.breakdown_table.table.iter() {
+            for desire in demos.row_desires(row).iter() {
+                let upped_desire = desire.create_multiple(row.count);
+                if let Some(existing) = target.iter_mut()
+                .find(|x| x.is_match(&upped_desire)) {
+                    existing.amount += upped_desire.amount;
+                } else {
+                    target.push(upped_desire);
                 }
             }
         }
+        // then update our existing desires to match, keeping satisfaction.
+        self.property.replace_desires(target);
+    }
 
-        // add in ideology desires
-        for row in self.breakdown_table.ideology_makeup().iter() {
-            if let Some(id) = row.0 {
-                let ideology = demos.ideology.get(id).expect("Ideology Id Not Found!");
-                for desire in ideology.desires.iter() {
-                    let upped_desire = desire.create_multiple(*row.1);
-                    self.property.add_desire(&upped_desire);
-                }
-            }
+    /// # Add Row Desires
+    /// 
+    /// Adds the desires of the people in the row to our own. Call when 
+    /// people are added to our breakdown table.
+    pub fn add_row_desires(&mut self, demos: &Demographics, row: &PBRow) {
+        for desire in demos.row_desires(row).iter() {
+            self.property.add_desire(&desire.create_multiple(row.count));
         }
+        self.property.is_sifted = false;
+    }
 
-        // add in movements
+    /// # Remove Row Desires
+    /// 
+    /// Removes the desires of the people in the row from our own. Call when
+    /// people are removed from our breakdown table.
+    /// 
+    /// Desires which are reduced to nothing are removed.
+    pub fn remove_row_desires(&mut self, demos: &Demographics, row: &PBRow) {
+        for desire in demos.row_desires(row).iter() {
+            self.property.remove_desire(&desire.create_multiple(row.count));
+        }
     }
 
     /// Get's an automatically generated name for the pop group.
@@ -146,7 +179,11 @@ impl Pop {
     /// TODO update to pass in data from elsewhere to get more useful names.
     /// Possibly add in an option no name them specially.
     pub fn id_name(&self) -> String {
-        format!("Job:{}|Firm:{}|Market:{}", self.job, self.firm, self.market)
+        let firm = match self.firm {
+            Some(firm) => firm.to_string(),
+            None => String::from("None"),
+        };
+        format!("Job:{}|Firm:{}|Market:{}", self.job, firm, self.market)
     }
 
     /// Get's the total number of people in this pop.
@@ -154,59 +191,667 @@ impl Pop {
         self.breakdown_table.total
     }
 
-    /// A helper function to push a message to the market.
-    /// Safely pushes without blocking.
-    ///
-    /// Tries to send, if fails it reads one message, and if it's for us, puts it on the
-    /// backlog. Then tries again, until it succeeds or the channel breaks.
-    ///
-    /// ## Panics
-    ///
-    /// If the send fails due to a disconnect.
-    pub fn push_message(&mut self, rx: &Receiver<ActorMessage>, tx: &Sender<ActorMessage>,
-    msg: ActorMessage) {
-        loop {
-            // try to clear out prior msgs before sending.
-            self.msg_catchup(rx);
-            let result = tx.try_send(msg);
-            if let Ok(_) = result {
-                break; // if message got sent out, break.
+    /// # Population Change
+    /// 
+    /// Applies births and deaths to each row of our breakdown table over 
+    /// the number of days given.
+    /// 
+    /// Rates come from the row's species, cohort, subtype, culture, and 
+    /// ideology. Unmet tier 0 desires reduce births and add 
+    /// STARVATION_MORTALITY_RATE to deaths in proportion to how much is unmet.
+    /// Holding what our toxic desires ask for adds TOXIC_MORTALITY_RATE in
+    /// proportion to our toxicity.
+    /// 
+    /// Species with a SpeciesTag::ReproductiveGroup only have children 
+    /// through their carriers, limited by the fertile partners available
+    /// in the pop (see Species::breeding_shares).
+    /// 
+    /// Newborns start in the species' first cohort, divided between it's
+    /// subtypes by weight. Those without a culture are raised in their 
+    /// civilization's default. People age out of their cohort over it's span
+    /// into the next, or die if it's the last.
+    /// 
+    /// Oviparous species lay their young as eggs into our property instead,
+    /// which hatch into newborns after incubating, so long as the eggs are
+    /// still there. Births are counted when they hatch.
+    /// 
+    /// Fractional people are born, age, or die by chance. Desires are 
+    /// updated for those added and removed.
+    /// 
+    /// Returns the number of births and deaths.
+    pub fn population_change(&mut self, demos: &Demographics, days: f64,
+    rng: &mut impl Rng) -> (usize, usize) {
+        let shortfall = self.property.tier_zero_shortfall();
+        let toxicity = self.property.toxicity();
+        let period = days / DAYS_PER_YEAR;
+        let mut additions = vec![];
+        let mut removals = vec![];
+        let mut total_births = 0;
+        let mut total_deaths = 0;
+        // hatch the clutches which are ready.
+        for mut clutch in std::mem::take(&mut self.clutches) {
+            clutch.days_left -= days;
+            if clutch.days_left > 0.0 {
+                self.clutches.push(clutch);
+                continue;
+            }
+            let mut hatched = clutch.row;
+            hatched.count = self.property.property.get_mut(&clutch.egg)
+                .map_or(0, |eggs| {
+                    let count = (eggs.total_property.floor() as usize)
+                        .min(clutch.row.count);
+                    eggs.remove(count as f64);
+                    count
+                });
+            total_births += hatched.count;
+            additions.push(hatched);
+        }
+        // find who can have children in species which need partners.
+        let mut fertile: HashMap<usize, HashMap<Option<usize>, usize>> = HashMap::new();
+        for row in self.breakdown_table.table.iter()
+        .filter(|x| demos.species_subentry(x).birth_rate > 0.0) {
+            *fertile.entry(row.species).or_default()
+                .entry(row.species_subtype).or_insert(0) += row.count;
+        }
+        let breeding: HashMap<usize, HashMap<Option<usize>, f64>> = fertile.iter()
+            .filter_map(|(id, counts)| demos.species.get(id)
+                .expect("Species Id Not Found!")
+                .breeding_shares(counts)
+                .map(|shares| (*id, shares)))
+            .collect();
+        for row in self.breakdown_table.table.iter() {
+            let species = demos.species.get(&row.species)
+                .expect("Species Id Not Found!");
+            let count = row.count as f64;
+            let breeding_share = match breeding.get(&row.species) {
+                Some(shares) => shares.get(&row.species_subtype).copied().unwrap_or(0.0),
+                None => 1.0,
+            };
+            let birth_rate = demos.birth_rate(row) * (1.0 - shortfall) * breeding_share;
+            let mortality_rate = demos.mortality_rate(row) + 
+                shortfall * STARVATION_MORTALITY_RATE +
+                toxicity * TOXIC_MORTALITY_RATE;
+            // births
+            for (subtype, share) in species.birth_subtypes() {
+                let mut born = *row;
+                born.species_cohort = species.birth_cohort();
+                // those without a culture are raised in their civilization's default.
+                if born.culture.is_none() {
+                    born.culture = demos.market_civilization(self.market)
+                        .and_then(|x| x.epoch())
+                        .and_then(|x| x.default_culture);
+                }
+                // newborns are raised in the culture's current generation.
+                if let Some(id) = born.culture {
+                    born.culture_generation = demos.cultures.get(&id)
+                        .expect("Culture Id Not Found!").current_generation;
+                }
+                born.species_subtype = subtype;
+                born.count = Pop::random_round(count * birth_rate * period * share, rng);
+                if let Some((egg, incubation)) = species.egg() {
+                    if born.count > 0 {
+                        self.property.property.entry(egg)
+                            .and_modify(|x| x.add_property(born.count as f64))
+                            .or_insert(PropertyInfo::new(born.count as f64));
+                        self.clutches.push(Clutch { row: born, egg, 
+                            days_left: incubation as f64 });
+                    }
+                } else {
+                    total_births += born.count;
+                    additions.push(born);
+                }
             }
-            else if let Err(msg) = result {
-                match msg { // failed to send, check why
-                    // If disconnected, panic, there's nothing more we can do.
-                    barrage::TrySendError::Disconnected(_) => panic!("Unexpected Disconnect"),
-                    barrage::TrySendError::Full(_) => (), // if just full, consume and try again.
+            // deaths
+            let mut leaving = *row;
+            leaving.count = Pop::random_round(count * mortality_rate * period, rng)
+                .min(row.count);
+            total_deaths += leaving.count;
+            // aging
+            if let Some(cohort) = row.species_cohort {
+                let span = species.cohorts.get(cohort)
+                    .expect("Cohort Not Found!").span as f64;
+                let aged = Pop::random_round(count * days / span, rng)
+                    .min(row.count - leaving.count);
+                leaving.count += aged;
+                if cohort + 1 < species.cohorts.len() {
+                    let mut older = *row;
+                    older.species_cohort = Some(cohort + 1);
+                    older.count = aged;
+                    additions.push(older);
+                } else { // the last cohort ages out by dying.
+                    total_deaths += aged;
                 }
             }
-        };
+            removals.push(leaving);
+        }
+        self.change_rows(demos, &removals, additions);
+        (total_births, total_deaths)
     }
 
-    /// # Message Catchup
-    ///
-    /// A shorthand function.
-    ///
-    /// Quickly consumes all messages from the queue it can, catching up
-    /// with the current back of the queue.
-    ///
-    /// If it finds something for us, it puts it in the backlog for later consumption.
-    ///
-    /// This focuses on keeping the Broadcast Queue open to ensure it doesn't get backed
-    /// up too much.
-    pub fn msg_catchup(&mut self, rx: &Receiver<ActorMessage>) {
-        loop {
-            let result = rx.try_recv()
-                .expect("Unexpected Disconnect"); // if disconnected, panic.
+    /// # Change Rows
+    /// 
+    /// Removes and adds the rows given to our breakdown table, updating our
+    /// desires to match. Those leaving are removed before those coming in
+    /// are added. Empty rows are skipped.
+    fn change_rows(&mut self, demos: &Demographics, removals: &[PBRow], 
+    additions: Vec<PBRow>) {
+        for row in removals.iter().filter(|x| x.count > 0) {
+            self.breakdown_table.remove_pops(row);
+            self.remove_row_desires(demos, row);
+        }
+        for row in additions.into_iter().filter(|x| x.count > 0) {
+            self.breakdown_table.insert_pops(row);
+            self.add_row_desires(demos, &row);
+        }
+    }
+
+    /// Creates the rows for people of the row given converting to the
+    /// ideology given. Converts join the current wave and divide between
+    /// its factions.
+    fn ideology_converts(demos: &Demographics, row: &PBRow, target: usize,
+    converting: f64, rng: &mut impl Rng) -> Vec<PBRow> {
+        let ideology = demos.ideology.get(&target)
+            .expect("Ideology Id Not Found!");
+        let mut result = vec![];
+        let mut total = 0;
+        for (faction, share) in ideology.convert_factions() {
+            let mut convert = *row;
+            convert.ideology = Some(target);
+            convert.ideology_wave = ideology.current_wave;
+            convert.ideology_faction = faction;
+            convert.count = Pop::random_round(converting * share, rng)
+                .min(row.count - total);
+            total += convert.count;
+            result.push(convert);
+        }
+        result
+    }
 
-            if let Some(msg) = result { // if we recieved a message, check it's for us
-                if msg.for_me(self.actor_info()) {
-                    self.backlog.push_back(msg); // if it's for us, push it to the backlog.
+    /// # Convert
+    /// 
+    /// Some of each group in the pop assimilates into the culture, or 
+    /// converts to the ideology, they are most pulled toward in their 
+    /// market. Those related to the target convert faster.
+    /// 
+    /// Assimilated people lose their class and are raised in the new 
+    /// culture's current generation. Converts join the ideology's current
+    /// wave and divide between its factions.
+    /// 
+    /// Returns how many people left each culture or ideology for which.
+    pub fn convert(&mut self, demos: &Demographics, 
+    pressures: &ConversionPressures, days: f64, 
+    rng: &mut impl Rng) -> Vec<(ConversionKind, Option<usize>, usize, usize)> {
+        let period = days / DAYS_PER_YEAR;
+        let mut conversions = vec![];
+        // assimilation first
+        let mut additions = vec![];
+        let mut removals = vec![];
+        for row in self.breakdown_table.table.iter() {
+            let relations = row.culture.map(|id| &demos.cultures.get(&id)
+                .expect("Culture Id Not Found!").relations);
+            let best = Pop::best_pressure(&pressures.cultures, row.culture, relations);
+            if let Some((target, rate)) = best {
+                let culture = demos.cultures.get(&target)
+                    .expect("Culture Id Not Found!");
+                let mut convert = *row;
+                convert.culture = Some(target);
+                convert.culture_class = None;
+                convert.culture_generation = culture.current_generation;
+                convert.count = Pop::random_round(row.count as f64 * 
+                    (rate * period).min(1.0), rng);
+                if convert.count > 0 {
+                    let mut leaving = *row;
+                    leaving.count = convert.count;
+                    conversions.push((ConversionKind::Culture, row.culture, 
+                        target, convert.count));
+                    removals.push(leaving);
+                    additions.push(convert);
                 }
             }
-            else { // if no messsage in queue, we've caught up so break out.
-                return;
+        }
+        self.change_rows(demos, &removals, additions);
+        // then conversion, with the new cultures.
+        let mut additions = vec![];
+        let mut removals = vec![];
+        for row in self.breakdown_table.table.iter() {
+            let relations = row.ideology.map(|id| &demos.ideology.get(&id)
+                .expect("Ideology Id Not Found!").relations);
+            let best = Pop::best_pressure(&pressures.ideologies, row.ideology, relations);
+            if let Some((target, rate)) = best {
+                let converts = Pop::ideology_converts(demos, row, target,
+                    row.count as f64 * (rate * period).min(1.0), rng);
+                let mut leaving = *row;
+                leaving.count = converts.iter().map(|x| x.count).sum();
+                if leaving.count > 0 {
+                    conversions.push((ConversionKind::Ideology, row.ideology, 
+                        target, leaving.count));
+                    removals.push(leaving);
+                    additions.extend(converts);
+                }
             }
         }
+        self.change_rows(demos, &removals, additions);
+        conversions
+    }
+
+    /// Finds the pressure with the highest yearly rate on those currently
+    /// in the group given, who are related to the groups in relations.
+    fn best_pressure(pressures: &[ConversionPressure], current: Option<usize>,
+    relations: Option<&Vec<usize>>) -> Option<(usize, f64)> {
+        pressures.iter()
+            .filter(|x| Some(x.target) != current)
+            .map(|x| (x.target, x.yearly_rate(relations
+                .is_some_and(|rel| rel.contains(&x.target)))))
+            .filter(|(_, rate)| *rate > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// # Is Unsatisfied
+    /// 
+    /// Whether the pop ended the last day worse off than it started it.
+    pub fn is_unsatisfied(&self) -> bool {
+        self.current_sat < self.prev_sat
+    }
+
+    /// # Is Restless
+    /// 
+    /// Whether the pop is unhappy enough to support movements. They are if
+    /// they ended the last day worse off, or their mood is at or below 
+    /// MOOD_UNREST_THRESHOLD.
+    pub fn is_restless(&self) -> bool {
+        self.is_unsatisfied() || self.mood <= MOOD_UNREST_THRESHOLD
+    }
+
+    /// # Is Looking For Work
+    /// 
+    /// Whether the pop is unhappy enough to look for a different job, 
+    /// their mood being at or below MOOD_JOB_SEARCH_THRESHOLD.
+    pub fn is_looking_for_work(&self) -> bool {
+        self.mood <= MOOD_JOB_SEARCH_THRESHOLD
+    }
+
+    /// # Wage Per Person
+    /// 
+    /// The wage the pop expects to make per person, from their wage estimate.
+    pub fn wage_per_person(&self) -> f64 {
+        if self.count() == 0 {
+            return 0.0;
+        }
+        self.property.wage_estimate / self.count() as f64
+    }
+
+    /// # Observe Wage Offer
+    /// 
+    /// Moves the pop's wage estimate WAGE_ESTIMATE_ADJUSTMENT of the way 
+    /// toward the wage (per person) offered. Employed pops only take note
+    /// of offers better than what they expect to make now, the unemployed
+    /// take note of any.
+    pub fn observe_wage_offer(&mut self, wage: f64) {
+        if self.firm.is_some() && wage <= self.wage_per_person() {
+            return;
+        }
+        let target = wage * self.count() as f64;
+        self.property.wage_estimate += (target - self.property.wage_estimate) *
+            WAGE_ESTIMATE_ADJUSTMENT;
+    }
+
+    /// # Job Switch Fraction
+    /// 
+    /// How much of the pop would take the job opening given. 
+    /// 
+    /// If it doesn't pay better than what they expect to make now, no one.
+    /// Otherwise, the unemployed all take it, and employed pops who are 
+    /// looking for work send JOB_SWITCH_FRACTION of themselves.
+    pub fn job_switch_fraction(&self, opening: &JobOpening) -> f64 {
+        if opening.wage <= self.wage_per_person() {
+            0.0
+        } else if self.firm.is_none() {
+            1.0
+        } else if self.is_looking_for_work() {
+            JOB_SWITCH_FRACTION
+        } else { 0.0 }
+    }
+
+    /// # Recieve Pay Stub
+    /// 
+    /// Records the pay given by our firm in today's wage, and moves our 
+    /// wage estimate WAGE_ESTIMATE_ADJUSTMENT of the way toward it, spread
+    /// over the days it covers. Those paid less often than daily only 
+    /// update their estimate on payday.
+    pub fn recieve_pay_stub(&mut self, wage: f64, period: usize) {
+        let period = period.max(1);
+        self.property.todays_wage += wage;
+        self.property.pay_period = period;
+        let daily = wage / period as f64;
+        self.property.wage_estimate += (daily - self.property.wage_estimate) *
+            WAGE_ESTIMATE_ADJUSTMENT;
+    }
+
+    /// # Take Job
+    /// 
+    /// Sets the pop to work the job opening given, expecting it's wage.
+    pub fn take_job(&mut self, opening: &JobOpening) {
+        self.job = opening.job;
+        self.firm = Some(opening.firm);
+        self.property.wage_estimate = opening.wage * self.count() as f64;
+    }
+
+    /// # Mood Target
+    /// 
+    /// How the pop feels about today, from -1 to 1, combining
+    /// - Trend: How much better or worse off they ended the day than they 
+    ///   started it.
+    /// - Expectation: How their gain today compares to the gain they 
+    ///   believed they could get (hypo_change).
+    /// - Comparison: How well off they are per person compared to the 
+    ///   market given.
+    /// 
+    /// Each is clamped to -1 to 1 and weighted by their MOOD_*_WEIGHT.
+    pub fn mood_target(&self, market: &MarketAppeal) -> f64 {
+        let trend = Pop::relative_change(self.current_sat / self.prev_sat);
+        let expectation = Pop::relative_change(
+            (self.current_sat - self.prev_sat) / self.hypo_change);
+        let mine = MarketAppeal::of_pops(std::iter::once(self)).value();
+        let theirs = market.value();
+        let comparison = if mine.abs() + theirs.abs() > 0.0 {
+            (mine - theirs) / (mine.abs() + theirs.abs())
+        } else { 0.0 };
+        trend * MOOD_TREND_WEIGHT + expectation * MOOD_EXPECTATION_WEIGHT +
+            comparison * MOOD_COMPARISON_WEIGHT
+    }
+
+    /// Turns a ratio of now to before into a change from -1 to 1. Ratios 
+    /// which cannot be measured (nothing before) are no change.
+    fn relative_change(ratio: f64) -> f64 {
+        if ratio.is_finite() {
+            (ratio - 1.0).clamp(-1.0, 1.0)
+        } else { 0.0 }
+    }
+
+    /// # Update Mood
+    /// 
+    /// Moves the pop's mood MOOD_ADJUSTMENT_RATE of the way toward how they
+    /// feel about today. See Pop::mood_target.
+    pub fn update_mood(&mut self, market: &MarketAppeal) {
+        let target = self.mood_target(market);
+        self.mood = (self.mood + (target - self.mood) * MOOD_ADJUSTMENT_RATE)
+            .clamp(-1.0, 1.0);
+    }
+
+    /// # Mood Productivity
+    /// 
+    /// The multiplier on the pop's productivity from their mood, up to
+    /// MOOD_PRODUCTIVITY_EFFECT better or worse.
+    pub fn mood_productivity(&self) -> f64 {
+        1.0 + self.mood * MOOD_PRODUCTIVITY_EFFECT
+    }
+
+    /// # Migration Willingness
+    /// 
+    /// How willing the pop is to migrate, multiplying how many would leave
+    /// for a better market. From 0 for a content pop to 2 for a miserable 
+    /// one.
+    pub fn migration_willingness(&self) -> f64 {
+        1.0 - self.mood
+    }
+
+    /// # Is Drone
+    /// 
+    /// Whether the pop is made up entirely of drones, and so is directed
+    /// by it's owner rather than acting in the market itself.
+    pub fn is_drone(&self, demos: &Demographics) -> bool {
+        !self.breakdown_table.table.is_empty() &&
+        self.breakdown_table.table.iter()
+            .all(|row| demos.species_subentry(row).is_drone)
+    }
+
+    /// # Skill Level
+    /// 
+    /// The average skill level of the pop, halfway between the lower and
+    /// higher ends of their spread.
+    pub fn skill_level(&self) -> f64 {
+        (self.lower_skill_level + self.higher_skill_level) / 2.0
+    }
+
+    /// # Skill Efficiency
+    /// 
+    /// The bonus the pop's skill gives to the outputs of processes, to be
+    /// passed in as other_efficiency_boni.
+    pub fn skill_efficiency(&self) -> f64 {
+        self.skill_level() * SKILL_EFFICIENCY_PER_LEVEL
+    }
+
+    /// # Practice Skill
+    /// 
+    /// Improves the pop's skill through working their job for the days 
+    /// given.
+    /// 
+    /// If their job uses a different skill than the one they have, they 
+    /// switch to it and start over from nothing. Their higher skill level
+    /// grows with diminishing returns as it approaches MAX_SKILL_LEVEL, and
+    /// the lower level follows at half the rate, never passing it.
+    /// 
+    /// Growth is scaled by each group's species learning modifiers for the
+    /// skill and its groups, weighted by their count.
+    pub fn practice_skill(&mut self, data: &DataManager, demos: &Demographics,
+    days: f64) {
+        let skill = match data.jobs.get(&self.job) {
+            Some(job) => job.skill,
+            None => return,
+        };
+        if skill != self.skill {
+            self.skill = skill;
+            self.lower_skill_level = 0.0;
+            self.higher_skill_level = 0.0;
+        }
+        let count = self.count();
+        if count == 0 {
+            return;
+        }
+        let learning_rate = match data.skills.get(&skill) {
+            Some(skill) => self.breakdown_table.table.iter()
+                .map(|row| demos.skill_learning_rate(row, skill) * row.count as f64)
+                .sum::<f64>() / count as f64,
+            None => 1.0,
+        };
+        let remaining = 1.0 - self.higher_skill_level / MAX_SKILL_LEVEL;
+        let growth = SKILL_LEARNING_RATE * learning_rate * remaining
+            * days / DAYS_PER_YEAR;
+        self.higher_skill_level = (self.higher_skill_level + growth)
+            .min(MAX_SKILL_LEVEL);
+        self.lower_skill_level = (self.lower_skill_level + growth / 2.0)
+            .min(self.higher_skill_level);
+    }
+
+    /// # Spread Ideologies
+    /// 
+    /// Each group in the pop compares their ideology to the others in the 
+    /// market, and some convert to the one with the best appeal. Converts
+    /// join the ideology's current wave and divide between its factions.
+    /// 
+    /// Returns how many people left each ideology for which.
+    pub fn spread_ideologies(&mut self, demos: &Demographics, 
+    appeals: &HashMap<Option<usize>, IdeologyAppeal>, days: f64, 
+    rng: &mut impl Rng) -> Vec<(Option<usize>, usize, usize)> {
+        let mut additions = vec![];
+        let mut removals = vec![];
+        let mut conversions = vec![];
+        for row in self.breakdown_table.table.iter() {
+            let current = appeals.get(&row.ideology).copied().unwrap_or_default();
+            let best = appeals.iter()
+                .filter_map(|(ideology, appeal)| ideology
+                    .filter(|x| Some(*x) != row.ideology)
+                    .map(|x| (x, current.conversion_fraction(appeal))))
+                .filter(|(_, fraction)| *fraction > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            let (target, fraction) = match best {
+                Some(best) => best,
+                None => continue,
+            };
+            let converts = Pop::ideology_converts(demos, row, target,
+                row.count as f64 * (fraction * days).min(1.0), rng);
+            let mut leaving = *row;
+            leaving.count = converts.iter().map(|x| x.count).sum();
+            if leaving.count > 0 {
+                conversions.push((row.ideology, target, leaving.count));
+                removals.push(leaving);
+                additions.extend(converts);
+            }
+        }
+        self.change_rows(demos, &removals, additions);
+        conversions
+    }
+
+    /// Rounds the value down, then rounds up by chance equal to the 
+    /// fractional part.
+    fn random_round(value: f64, rng: &mut impl Rng) -> usize {
+        let whole = value.floor();
+        let extra = if rng.gen::<f64>() < value - whole { 1 } else { 0 };
+        whole as usize + extra
+    }
+
+    /// # Split Migrants
+    /// 
+    /// Takes the migrants, already removed from our breakdown table, and
+    /// creates a new pop with the id given for them in the destination.
+    /// 
+    /// See Pop::split_off_table for what they take with them.
+    pub fn split_migrants(&mut self, migrants: PopBreakdownTable, 
+    new_id: usize, destination: usize, demos: &Demographics) -> Pop {
+        let mut result = self.split_off_table(migrants, new_id, demos);
+        result.market = destination;
+        result
+    }
+
+    /// # Split
+    /// 
+    /// Splits the fraction given of the pop off into a new pop with the id
+    /// given. See Pop::split_off_table.
+    /// 
+    /// The id is not checked, prefer ActorManager::split_pop, which gives 
+    /// it one no other pop has.
+    pub fn split(&mut self, fraction: f64, new_id: usize, 
+    demos: &Demographics) -> Pop {
+        let taken = self.breakdown_table.split_off(fraction);
+        self.split_off_table(taken, new_id, demos)
+    }
+
+    /// # Split Rows
+    /// 
+    /// Splits the rows given (up to as many as we have of each) off into a 
+    /// new pop with the id given. See Pop::split_off_table.
+    /// 
+    /// As with Pop::split, the id is not checked, so it should come from 
+    /// ActorManager::split_pop_rows.
+    pub fn split_rows(&mut self, rows: &[PBRow], new_id: usize, 
+    demos: &Demographics) -> Pop {
+        let mut taken = PopBreakdownTable { table: vec![], total: 0 };
+        for row in rows.iter() {
+            let have = self.breakdown_table.table.iter()
+                .find(|x| x.is_match(row))
+                .map_or(0, |x| x.count);
+            if have == 0 {
+                continue;
+            }
+            let mut row = *row;
+            row.count = row.count.min(have);
+            self.breakdown_table.remove_pops(&row);
+            taken.insert_pops(row);
+        }
+        self.split_off_table(taken, new_id, demos)
+    }
+
+    /// # Split Off Table
+    /// 
+    /// Takes the people given, already removed from our breakdown table, 
+    /// and creates a new pop with the id given for them.
+    /// 
+    /// They keep our market, job, skill, mood, and how satisfied we've 
+    /// been, but have no firm, as a firm job holds only one pop. They take 
+    /// their share (by headcount) of our property, stored wants, wages, 
+    /// desire satisfaction, and the clutches our eggs will hatch from. 
+    /// Both pops then update their desires to match their people.
+    pub fn split_off_table(&mut self, taken: PopBreakdownTable, 
+    new_id: usize, demos: &Demographics) -> Pop {
+        let total = self.count() + taken.total;
+        let share = if total == 0 { 0.0 } 
+            else { taken.total as f64 / total as f64 };
+        let property = self.property.split_off(share);
+        let clutches = self.clutches.iter_mut()
+            .map(|clutch| clutch.split_off(share))
+            .filter(|clutch| clutch.row.count > 0)
+            .collect();
+        self.clutches.retain(|clutch| clutch.row.count > 0);
+        self.update_desires(demos);
+        let mut result = Pop {
+            id: new_id,
+            job: self.job,
+            firm: None,
+            owner: self.owner,
+            market: self.market,
+            skill: self.skill,
+            lower_skill_level: self.lower_skill_level,
+            higher_skill_level: self.higher_skill_level,
+            property,
+            breakdown_table: taken,
+            is_selling: self.is_selling,
+            mood: self.mood,
+            current_sat: self.current_sat,
+            prev_sat: self.prev_sat,
+            hypo_change: self.hypo_change,
+            clutches,
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new(),
+        };
+        result.update_desires(demos);
+        result
+    }
+
+    /// # Can Merge
+    /// 
+    /// Whether the other pop is similar enough to merge into us, being in
+    /// the same market, working the same job (and skill) for the same firm,
+    /// with the same owner.
+    pub fn can_merge(&self, other: &Pop) -> bool {
+        self.id != other.id && self.market == other.market && 
+        self.job == other.job && self.firm == other.firm && 
+        self.owner == other.owner &&
+        self.skill == other.skill
+    }
+
+    /// # Merge
+    /// 
+    /// Combines the other pop into us, adding their people, property, and 
+    /// satisfaction to ours. Skill levels and mood are averaged by headcount,
+    /// and their unhatched eggs become ours.
+    /// 
+    /// Returns an error, leaving both untouched, if they cannot be merged. 
+    /// See Pop::can_merge.
+    pub fn merge(&mut self, other: Pop, demos: &Demographics) -> Result<(), String> {
+        if !self.can_merge(&other) {
+            return Err(format!("Pop {} cannot be merged into Pop {}.", 
+                other.id, self.id));
+        }
+        let total = self.count() + other.count();
+        if total > 0 {
+            let ours = self.count() as f64 / total as f64;
+            let theirs = other.count() as f64 / total as f64;
+            self.lower_skill_level = self.lower_skill_level * ours + 
+                other.lower_skill_level * theirs;
+            self.higher_skill_level = self.higher_skill_level * ours +
+                other.higher_skill_level * theirs;
+            self.mood = self.mood * ours + other.mood * theirs;
+        }
+        for row in other.breakdown_table.table.iter() {
+            self.breakdown_table.insert_pops(*row);
+        }
+        self.property.merge(other.property);
+        self.current_sat += other.current_sat;
+        self.prev_sat += other.prev_sat;
+        self.hypo_change += other.hypo_change;
+        self.clutches.extend(other.clutches);
+        self.update_desires(demos);
+        Ok(())
     }
 
     /// A shorthand function to recieve the next message from the queue for us.
@@ -287,43 +932,6 @@ impl Pop {
         }
     }
 
-    /// # Specific wait function.
-    ///
-    /// First checks the backlog for the message requested, if it finds it, 
-    /// it extracts it and returns that. If it doesn't, then it 
-    /// Waits on a specific message or messages to be recieved directed for us.
-    /// If it's any other message for us, it's put onto the backlog.
-    ///
-    /// Meant to be used primarily when we are locked into a state where we
-    /// shouldn't respond to anything else but what we're focusing on.
-    /// 
-    /// May be improved by making find work with incomplete ActorMessages or some
-    /// other mechanism that removes the need to created dummies to make it work.
-    pub fn specific_wait(&mut self,
-    rx: &Receiver<ActorMessage>,
-    find: &Vec<ActorMessage>) -> ActorMessage {
-        // TODO Look into improving Find Parameter so it doesn't need a fully filled out ActorMessage to function.
-        // first, look through the backlog to ensure we haven't already gotten what we're looking for.
-        for idx in 0..self.backlog.len() {
-            if find.iter()
-            .any(|x| {
-                std::mem::discriminant(x) == std::mem::discriminant(&self.backlog[idx])
-            }) {
-                return self.backlog.remove(idx).expect("Somehow walked off end of backlog.");
-            }
-        }
-        loop {
-            let msg = self.get_next_message(rx);
-            if find.iter()
-            .any(|x| std::mem::discriminant(x) == std::mem::discriminant(&msg)) {
-                return msg;
-            }
-            else {
-                self.backlog.push_back(msg);
-            }
-        }
-    }
-
     /// Processes firm messages for standard day work.
     ///
     /// Returns true if the workday has ended.
@@ -398,6 +1006,9 @@ impl Pop {
                     amount
                 }); // no need to send more
             },
+            FirmEmployeeAction::PayStub { wage, period } => {
+                self.recieve_pay_stub(wage, period);
+            },
             _ => ()
         }
         false
@@ -478,6 +1089,12 @@ impl Pop {
     /// Keep and spend are defined/recorded by self.memory rather than calculated, and corrected
     /// as successes or failure comes in.
     ///
+    /// Surplus worth auctioning (see surplus_auction()) is auctioned while they shop, and
+    /// the auctions closed once they are done shopping.
+    ///
+    /// Salesmen who come to them are heard out between shopping trips and once
+    /// they are done, never in the middle of another deal.
+    ///
     /// ## Not Tested due to complexity.
     pub fn free_time(&mut self, rx: &mut Receiver<ActorMessage>, tx: &mut Sender<ActorMessage>,
     data: &DataManager,
@@ -502,6 +1119,7 @@ impl Pop {
         self.hypo_change = self.property.satisfaction_from_amv(amv_surplus, market);
 
         // put up our surplus for sale if we desire it
+        let mut auctions = vec![];
         if self.is_selling {
             for (&product, &amount) in surplus.iter()
             .filter(|(&id, _)| !data.products.get(&id).expect("Product Not found!").tags
@@ -509,10 +1127,33 @@ impl Pop {
                 if amount.floor() == 0.0 {
                     continue;
                 }
+                // floor it because only whole units may be transfered.
+                let mut quantity = amount.floor();
+                // large surpluses are auctioned off instead, if we have a currency to take bids in.
+                if let Some(terms) = self.surplus_auction(product, quantity, market) {
+                    if let Some(auction) = self.open_auction(rx, tx, data, market, terms) {
+                        auctions.push(auction);
+                        continue;
+                    }
+                }
+                if let ExchangeMode::OrderBook { currency } = market.exchange_mode {
+                    // only ask for what we could handle if it all sold.
+                    let mut exchange = HashMap::new();
+                    exchange.insert(product, -quantity);
+                    exchange.insert(currency, quantity * market.get_product_price(&product, 1.0)
+                        / market.get_product_price(&currency, 1.0));
+                    quantity = (quantity * self.property.handleable_share(&exchange, data,
+                        self.skill_level(), self.skill)).floor();
+                    if quantity == 0.0 {
+                        continue;
+                    }
+                    // asks rest in the book, hold the goods so they can't be sold twice.
+                    quantity = self.property.hold(Hold::Ask, product, quantity, data);
+                }
                 self.push_message(rx, tx,
                 ActorMessage::SellOrder { sender: self.actor_info(),
                     product,
-                    quantity: amount.floor(), // floor it because only whole units may be transfered.
+                    quantity,
                     amv: market.get_product_price(&product, 1.0) });
                 // non-firms offer at the current market price.
             }
@@ -521,16 +1162,67 @@ impl Pop {
         // Go shopping to get more stuff
         shopping_loop(self, rx, tx, data, market);
 
+        // with shopping done, take the best bids on our auctions.
+        for auction in auctions {
+            self.close_auction(rx, tx, data, market, auction);
+        }
+
         // measure overall success
         self.current_sat = self.property.sift_all(data);
-        // this helps define economic sentiment (prospering/decaying)
-        // todo modify mood based on results here.
+        // this helps define economic sentiment (prospering/decaying), which
+        // feeds into mood after the day, see Pop::update_mood.
 
         // after we run out of stuff to buy, send finished and leave, consumption comes later
         self.push_message(rx, tx, ActorMessage::Finished { sender: self.actor_info() });
-        self.active_wait(rx, tx, data, market, &vec![
-            ActorMessage::AllFinished
-        ]);
+        // we're idle until the day ends, so hear out any salesmen who come to us.
+        loop {
+            self.hear_out_salesmen(rx, tx, data, market);
+            let msg = self.active_wait(rx, tx, data, market, &vec![
+                ActorMessage::AllFinished,
+                ActorMessage::FoundBuyer { seller: ActorInfo::Firm(0), 
+                    buyer: ActorInfo::Firm(0), product: 0 }
+            ]);
+            if let ActorMessage::AllFinished = msg {
+                break;
+            }
+            self.process_common_msg(rx, tx, data, market, msg);
+        }
+        // nothing more can settle, return anything still held.
+        self.property.release_all(data);
+    }
+
+    /// # Surplus Auction
+    ///
+    /// Decides whether to auction off our surplus of a product rather than
+    /// wait for buyers to find us.
+    ///
+    /// We auction when buyers didn't take all that was offered of the product
+    /// yesterday, our surplus is worth at least AUCTION_SURPLUS_THRESHOLD per 
+    /// person, and the market has a currency to take bids in (which isn't 
+    /// the product itself). The reserve is the market price in that currency.
+    ///
+    /// Order book markets settle through asks instead, so we never auction 
+    /// there.
+    pub fn surplus_auction(&self, product: usize, quantity: f64, 
+    market: &MarketHistory) -> Option<AuctionTerms> {
+        if let ExchangeMode::OrderBook { .. } = market.exchange_mode {
+            return None;
+        }
+        let info = market.product_info.get(&product)?;
+        if info.offered <= info.sold {
+            return None; // it sells well enough without going out of our way.
+        }
+        let currency = *market.currencies.first()?;
+        if currency == product {
+            return None;
+        }
+        let price = market.get_product_price(&product, 1.0);
+        if quantity * price < constants::AUCTION_SURPLUS_THRESHOLD * 
+        self.count().max(1) as f64 {
+            return None;
+        }
+        Some(AuctionTerms { kind: AuctionKind::Auction, product, quantity, 
+            currency, reserve: price / market.get_product_price(&currency, 1.0) })
     }
 
     /// # Shopping Loop
@@ -580,6 +1272,8 @@ impl Pop {
         let mut retry = false;
         // Should have our current desire coords in next_desire
         while let Some(curr_desire_coord) = next_desire {
+            // between trips we're idle, so hear out any salesmen first.
+            pop.hear_out_salesmen(rx, tx, data, market);
             if completed_desires.len() == pop.property.desires.len() {
                 break; // if all desires are marked complete, gtfo.
             }
@@ -712,7 +1406,7 @@ impl Pop {
                 // TODO when purchasing shopping time is available, add an option for that here.
                 available_shopping_time += pop.property.get_shopping_time(
                     shopping_time_cost - available_shopping_time,
-                    data, market, Some(curr_desire_coord));
+                    data, pop.skill_level(), pop.skill, market, Some(curr_desire_coord));
                 // check that it's enough time to go out buying
                 if shopping_time_cost > available_shopping_time {
                     // if we don't have enough time to go shopping for this desire we likely won't be able to go shopping for
@@ -795,6 +1489,137 @@ impl Pop {
         }
     }
 
+    /// # Shop and Sell Loop
+    ///
+    /// The standard free time loop. Runs the shopping_loop, then, if we are
+    /// selling, the salesmanning_loop with whatever time is left.
+    pub fn shop_and_sell_loop(pop: &mut Pop, rx: &mut Receiver<ActorMessage>,
+    tx: &mut Sender<ActorMessage>,
+    data: &DataManager, market: &MarketHistory) {
+        Pop::shopping_loop(pop, rx, tx, data, market);
+        if pop.is_selling {
+            Pop::salesmanning_loop(pop, rx, tx, data, market);
+        }
+    }
+
+    /// # Drone Loop
+    ///
+    /// The free time loop for drones. Rather than shopping, they ask their
+    /// owner, be it firm or institution, to provide whatever their (finite)
+    /// desires are missing, in the order of their desires. Any provisions 
+    /// come back as SendProduct or SendWant messages while they wait for 
+    /// the day to end.
+    ///
+    /// Drones without an owner can do nothing but wait.
+    pub fn drone_loop(pop: &mut Pop, rx: &mut Receiver<ActorMessage>,
+    tx: &mut Sender<ActorMessage>,
+    _data: &DataManager, _market: &MarketHistory) {
+        let owner = match pop.owner.or(pop.firm.map(ActorInfo::Firm)) {
+            Some(owner) => owner,
+            None => return,
+        };
+        let requests = pop.property.desires.iter()
+            .filter_map(|desire| desire.total_desire()
+                .map(|total| (desire.item, total - desire.satisfaction)))
+            .filter(|(_, missing)| *missing > 0.0)
+            .collect_vec();
+        for (item, amount) in requests {
+            pop.push_message(rx, tx, ActorMessage::EmployeeToFirm { 
+                employee: pop.actor_info(), 
+                firm: owner, 
+                action: FirmEmployeeAction::RequestProvisions { item, amount }
+            });
+        }
+    }
+
+    /// # Salesmanning Loop
+    ///
+    /// Goes out looking for buyers for our surplus goods, one trip per
+    /// product in ID order, so long as we have the time for another trip.
+    ///
+    /// Only transferrable goods with at least one whole unit available
+    /// are sold.
+    pub fn salesmanning_loop(pop: &mut Pop, rx: &mut Receiver<ActorMessage>,
+    tx: &mut Sender<ActorMessage>,
+    data: &DataManager, market: &MarketHistory) {
+        let salesman_time_cost = pop.salesman_time_cost();
+        let products = pop.property.property.iter()
+            .filter(|(_, info)| info.available() >= 1.0)
+            .filter(|(id, _)| !data.products.get(id).expect("Product Not found!").tags
+                .contains(&ProductTag::NonTransferrable))
+            .map(|(&id, _)| id)
+            .sorted()
+            .collect_vec();
+        for product in products {
+            pop.hear_out_salesmen(rx, tx, data, market);
+            if !pop.spend_shopping_time(salesman_time_cost, product, data, market) {
+                break; // out of time.
+            }
+            pop.salesman_sell(rx, tx, data, market, product);
+        }
+    }
+
+    /// # Salesman Sell
+    ///
+    /// Asks the market to find us a buyer for the product. If one is found,
+    /// we open the deal with them as though they came to us, via
+    /// standard_sell.
+    ///
+    /// Like try_to_buy, this does not spend shopping time.
+    ///
+    /// Returns the buyer we were sent to, if any.
+    pub fn salesman_sell(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, market: &MarketHistory,
+    product: usize) -> Option<ActorInfo> {
+        self.push_message(rx, tx, ActorMessage::FindBuyer { product, 
+            sender: self.actor_info() });
+        loop {
+            let result = self.active_wait(rx, tx, data, market, &vec![
+                ActorMessage::FoundBuyer { seller: ActorInfo::Firm(0), 
+                    buyer: ActorInfo::Firm(0), product: 0 },
+                ActorMessage::BuyerNotFound { product: 0, seller: ActorInfo::Firm(0) }
+            ]);
+            match result {
+                ActorMessage::BuyerNotFound { .. } => return None,
+                ActorMessage::FoundBuyer { seller, buyer, 
+                product: found } if seller == self.actor_info() => {
+                    self.standard_sell(rx, tx, data, market, found, buyer);
+                    return Some(buyer);
+                },
+                // another salesman found us, deal with them before going on.
+                _ => { self.process_common_msg(rx, tx, data, market, result); }
+            }
+        }
+    }
+
+    /// # Hear Out Salesmen
+    ///
+    /// Deals with each salesman who has come to us, as we would any seller,
+    /// including any still waiting in the backlog.
+    ///
+    /// Only called while we're idle, between deals of our own, so a 
+    /// salesman's deal never nests inside another.
+    pub fn hear_out_salesmen(&mut self, rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>, data: &DataManager, market: &MarketHistory) {
+        let me = self.actor_info();
+        loop {
+            self.msg_catchup(rx);
+            let (found, rest): (Vec<_>, Vec<_>) = self.backlog.drain(..)
+                .partition(|msg| matches!(msg, 
+                    ActorMessage::FoundBuyer { buyer, .. } if *buyer == me));
+            self.backlog = rest.into();
+            for msg in found {
+                if let ActorMessage::FoundBuyer { seller, .. } = msg {
+                    self.salesmen.push_back(seller);
+                }
+            }
+            match self.salesmen.pop_front() {
+                Some(seller) => { self.standard_buy(rx, tx, data, market, 0.0, seller); },
+                None => return,
+            }
+        }
+    }
+
     /// # Find Class Product
     ///
     /// Helper Function, summarizes the sending of a class find and responding to it.
@@ -840,7 +1665,15 @@ impl Pop {
                 }
                 return None;
             },
-            // TODO add Seller Approaches Logic Here.
+            ActorMessage::FoundBuyer { seller, buyer, .. } => {
+                if buyer == self.actor_info() {
+                    // a salesman has come to us, hear them out once we're idle.
+                    self.salesmen.push_back(seller);
+                } else {
+                    debug_assert!(false, "This message should only ever be recieved here while we are a buyer.");
+                }
+                return None;
+            },
             ActorMessage::SendProduct { product, amount, .. } => {
                 // We're recieving a product, add to our unreserved amount.
                 self.property.property.entry(product)
@@ -854,6 +1687,54 @@ impl Pop {
                     .add(amount);
                 return None;
             },
+            ActorMessage::OrderFilled { buyer, seller, product, 
+            quantity, currency, price } => {
+                // the order book matched us, exchange the goods.
+                let payment = quantity * price;
+                let mut exchange = HashMap::new();
+                if buyer == self.actor_info() {
+                    // pay from the funds held for our bid.
+                    self.property.release(Hold::Bid(product), currency, payment, data);
+                    exchange.insert(product, quantity);
+                    exchange.insert(currency, -payment);
+                } else if seller == self.actor_info() {
+                    self.property.release(Hold::Ask, product, quantity, data);
+                    exchange.insert(product, -quantity);
+                    exchange.insert(currency, payment);
+                }
+                self.property.add_products(&exchange, data);
+                self.property.pay_handling_cost(&exchange, data,
+                    self.skill_level(), self.skill, market);
+                self.property.record_exchange(exchange);
+                if buyer == self.actor_info() {
+                    // record what we actually paid, not what we bid.
+                    self.property.record_purchase(product, 
+                        payment * market.get_product_price(&currency, 1.0));
+                }
+                return None;
+            },
+            ActorMessage::AuctionOpened { auction, organizer, kind, 
+            product, quantity, currency, reserve } => {
+                if organizer != self.actor_info() {
+                    self.consider_auction(rx, tx, data, market, auction, 
+                        AuctionTerms { kind, product, quantity, currency, reserve });
+                }
+                return None;
+            },
+            ActorMessage::AuctionClosed { auction, organizer, winner, kind, 
+            product, quantity, currency, price } => {
+                if organizer != self.actor_info() {
+                    if winner == Some(self.actor_info()) {
+                        // we won someone else's auction, exchange the goods.
+                        self.settle_auction(auction, false, AuctionTerms { kind, 
+                            product, quantity, currency, reserve: price }, 
+                            price, data, market);
+                    }
+                    // winner or loser, what's left of our bid is ours again.
+                    self.property.release_hold(Hold::Auction(auction), data);
+                }
+                return None;
+            },
             ActorMessage::WantSplash { want, amount, .. } => {
                 self.property.want_store.entry(want)
                     .or_insert(WantInfo::new(0.0))
@@ -912,6 +1793,12 @@ impl Pop {
             return BuyResult::CancelBuy;
         }
 
+        // if the market uses an order book, bid there instead of bargaining.
+        if let ExchangeMode::OrderBook { .. } = market.exchange_mode {
+            return self.order_book_buy(rx, tx, data, market, product, 
+                buy_target);
+        }
+
         // since the current market price is within our budget, try to look for it.
         self.push_message(rx, tx, ActorMessage::FindProduct { product: product, sender: self.actor_info() });
         // with the message sent, wait for the response back while in our standard holding pattern.
@@ -933,6 +1820,91 @@ impl Pop {
         else { unreachable!("Somehow did not get FoundProduct or ProductNotFound."); }
     }
 
+    /// # Order Book Buy
+    ///
+    /// Used in place of FindProduct and standard_buy when the market 
+    /// exchanges through an order book.
+    ///
+    /// Bids yesterday's market price (in the settlement currency) for as much
+    /// of the buy target as our available currency can cover. If giving up
+    /// the currency would cost us more satisfaction than the product gains
+    /// us, it doesn't bid at all.
+    ///
+    /// The currency bid is held until the bid closes, so it can't be spent
+    /// elsewhere while the bid rests. Fills are settled from it as 
+    /// OrderFilled messages arrive, after which the market sends BidClosed 
+    /// with the total filled and we release whatever wasn't spent.
+    ///
+    /// Like try_to_buy, this does not spend shopping time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the market does not exchange through an order book.
+    pub fn order_book_buy(&mut self,
+    rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>,
+    data: &DataManager,
+    market: &MarketHistory,
+    product: usize,
+    buy_target: f64) -> BuyResult {
+        let currency = match market.exchange_mode {
+            ExchangeMode::OrderBook { currency } => currency,
+            _ => panic!("Market does not exchange through an order book."),
+        };
+        let currency_price = market.get_product_price(&currency, 1.0);
+        let unit_price = market.get_product_price(&product, 1.0) / currency_price;
+        let available = self.property.property.get(&currency)
+            .map(|x| x.available()).unwrap_or(0.0);
+        // buy as much as we can afford, up to our target.
+        let mut quantity = if unit_price > 0.0 {
+            buy_target.min(available / unit_price)
+        } else { buy_target };
+        if !data.products.get(&product).expect("Product Not Found!").fractional {
+            quantity = quantity.floor();
+        }
+        if quantity <= 0.0 {
+            return BuyResult::NotSuccessful { reason: OfferResult::TooExpensive };
+        }
+        // only bid for what we could handle.
+        let mut exchange = HashMap::new();
+        exchange.insert(product, quantity);
+        exchange.insert(currency, -(quantity * unit_price));
+        let share = self.property.handleable_share(&exchange, data,
+            self.skill_level(), self.skill);
+        if share < 1.0 {
+            quantity *= share;
+            if !data.products.get(&product).expect("Product Not Found!").fractional {
+                quantity = quantity.floor();
+            }
+            if quantity <= 0.0 {
+                return BuyResult::NotSuccessful { reason: OfferResult::TooExpensive };
+            }
+            exchange.insert(product, quantity);
+            exchange.insert(currency, -(quantity * unit_price));
+        }
+        // check that the exchange is worth it to us.
+        if self.property.predict_value_changed(&exchange, data).value < 0.0 {
+            return BuyResult::NotSuccessful { reason: OfferResult::TooExpensive };
+        }
+        self.property.hold(Hold::Bid(product), currency, quantity * unit_price, data);
+        self.push_message(rx, tx, ActorMessage::BidOrder { sender: self.actor_info(), 
+            product, quantity, price: unit_price });
+        // fills are settled (and purchases recorded) in process_common_msg 
+        // while we wait for the close.
+        let result = self.active_wait(rx, tx, data, market, &vec![
+            ActorMessage::BidClosed { buyer: ActorInfo::Firm(0), product: 0, filled: 0.0 }
+        ]);
+        self.property.release_hold(Hold::Bid(product), data);
+        if let ActorMessage::BidClosed { filled, .. } = result {
+            if filled > 0.0 {
+                BuyResult::Successful
+            } else {
+                self.property.record_purchase(product, 0.0);
+                BuyResult::NotSuccessful { reason: OfferResult::NotInMarket }
+            }
+        } else { unreachable!("Somehow did not get BidClosed."); }
+    }
+
     /// Gets the standard shopping time cost for this pop.
     ///
     /// This is currently calculated as being equal to
@@ -942,6 +1914,244 @@ impl Pop {
         constants::SHOPPING_TIME_COST * self.count() as f64
     }
 
+    /// Gets the salesmanning time cost for this pop.
+    ///
+    /// This is currently calculated as being equal to
+    /// SALESMANNING_TIME_COST (0.3) * self.total_population
+    pub fn salesman_time_cost(&self) -> f64 {
+        constants::SALESMANNING_TIME_COST * self.count() as f64
+    }
+
+    /// Gets the time cost for this pop to organize an auction.
+    ///
+    /// This is currently calculated as being equal to
+    /// AUCTION_ORGANIZER_TIME_COST (1.0) * self.total_population
+    pub fn auction_organizer_time_cost(&self) -> f64 {
+        constants::AUCTION_ORGANIZER_TIME_COST * self.count() as f64
+    }
+
+    /// Gets the time cost for this pop to bid on an auction.
+    ///
+    /// This is currently calculated as being equal to
+    /// AUCTION_BIDDER_TIME_COST (0.05) * self.total_population
+    pub fn auction_bidder_time_cost(&self) -> f64 {
+        constants::AUCTION_BIDDER_TIME_COST * self.count() as f64
+    }
+
+    /// # Spend Shopping Time
+    ///
+    /// Tries to get and spend the given amount of shopping time, adding
+    /// it to the time cost of the product it was spent on.
+    ///
+    /// If it can't get enough, what it did get is returned to property and
+    /// nothing is spent.
+    ///
+    /// Returns true if the time was spent.
+    fn spend_shopping_time(&mut self, cost: f64, product: usize,
+    data: &DataManager, market: &MarketHistory) -> bool {
+        let available = self.property.get_shopping_time(cost, data,
+            self.skill_level(), self.skill, market, None);
+        if available < cost {
+            if available > 0.0 {
+                self.property.add_property(SHOPPING_TIME_PRODUCT_ID, available, data);
+            }
+            return false;
+        }
+        self.property.property.entry(product)
+            .or_insert(PropertyInfo::new(0.0))
+            .time_cost += cost;
+        true
+    }
+
+    /// # Open Auction
+    ///
+    /// Opens an auction (selling) or reverse auction (buying) of the product
+    /// in the market, spending our organizer time cost to do so.
+    ///
+    /// What we put up for it, the product for an auction or the currency 
+    /// at the reserve for a reverse auction, is held until the auction 
+    /// closes. If we can't cover it all, we don't open it.
+    ///
+    /// Bids are collected by the market until we close it with close_auction().
+    ///
+    /// Returns the id of the auction, or None if we didn't have the goods or
+    /// time to organize it.
+    pub fn open_auction(&mut self,
+    rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>,
+    data: &DataManager,
+    market: &MarketHistory,
+    terms: AuctionTerms) -> Option<usize> {
+        let (backing, needed) = terms.organizer_gives();
+        let mut exchange = HashMap::new();
+        exchange.insert(terms.product, terms.quantity);
+        exchange.insert(terms.currency, terms.quantity * terms.reserve);
+        if Property::handling_cost(&exchange, data) + self.auction_organizer_time_cost() >
+        self.property.available_handling_time(data, self.skill_level(), self.skill) {
+            return None; // we couldn't organize it and handle the goods if it sold.
+        }
+        if self.property.hold(Hold::Opening, backing, needed, data) < needed ||
+        !self.spend_shopping_time(self.auction_organizer_time_cost(), 
+        terms.product, data, market) {
+            self.property.release_hold(Hold::Opening, data);
+            return None;
+        }
+        self.push_message(rx, tx, ActorMessage::OpenAuction { 
+            organizer: self.actor_info(), kind: terms.kind, product: terms.product, 
+            quantity: terms.quantity, currency: terms.currency, 
+            reserve: terms.reserve });
+        // wait for our auction to be announced, other auctions are dealt with normally.
+        loop {
+            let result = self.active_wait(rx, tx, data, market, &vec![
+                ActorMessage::AuctionOpened { auction: 0, organizer: ActorInfo::Firm(0), 
+                    kind: terms.kind, product: 0, quantity: 0.0, currency: 0, reserve: 0.0 }
+            ]);
+            if let ActorMessage::AuctionOpened { auction, organizer, .. } = result {
+                if organizer == self.actor_info() {
+                    self.property.move_hold(Hold::Opening, Hold::Auction(auction));
+                    return Some(auction);
+                }
+                self.process_common_msg(rx, tx, data, market, result);
+            }
+        }
+    }
+
+    /// # Close Auction
+    ///
+    /// Closes an auction we opened, exchanging the goods with the winner
+    /// if there is one, and releasing whatever we held for it that wasn't
+    /// given up.
+    ///
+    /// Returns the winner and the unit price they won at, or None if no bid
+    /// met our reserve (or the market didn't have the auction open).
+    pub fn close_auction(&mut self,
+    rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>,
+    data: &DataManager,
+    market: &MarketHistory,
+    auction: usize) -> Option<(ActorInfo, f64)> {
+        self.push_message(rx, tx, ActorMessage::CloseAuction { auction, 
+            organizer: self.actor_info() });
+        loop {
+            let result = self.active_wait(rx, tx, data, market, &vec![
+                ActorMessage::AuctionClosed { auction: 0, organizer: ActorInfo::Firm(0),
+                    winner: None, kind: AuctionKind::Auction, product: 0, 
+                    quantity: 0.0, currency: 0, price: 0.0 },
+                ActorMessage::AuctionNotFound { auction: 0, organizer: ActorInfo::Firm(0) }
+            ]);
+            match result {
+                ActorMessage::AuctionClosed { auction: closed, organizer, 
+                winner, kind, product, quantity, currency, price } 
+                if closed == auction && organizer == self.actor_info() => {
+                    let mut won = None;
+                    if let Some(winner) = winner {
+                        let terms = AuctionTerms { kind, product, quantity, 
+                            currency, reserve: price };
+                        self.settle_auction(auction, true, terms, price, data, market);
+                        won = Some((winner, price));
+                    }
+                    self.property.release_hold(Hold::Auction(auction), data);
+                    return won;
+                },
+                ActorMessage::AuctionNotFound { auction: missing, organizer } 
+                if missing == auction && organizer == self.actor_info() => {
+                    self.property.release_hold(Hold::Auction(auction), data);
+                    return None;
+                },
+                // someone else's auction, deal with it normally.
+                _ => { self.process_common_msg(rx, tx, data, market, result); }
+            }
+        }
+    }
+
+    /// # Consider Auction
+    ///
+    /// Reacts to an auction opened by someone else. If taking part would
+    /// improve our satisfaction at the current market price (limited by the
+    /// reserve) and we have the goods and time to do so, we spend our bidder
+    /// time cost and bid.
+    ///
+    /// For an Auction, we bid to buy, needing enough currency to pay. For a
+    /// Reverse Auction, we bid to sell, needing enough of the product. What
+    /// we bid is held until the auction closes.
+    fn consider_auction(&mut self,
+    rx: &mut Receiver<ActorMessage>,
+    tx: &Sender<ActorMessage>,
+    data: &DataManager,
+    market: &MarketHistory,
+    auction: usize,
+    terms: AuctionTerms) {
+        let market_price = market.get_product_price(&terms.product, 1.0) / 
+            market.get_product_price(&terms.currency, 1.0);
+        let price = match terms.kind {
+            AuctionKind::Auction => market_price.max(terms.reserve),
+            AuctionKind::ReverseAuction => market_price.min(terms.reserve),
+        };
+        let (needed, required) = terms.bidder_gives(price);
+        let mut exchange = HashMap::new();
+        match terms.kind {
+            AuctionKind::Auction => {
+                exchange.insert(terms.product, terms.quantity);
+                exchange.insert(terms.currency, -(terms.quantity * price));
+            },
+            AuctionKind::ReverseAuction => {
+                exchange.insert(terms.product, -terms.quantity);
+                exchange.insert(terms.currency, terms.quantity * price);
+            },
+        }
+        let available = self.property.property.get(&needed)
+            .map(|x| x.available()).unwrap_or(0.0);
+        if available < required {
+            return; // we can't cover the exchange.
+        }
+        if self.property.predict_value_changed(&exchange, data).value <= 0.0 {
+            return; // not worth it to us.
+        }
+        if Property::handling_cost(&exchange, data) + self.auction_bidder_time_cost() >
+        self.property.available_handling_time(data, self.skill_level(), self.skill) {
+            return; // we couldn't bid and handle the goods if we won.
+        }
+        if !self.spend_shopping_time(self.auction_bidder_time_cost(), 
+        terms.product, data, market) {
+            return;
+        }
+        self.property.hold(Hold::Auction(auction), needed, required, data);
+        self.push_message(rx, tx, ActorMessage::AuctionBid { auction, 
+            bidder: self.actor_info(), price });
+    }
+
+    /// # Settle Auction
+    ///
+    /// Exchanges the goods of a closed auction, as either the organizer or
+    /// the winner, giving up what we held for it.
+    ///
+    /// What we give is re-checked against what we still hold (and own), so 
+    /// we never give more than we have.
+    fn settle_auction(&mut self, auction: usize, organizer: bool, 
+    terms: AuctionTerms, price: f64, data: &DataManager, 
+    market: &MarketHistory) {
+        let payment = terms.quantity * price;
+        // the organizer of an auction and the winner of a reverse auction sell.
+        let selling = organizer == (terms.kind == AuctionKind::Auction);
+        let (given, gotten, mut give, get) = if selling {
+            (terms.product, terms.currency, terms.quantity, payment)
+        } else {
+            (terms.currency, terms.product, payment, terms.quantity)
+        };
+        self.property.release(Hold::Auction(auction), given, give, data);
+        let owned = self.property.property.get(&given)
+            .map(|x| x.total_property).unwrap_or(0.0);
+        debug_assert!(owned >= give, "Settling an auction we can't cover.");
+        give = give.min(owned);
+        let mut exchange = HashMap::new();
+        exchange.insert(given, -give);
+        exchange.insert(gotten, get);
+        self.property.add_products(&exchange, data);
+        self.property.pay_handling_cost(&exchange, data,
+            self.skill_level(), self.skill, market);
+        self.property.record_exchange(exchange);
+    }
+
     /// Standard Buy Function
     ///
     /// This has been reached when we have successfully recieved a
@@ -984,8 +2194,9 @@ impl Pop {
     /// the day, adding any recieved to that item's achieved and adding to spent
     /// if spent, as well as updating the target it's AMV spent on it.
     ///
-    /// It will not expend time (or at least it currently doesn't. This may
-    /// be changed if time for a deal scales with items exchanged.)
+    /// It does not expend the time of the trip itself, but does pay the
+    /// handling cost of the goods moved if the deal goes through. If we
+    /// couldn't pay that cost, we don't make the offer.
     ///
     /// TODO will need to be updated when price estimates for wants and classes are added
     /// TODO Update to take into account storage gained/lost from the exchange also.
@@ -995,15 +2206,26 @@ impl Pop {
     data: &DataManager,
     market: &MarketHistory,
     buy_target: f64,
-    _seller: ActorInfo) -> BuyResult {
+    seller: ActorInfo) -> BuyResult {
         // We don't send CheckItem message as FindProduct msg includes that in the logic.
-        // wait for deal start or preemptive close.
-        let result = self.specific_wait(rx, &vec![
-            ActorMessage::InStock { buyer: ActorInfo::Firm(0), seller:
-                ActorInfo::Firm(0), product: 0, price: 0.0, quantity: 0.0 },
-            ActorMessage::NotInStock { buyer: ActorInfo::Firm(0), seller:
-                ActorInfo::Firm(0), product: 0 }
-        ]);
+        // wait for deal start or preemptive close from our seller.
+        let mut others = vec![];
+        let result = loop {
+            let msg = self.specific_wait(rx, &[
+                ActorMessage::InStock { buyer: ActorInfo::Firm(0), seller:
+                    ActorInfo::Firm(0), product: 0, price: 0.0, quantity: 0.0 },
+                ActorMessage::NotInStock { buyer: ActorInfo::Firm(0), seller:
+                    ActorInfo::Firm(0), product: 0 }
+            ]);
+            match msg {
+                ActorMessage::InStock { seller: from, .. } |
+                ActorMessage::NotInStock { seller: from, .. } if from == seller => break msg,
+                _ => others.push(msg), // a salesman waiting on us, we'll get to them later.
+            }
+        };
+        for msg in others.into_iter().rev() {
+            self.backlog.push_front(msg);
+        }
         if let ActorMessage::NotInStock { .. } = result {
             // maybe record failure
             return BuyResult::NotSuccessful { reason: OfferResult::OutOfStock };
@@ -1210,6 +2432,16 @@ impl Pop {
                     seller: seller, product: sought_product, price_opinion: OfferResult::TooExpensive });
                 return BuyResult::NotSuccessful { reason: OfferResult::TooExpensive };
             }
+            // check that we could handle the goods moved if they accept.
+            let mut exchange: HashMap<usize, f64> = current_offer.iter()
+                .map(|(&prod, &quant)| (prod, -quant)).collect();
+            *exchange.entry(sought_product).or_insert(0.0) += final_target;
+            if self.property.handleable_share(&exchange, data,
+                self.skill_level(), self.skill) < 1.0 {
+                self.push_message(rx, tx, ActorMessage::RejectPurchase { buyer: self.actor_info(),
+                    seller, product: sought_product, price_opinion: OfferResult::TooExpensive });
+                return BuyResult::NotSuccessful { reason: OfferResult::TooExpensive };
+            }
             // get an opinion estimate from how much satisfaction we are giving up vs
             let offer_result = Pop::offer_result_selector(sat_gain, sat_lost);
             // after the previous section, we either have enough AMV to try and purchase,
@@ -1219,7 +2451,7 @@ impl Pop {
             // TODO add in possibility of rejection here. For now they will always try, if nothing else.
 
             // deal with responses
-            let response = self.specific_wait(rx, &vec![
+            let response = self.specific_wait(rx, &[
                 ActorMessage::SellerAcceptOfferAsIs { buyer: ActorInfo::Firm(0),
                     seller: ActorInfo::Firm(0),
                     product: 0,
@@ -1253,6 +2485,8 @@ impl Pop {
                 product,
                 offer_result: _ } => {
                     let _gain = self.property.add_products(&resulting_change, data);
+                    self.property.pay_handling_cost(&resulting_change, data,
+                        self.skill_level(), self.skill, market);
                     self.property.record_exchange(resulting_change);
                     self.property.record_purchase(product, current_offer_amv);
                     // send back close
@@ -1291,6 +2525,8 @@ impl Pop {
                         return BuyResult::CancelBuy;
                     } else { // if still positive satisfaction change, accept.
                         self.property.add_products(&resulting_change, data);
+                        self.property.pay_handling_cost(&resulting_change, data,
+                            self.skill_level(), self.skill, market);
                         self.property.record_exchange(resulting_change);
                         self.property.record_purchase(product, current_offer_amv);
                         self.push_message(rx, tx, ActorMessage::FinishDeal { buyer, seller, product });
@@ -1327,7 +2563,7 @@ impl Pop {
     followups: usize) -> HashMap<usize, f64> {
         let mut result = HashMap::new();
         for expected_remainder in (0..followups).rev() {
-            let response = self.specific_wait(rx, &vec![
+            let response = self.specific_wait(rx, &[
                 ActorMessage::ChangeFollowup { buyer: ActorInfo::Firm(0),
                     seller: ActorInfo::Firm(0),
                     product: 0,
@@ -1358,7 +2594,7 @@ impl Pop {
     pub fn recieve_offer_followups(&mut self, rx: &mut Receiver<ActorMessage>, _tx: &Sender<ActorMessage>, buyer: ActorInfo, followups: usize) -> HashMap<usize, f64> {
         let mut result = HashMap::new();
         for expected_remainder in (0..followups).rev() {
-            let response = self.specific_wait(rx, &vec![
+            let response = self.specific_wait(rx, &[
                 ActorMessage::BuyOfferFollowup { buyer: ActorInfo::Firm(0),
                     seller: ActorInfo::Firm(0),
                     product: 0,
@@ -1614,7 +2850,7 @@ impl Pop {
                     product, price, quantity: available });
         } // message was sent
         // get response
-        let response = self.specific_wait(rx, &vec![
+        let response = self.specific_wait(rx, &[
             ActorMessage::RejectPurchase { buyer, seller: self.actor_info(), product: 0, price_opinion: OfferResult::Cheap },
             ActorMessage::BuyOffer { buyer, seller: self.actor_info(), product, 
                 price_opinion: OfferResult::Cheap, quantity: 1.0, followup: 0 }
@@ -1630,7 +2866,9 @@ impl Pop {
             ret = self.recieve_offer_followups(rx, tx, buyer, followup);
             ret.insert(product, -quantity);
             let value_change = self.property.predict_value_changed(&ret, data);
-            if value_change.value < 0.0 { // if loss less than gain, reject offer
+            // if loss less than gain, or we couldn't handle the goods, reject offer
+            if value_change.value < 0.0 || self.property.handleable_share(&ret, data,
+                self.skill_level(), self.skill) < 1.0 {
                 self.push_message(rx, tx, 
                     ActorMessage::RejectOffer { buyer, 
                         seller: self.actor_info(), product });
@@ -1643,6 +2881,8 @@ impl Pop {
             }
         } else { unreachable!("Should never be reached."); }
         self.property.add_products(&ret, data);
+        self.property.pay_handling_cost(&ret, data,
+            self.skill_level(), self.skill, market);
     }
 
     /// # Consume Goods
@@ -1730,6 +2970,12 @@ impl Pop {
     }
 }
 
+impl MessageBacklog for Pop {
+    fn backlog(&mut self) -> &mut VecDeque<ActorMessage> {
+        &mut self.backlog
+    }
+}
+
 impl Buyer for Pop {
 }
 
@@ -1773,6 +3019,9 @@ impl Actor for Pop {
     /// case they tell the market they're done and enter a holding pattern,
     /// waiting for either buying messages or for the market day to end.
     ///
+    /// Drone pops never sell, and rather than shopping they use their free
+    /// time asking their owner for provisions. See Pop::drone_loop.
+    ///
     /// # Selling Notes
     ///
     /// If they are offering stuff for exchange, they will send up messages
@@ -1797,11 +3046,14 @@ impl Actor for Pop {
     tx: &mut Sender<ActorMessage>,
     rx: &mut Receiver<ActorMessage>,
     data: &DataManager,
-    _demos: &Demographics,
+    demos: &Demographics,
     history: &MarketHistory) {
-        // before we even begin, add in the time we have for the day.
+        // before we even begin, add in the time we have for the day, and 
+        // clear out yesterday's pay.
+        self.property.todays_wage = 0.0;
         self.property.add_property(TIME_PRODUCT_ID, (self.breakdown_table.total as f64) *
-            24.0 * self.breakdown_table.average_productivity(), data);
+            24.0 * self.breakdown_table.average_productivity(demos) * 
+            self.mood_productivity(), data);
 
         // started up, so wait for the first message.
         match rx.recv().expect("Channel Broke.") {
@@ -1811,7 +3063,10 @@ impl Actor for Pop {
         // precalculate our plans for the day based on yesterday's results and
         // see if we want to sell and what we want to sell.
         self.property.sift_specific_products();
-        self.is_selling = if self.property.is_disorganized {
+        let is_drone = self.is_drone(demos);
+        self.is_selling = if is_drone {
+            false // drones leave selling to their owner.
+        } else if self.property.is_disorganized {
             true
         } else {
             // TODO add check here.
@@ -1827,14 +3082,19 @@ impl Actor for Pop {
 
         // Wait for our job to poke us, asking/telling us what to give them
         // and send it all over (will likely get a short lived channel for this)
-        // then wait for the firm to get back.
-        self.work_day_processing(rx, &tx, data);
+        // then wait for the firm to get back. Those without work skip this.
+        if self.firm.is_some() {
+            self.work_day_processing(rx, &tx, data);
+        }
 
         // The firm will return either with a paycheck, paystub if a wage
         // employee, or if it's a disorganized owner, it's share of everything.
         // Start free time section, roll between processing for wants, going
         // out to buy things, and dealing with recieved sale orders.
-        self.free_time(rx, tx, data, history, Pop::shopping_loop);
+        // Drones instead ask their owner to provide for them.
+        let free_time_loop = if is_drone { Pop::drone_loop } 
+            else { Pop::shop_and_sell_loop };
+        self.free_time(rx, tx, data, history, free_time_loop);
 
         // TODO Taxes will either be done here, or in free_time above. Methods of Taxation will need to be looked into for the system.
 
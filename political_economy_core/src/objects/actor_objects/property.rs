@@ -26,11 +26,13 @@ use std::{
 };
 
 use itertools::Itertools;
+use rand::Rng;
 
 use crate::{data_manager::DataManager, 
-    constants::{TIER_RATIO, SHOPPING_TIME_PRODUCT_ID},
+    constants::{DESIRE_EPSILON, TIER_RATIO, SHOPPING_TIME_PRODUCT_ID, SKILL_EFFICIENCY_PER_LEVEL},
     objects::data_objects::{
         item::Item,
+        process::Process,
         want_info::WantInfo,
     },
     objects::environmental_objects::market::MarketHistory
@@ -45,10 +47,22 @@ use super::{desire::Desire,
 pub struct Property {
     /// All of the desires we are storing and looking over.
     pub desires: Vec<Desire>,
+    /// Sporadic and Periodic desires which are not needed today. They are
+    /// kept out of desires until their day comes. See activate_desires().
+    pub dormant_desires: Vec<Desire>,
+    /// Toxic desires, things which harm us when we have them. They are 
+    /// kept out of desires so they add no value. See toxicity().
+    pub toxic_desires: Vec<Desire>,
     /// The property currently owned bey the actor.
     pub property: HashMap<usize, PropertyInfo>,
     /// The wants stored and not used up yet.
     pub want_store: HashMap<usize, WantInfo>,
+    /// Goods set aside for trades we have committed to but which have not
+    /// settled yet, by what they are held for and product. Held goods are 
+    /// not in property, so neither sifting nor other deals can touch them.
+    /// 
+    /// Holds only last the market day, see release_all().
+    pub held: HashMap<(Hold, usize), f64>,
     /// Whether the pop who owns this property is a disorganized firm or not.
     pub is_disorganized: bool,
     /// How much time we have worked on average over the past few days.
@@ -126,11 +140,18 @@ pub struct Property {
 
 impl Property {
     /// Creates a new desire collection based on a list of desires.
+    /// 
+    /// Toxic desires are set aside in toxic_desires.
     pub fn new(desires: Vec<Desire>) -> Self {
+        let (toxic_desires, desires) = desires.into_iter()
+            .partition(|x| x.is_toxic());
         Property {
             desires,
+            dormant_desires: vec![],
+            toxic_desires,
             property: HashMap::new(),
             want_store: HashMap::new(),
+            held: HashMap::new(),
             full_tier_satisfaction: None,
             hard_satisfaction: None,
             quantity_satisfied: 0.0,
@@ -571,6 +592,47 @@ impl Property {
             .add(*amount);
     }
 
+    /// # Provide
+    ///
+    /// Takes up to amount of the item out of our available property and
+    /// wants so it can be given to another, such as an owner providing
+    /// for their drones. Class items are taken from the matching products
+    /// in ID order.
+    ///
+    /// Returns the items taken and how much of each, either Products or
+    /// Wants.
+    pub fn provide(&mut self, data: &DataManager, item: Item, amount: f64) -> Vec<(Item, f64)> {
+        let products = match item {
+            Item::Want(want) => {
+                let given = self.want_store.get(&want)
+                    .map(|x| x.expendable()).unwrap_or(0.0).min(amount);
+                if given <= 0.0 {
+                    return vec![];
+                }
+                self.want_store.get_mut(&want).unwrap().expend(given);
+                return vec![(Item::Want(want), given)];
+            },
+            Item::Product(product) => vec![product],
+            Item::Class(class) => self.property.keys()
+                .filter(|x| data.get_product_class(**x) == Some(class))
+                .copied().sorted().collect_vec(),
+        };
+        let mut result = vec![];
+        let mut remaining = amount;
+        for product in products {
+            let given = self.property.get(&product)
+                .map(|x| x.available()).unwrap_or(0.0).min(remaining);
+            if given <= 0.0 {
+                continue;
+            }
+            self.is_sifted = false;
+            self.property.get_mut(&product).unwrap().remove(given);
+            remaining -= given;
+            result.push((Item::Product(product), given));
+        }
+        result
+    }
+
     /// Adds a desire to our collection.
     /// 
     /// If the desire is a match for an existing desire, it will 
@@ -581,6 +643,15 @@ impl Property {
     /// 
     /// If a desire is reduced to 0 or less, then it will be removed.
     pub fn add_desire(&mut self, desire: &Desire) {
+        // toxic and dormant desires are kept apart.
+        if desire.is_toxic() {
+            Property::add_to_list(&mut self.toxic_desires, desire);
+            return;
+        }
+        if self.dormant_desires.iter().any(|x| x.is_match(desire)) {
+            Property::add_to_list(&mut self.dormant_desires, desire);
+            return;
+        }
         let existing_position = self.desires.iter().position(|x| x.is_match(desire));
         
         // if the desire is a match for an exsiting one, add to that.
@@ -589,6 +660,8 @@ impl Property {
             // if this match was subtracted such that we are now at or below 0, remove it entirely.
             if self.desires[pos].amount <= 0.0 {
                 self.desires.remove(pos);
+            } else {
+                self.desires[pos].clamp_satisfaction();
             }
             return;
         }
@@ -600,6 +673,141 @@ impl Property {
         self.desires.push(dup);
     }
 
+    /// Adds the desire to the matching desire in the list, or inserts it 
+    /// if there is none. Desires reduced to 0 or less are removed.
+    fn add_to_list(list: &mut Vec<Desire>, desire: &Desire) {
+        if let Some(pos) = list.iter().position(|x| x.is_match(desire)) {
+            list[pos].amount += desire.amount;
+            if list[pos].amount <= 0.0 {
+                list.remove(pos);
+            } else {
+                list[pos].clamp_satisfaction();
+            }
+        } else if desire.amount > 0.0 {
+            list.push(desire.clone());
+        }
+    }
+
+    /// # Remove Desire
+    /// 
+    /// Reduces the matching desire, active, dormant, or toxic, by the 
+    /// amount of the desire given, capping it's satisfaction at what's 
+    /// left. Desires reduced to DESIRE_EPSILON or less are removed.
+    /// 
+    /// This unsifts our property.
+    pub fn remove_desire(&mut self, desire: &Desire) {
+        for list in [&mut self.desires, &mut self.dormant_desires, 
+        &mut self.toxic_desires] {
+            if let Some(pos) = list.iter().position(|x| x.is_match(desire)) {
+                list[pos].amount -= desire.amount;
+                if list[pos].amount <= DESIRE_EPSILON {
+                    list.remove(pos);
+                } else {
+                    list[pos].clamp_satisfaction();
+                }
+                break;
+            }
+        }
+        self.is_sifted = false;
+    }
+
+    /// # Replace Desires
+    /// 
+    /// Replaces all of our desires with those given. Those which match 
+    /// our existing desires keep their satisfaction and stay dormant if 
+    /// they were, toxic desires are set aside.
+    /// 
+    /// This unsifts our property.
+    pub fn replace_desires(&mut self, desires: Vec<Desire>) {
+        let mut active = vec![];
+        let mut dormant = vec![];
+        let mut toxic = vec![];
+        for mut desire in desires.into_iter() {
+            if desire.is_toxic() {
+                toxic.push(desire);
+            } else if self.dormant_desires.iter().any(|x| x.is_match(&desire)) {
+                dormant.push(desire);
+            } else {
+                if let Some(existing) = self.desires.iter()
+                .find(|x| x.is_match(&desire)) {
+                    desire.satisfaction = existing.satisfaction;
+                    desire.clamp_satisfaction();
+                }
+                active.push(desire);
+            }
+        }
+        self.desires = active;
+        self.dormant_desires = dormant;
+        self.toxic_desires = toxic;
+        self.is_sifted = false;
+    }
+
+    /// # Activate Desires
+    /// 
+    /// Decides which of our desires are needed on the day given. Those 
+    /// which are not (see Desire::is_active_on()) are moved to 
+    /// dormant_desires, those which are return to desires.
+    /// 
+    /// If anything changed, we unsift our property, so be sure to sift
+    /// again before relying on satisfaction.
+    pub fn activate_desires(&mut self, day: u64, rng: &mut impl Rng) {
+        let mut changed = false;
+        let mut active = vec![];
+        let mut dormant = vec![];
+        for desire in std::mem::take(&mut self.desires) {
+            if desire.is_active_on(day, rng) {
+                active.push(desire);
+            } else {
+                changed = true;
+                dormant.push(desire);
+            }
+        }
+        for desire in std::mem::take(&mut self.dormant_desires) {
+            if desire.is_active_on(day, rng) {
+                changed = true;
+                active.push(desire);
+            } else {
+                dormant.push(desire);
+            }
+        }
+        self.desires = active;
+        self.dormant_desires = dormant;
+        if changed {
+            for desire in self.dormant_desires.iter_mut() {
+                desire.satisfaction = 0.0;
+            }
+            self.unsift();
+            self.is_sifted = false;
+        }
+    }
+
+    /// # Toxicity
+    /// 
+    /// How exposed we are to our toxic desires, from 0.0 (none) to 1.0 
+    /// (everything they ask for). Each toxic desire counts the products or
+    /// wants we hold, up to its amount.
+    /// 
+    /// TODO Class items are not counted as they need the product classes.
+    pub fn toxicity(&self) -> f64 {
+        let mut limit = 0.0;
+        let mut exposure = 0.0;
+        for desire in self.toxic_desires.iter() {
+            let held = match desire.item {
+                Item::Product(id) => self.property.get(&id)
+                    .map_or(0.0, |x| x.total_property),
+                Item::Want(id) => self.want_store.get(&id)
+                    .map_or(0.0, |x| x.total_current),
+                Item::Class(_) => 0.0,
+            };
+            limit += desire.amount;
+            exposure += held.min(desire.amount);
+        }
+        if limit <= 0.0 {
+            return 0.0;
+        }
+        exposure / limit
+    }
+
     /// Finds the highest tier for a particular item which has satisfaction
     /// 
     /// Returns None if no satisfaction in any product found.
@@ -976,6 +1184,25 @@ impl Property {
         .sum()
     }
 
+    /// # Tier Zero Shortfall
+    /// 
+    /// How much of our tier 0 desires, those needed to survive, are unmet.
+    /// 
+    /// Returns a value from 0.0 (all met) to 1.0 (none met). If we have
+    /// no tier 0 desires, it returns 0.0.
+    pub fn tier_zero_shortfall(&self) -> f64 {
+        let mut needed = 0.0;
+        let mut met = 0.0;
+        for desire in self.desires.iter().filter(|x| x.start == 0) {
+            needed += desire.amount;
+            met += desire.satisfaction.min(desire.amount);
+        }
+        if needed <= 0.0 {
+            return 0.0;
+        }
+        1.0 - met / needed
+    }
+
     /// Clears self.desires
     pub fn clear_desires(&mut self) {
         self.desires.clear()
@@ -1714,6 +1941,106 @@ impl Property {
         result
     }
 
+    /// # Split Off
+    /// 
+    /// Splits off the share given of everything we have, our property, 
+    /// stored wants, desires (with their satisfaction), and wages, returning
+    /// it as a new Property. The summaries of our satisfaction are split
+    /// the same way, while the tiers reached are shared by both.
+    /// 
+    /// Our reserves are kept where they can be, what's split off coming out
+    /// of our unreserved property first. Both are left unsifted.
+    pub fn split_off(&mut self, share: f64) -> Property {
+        let share = share.clamp(0.0, 1.0);
+        let mut result = Property::new(vec![]);
+        result.desires = Property::split_desires(&mut self.desires, share);
+        result.dormant_desires = Property::split_desires(&mut self.dormant_desires, share);
+        result.toxic_desires = Property::split_desires(&mut self.toxic_desires, share);
+        for (&id, info) in self.property.iter_mut() {
+            let moved = info.total_property * share;
+            info.remove(moved);
+            result.property.insert(id, PropertyInfo::new(moved));
+        }
+        for (&id, info) in self.want_store.iter_mut() {
+            let moved = info.total_current * share;
+            info.total_current -= moved;
+            result.want_store.insert(id, WantInfo::new(moved));
+        }
+        result.full_tier_satisfaction = self.full_tier_satisfaction;
+        result.hard_satisfaction = self.hard_satisfaction;
+        result.highest_tier = self.highest_tier;
+        result.tiered_satisfaction = self.tiered_satisfaction;
+        result.quantity_satisfied = self.quantity_satisfied * share;
+        result.partial_satisfaction = self.partial_satisfaction * share;
+        result.market_satisfaction = self.market_satisfaction * share;
+        self.quantity_satisfied -= result.quantity_satisfied;
+        self.partial_satisfaction -= result.partial_satisfaction;
+        self.market_satisfaction -= result.market_satisfaction;
+        result.is_disorganized = self.is_disorganized;
+        result.pay_period = self.pay_period;
+        result.work_time = self.work_time * share;
+        result.todays_wage = self.todays_wage * share;
+        result.wage_estimate = self.wage_estimate * share;
+        result.extra_benefits = self.extra_benefits * share;
+        self.work_time -= result.work_time;
+        self.todays_wage -= result.todays_wage;
+        self.wage_estimate -= result.wage_estimate;
+        self.extra_benefits -= result.extra_benefits;
+        self.is_sifted = false;
+        result.is_sifted = false;
+        result
+    }
+
+    /// Splits the share given off of each desire, amount and satisfaction.
+    fn split_desires(desires: &mut [Desire], share: f64) -> Vec<Desire> {
+        let mut result = vec![];
+        for desire in desires.iter_mut() {
+            let mut taken = desire.clone();
+            taken.amount *= share;
+            taken.satisfaction *= share;
+            desire.amount -= taken.amount;
+            desire.satisfaction -= taken.satisfaction;
+            result.push(taken);
+        }
+        result
+    }
+
+    /// # Merge
+    /// 
+    /// Combines another property into ours, adding together property, 
+    /// stored wants, desires (with their satisfaction), and wages.
+    /// 
+    /// This unsifts our property.
+    pub fn merge(&mut self, other: Property) {
+        for (list, desires) in [(&mut self.desires, other.desires),
+        (&mut self.dormant_desires, other.dormant_desires),
+        (&mut self.toxic_desires, other.toxic_desires)] {
+            for desire in desires.into_iter() {
+                if let Some(existing) = list.iter_mut().find(|x| x.is_match(&desire)) {
+                    existing.amount += desire.amount;
+                    existing.satisfaction += desire.satisfaction;
+                } else {
+                    list.push(desire);
+                }
+            }
+        }
+        for (id, info) in other.property.into_iter() {
+            self.property.entry(id)
+                .and_modify(|x| x.add_property(info.total_property))
+                .or_insert(PropertyInfo::new(info.total_property));
+        }
+        for (id, info) in other.want_store.into_iter() {
+            self.want_store.entry(id)
+                .or_insert(WantInfo::new(0.0))
+                .add(info.total_current);
+        }
+        self.work_time += other.work_time;
+        self.todays_wage += other.todays_wage;
+        self.wage_estimate += other.wage_estimate;
+        self.extra_benefits += other.extra_benefits;
+        self.is_sifted = false;
+    }
+
     /// # Cheap Clone
     /// 
     /// Creates a cheap clone of ourself, copying over property and desires.
@@ -1722,6 +2049,8 @@ impl Property {
     pub fn cheap_clone(&self) -> Self {
         // copy desires
         let mut result = Property::new(self.desires.clone());
+        result.dormant_desires = self.dormant_desires.clone();
+        result.toxic_desires = self.toxic_desires.clone();
         // copy property
         for (&id, info) in self.property.iter() {
             result.property.insert(id, PropertyInfo::new(info.total_property));
@@ -2239,8 +2568,12 @@ impl Property {
     /// 
     /// # Note
     /// 
-    /// Currently unused as we've chosen a less efficient, but simpler method of dealing with available shopping time.
-    pub fn _available_shopping_time(&self, data: &DataManager, _skill_level: f64, _skill: usize) -> f64 {
+    /// Processes which use the skill's product get an efficiency bonus of
+    /// skill_level * SKILL_EFFICIENCY_PER_LEVEL, matching Pop::skill_efficiency.
+    /// 
+    /// Used to check whether we can cover handling costs before committing 
+    /// to a trade, see available_handling_time().
+    pub fn available_shopping_time(&self, data: &DataManager, skill_level: f64, skill: usize) -> f64 {
         // first extract our available resources
         let mut available_products = HashMap::new();
         for (&id, &info) in self.property.iter() {
@@ -2257,9 +2590,9 @@ impl Property {
         .processes.iter() // the process IDs which time is related to
         .map(|x| data.processes.get(x).unwrap()) // the process info
         .filter(|x| x.outputs_product(SHOPPING_TIME_PRODUCT_ID)) { // the processes which output it.
-            // todo, pass in pop_skill info later. Ignore for now
+            let boni = Property::skill_boni(process, data, skill_level, skill);
             let result = process.do_process(&available_products, 
-                &available_wants, 0.0, 
+                &available_wants, boni, 
                 None, false, data);
             // check result availability
             if result.iterations > 0.0 {
@@ -2287,6 +2620,17 @@ impl Property {
         max_available
     }
 
+    /// The efficiency bonus a skill gives a process, if the process uses the
+    /// skill's product. See available_shopping_time().
+    fn skill_boni(process: &Process, data: &DataManager, skill_level: f64, 
+    skill: usize) -> f64 {
+        match data.skills.get(&skill).and_then(|x| x.product) {
+            Some(product) if process.uses_product(product, data) => 
+                skill_level * SKILL_EFFICIENCY_PER_LEVEL,
+            _ => 0.0,
+        }
+    }
+
     /// # Get Shopping Time
     /// 
     /// As available_shopping_time(), but instead of returning a total estimate it tries to get
@@ -2304,14 +2648,15 @@ impl Property {
     /// 
     /// ## Note
     /// 
-    /// Processes are selected in ID order.
+    /// Processes are selected in ID order, and get the same skill bonus as in
+    /// available_shopping_time().
     /// 
     /// TODO this can likely be repurposed into a more general "get X units of y product function"
     /// TODO Improve to prioritize by market cost of the process. Eventually.
     /// TODO Improve this to only take from satisfaction above a certain point, not more.
     /// TODO Sift Improvement: When Sift has been improved to function non-destructively, this can be upgraded to take from satisfaction as well.
     pub fn get_shopping_time(&mut self, target: f64, data: &DataManager, 
-    _market: &MarketHistory, _cutoff: Option<DesireCoord>) -> f64 {
+    skill_level: f64, skill: usize, _market: &MarketHistory, _cutoff: Option<DesireCoord>) -> f64 {
         // get the final output ready.
         let mut final_result = 0.0;
         // first extract from storage any Shopping Time we have available and waiting to use.
@@ -2336,10 +2681,12 @@ impl Property {
         .filter(|x| x.outputs_product(SHOPPING_TIME_PRODUCT_ID)) // the processes which output it.
         .sorted_by(|a, b| a.id.cmp(&b.id)) { // ID order.
             // try to do the process up to our target output.
+            let boni = Property::skill_boni(process, data, skill_level, skill);
             let iter_target = (target - final_result) / // the remaining target
-                process.effective_output_of(Item::Product(SHOPPING_TIME_PRODUCT_ID)); // how much an iteration completes
+                (process.effective_output_of(Item::Product(SHOPPING_TIME_PRODUCT_ID)) 
+                    * (1.0 + boni)); // how much an iteration completes
             let proc_result = process.do_process_with_property(&self.property, 
-                &available_wants, 0.0, Some(iter_target), false, data, false);
+                &available_wants, boni, Some(iter_target), false, data, false);
             if proc_result.iterations == 0.0 {
                 continue; // if no successful iterations, skip.
             }
@@ -2468,6 +2815,174 @@ impl Property {
         }
     }
 
+    /// # Handling Cost
+    /// 
+    /// The time needed to handle all of the goods moved in an exchange, 
+    /// given (negative) or recieved (positive), based on their mass and bulk.
+    /// 
+    /// Both sides of an exchange pay this, as both have to move the goods 
+    /// and the payment.
+    pub fn handling_cost(exchange: &HashMap<usize, f64>, data: &DataManager) -> f64 {
+        exchange.iter()
+            .map(|(prod, &quant)| data.products.get(prod)
+                .expect("Product not found!").handling_cost(quant))
+            .sum()
+    }
+
+    /// # Available Handling Time
+    /// 
+    /// How much time we could put towards handling goods, from our 
+    /// Handling Time product (if the data has one), our spare Shopping Time,
+    /// and the Shopping Time we could make from our unreserved property with
+    /// the skill given, see available_shopping_time().
+    /// 
+    /// This does not consume anything.
+    pub fn available_handling_time(&self, data: &DataManager, skill_level: f64,
+    skill: usize) -> f64 {
+        let mut result = self.available_shopping_time(data, skill_level, skill);
+        if let Some(info) = self.property.get(&SHOPPING_TIME_PRODUCT_ID) {
+            result += info.available();
+        }
+        if let Some(handling) = data.handling_time_product {
+            if let Some(info) = self.property.get(&handling) {
+                result += info.available();
+            }
+        }
+        result
+    }
+
+    /// # Handleable Share
+    /// 
+    /// The share (0.0 to 1.0) of an exchange whose handling cost we could 
+    /// pay right now. Used to reject or scale down trades we couldn't 
+    /// handle before committing to them.
+    pub fn handleable_share(&self, exchange: &HashMap<usize, f64>, 
+    data: &DataManager, skill_level: f64, skill: usize) -> f64 {
+        let cost = Property::handling_cost(exchange, data);
+        if cost <= 0.0 {
+            return 1.0;
+        }
+        (self.available_handling_time(data, skill_level, skill) / cost).min(1.0)
+    }
+
+    /// # Pay Handling Cost
+    /// 
+    /// Pays the time needed to handle all the goods moved in an exchange,
+    /// see handling_cost().
+    /// 
+    /// The cost is paid from any Handling Time product we have first (if 
+    /// the data has one), then from Shopping Time via get_shopping_time().
+    /// 
+    /// Returns how much of the cost could not be paid. Trades should be 
+    /// checked with handleable_share() before being agreed to, so this 
+    /// should only be left unpaid if our time was spent elsewhere since.
+    pub fn pay_handling_cost(&mut self, exchange: &HashMap<usize, f64>,
+    data: &DataManager, skill_level: f64, skill: usize, 
+    market: &MarketHistory) -> f64 {
+        let mut remaining = Property::handling_cost(exchange, data);
+        if remaining <= 0.0 {
+            return 0.0;
+        }
+        if let Some(handling) = data.handling_time_product {
+            if let Some(info) = self.property.get_mut(&handling) {
+                let shift = info.available().min(remaining);
+                info.remove(shift);
+                info.spent += shift;
+                remaining -= shift;
+            }
+        }
+        if remaining > 0.0 {
+            remaining -= self.get_shopping_time(remaining, data, skill_level, 
+                skill, market, None);
+        }
+        remaining
+    }
+
+    /// # Hold
+    /// 
+    /// Takes up to the amount given of the product out of our available 
+    /// property and holds it for the reason given.
+    /// 
+    /// Returns how much was held.
+    pub fn hold(&mut self, reason: Hold, product: usize, amount: f64,
+    data: &DataManager) -> f64 {
+        let available = self.property.get(&product)
+            .map(|x| x.available()).unwrap_or(0.0);
+        let amount = amount.min(available);
+        if amount <= 0.0 {
+            return 0.0;
+        }
+        self.remove_property(product, amount, data);
+        *self.held.entry((reason, product)).or_insert(0.0) += amount;
+        amount
+    }
+
+    /// # Release
+    /// 
+    /// Returns up to the amount given of a held product to our property.
+    /// 
+    /// Returns how much was released.
+    pub fn release(&mut self, reason: Hold, product: usize, amount: f64,
+    data: &DataManager) -> f64 {
+        let held = match self.held.get_mut(&(reason, product)) {
+            Some(held) => held,
+            None => return 0.0,
+        };
+        let amount = amount.min(*held);
+        *held -= amount;
+        if *held <= 0.0 {
+            self.held.remove(&(reason, product));
+        }
+        if amount > 0.0 {
+            self.add_property(product, amount, data);
+        }
+        amount
+    }
+
+    /// # Release Hold
+    /// 
+    /// Returns everything held for the reason given to our property.
+    pub fn release_hold(&mut self, reason: Hold, data: &DataManager) {
+        let products = self.held.keys()
+            .filter(|(held_for, _)| *held_for == reason)
+            .map(|&(_, product)| product)
+            .collect_vec();
+        for product in products {
+            self.release(reason, product, f64::INFINITY, data);
+        }
+    }
+
+    /// # Move Hold
+    /// 
+    /// Moves everything held for one reason to another, without returning 
+    /// it to property in between.
+    pub fn move_hold(&mut self, from: Hold, to: Hold) {
+        let products = self.held.keys()
+            .filter(|(held_for, _)| *held_for == from)
+            .map(|&(_, product)| product)
+            .collect_vec();
+        for product in products {
+            let amount = self.held.remove(&(from, product)).unwrap();
+            *self.held.entry((to, product)).or_insert(0.0) += amount;
+        }
+    }
+
+    /// # Release All
+    /// 
+    /// Returns everything we are holding to our property. Called at the end
+    /// of the market day, once nothing can settle against our holds.
+    pub fn release_all(&mut self, data: &DataManager) {
+        if self.held.is_empty() {
+            return;
+        }
+        for ((_, product), amount) in self.held.drain().collect_vec() {
+            self.property.entry(product)
+                .and_modify(|x| x.add_property(amount))
+                .or_insert(PropertyInfo::new(amount));
+        }
+        self.sift_all(data);
+    }
+
     /// # Record Exchange
     /// 
     /// Records the results of an exchange in various property data.
@@ -2558,6 +3073,22 @@ impl Property {
     }
 }
 
+/// # Hold
+/// 
+/// What goods held out of property are set aside for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hold {
+    /// Backing the asks we have resting in the order book.
+    Ask,
+    /// Backing our bid in the order book for the product.
+    Bid(usize),
+    /// Backing an auction we have asked to open, until the market gives
+    /// it an id.
+    Opening,
+    /// Backing an auction, by id, which we organized or bid on.
+    Auction(usize),
+}
+
 /// # Time Breakdown
 /// 
 /// 
@@ -18,6 +18,13 @@ pub struct State {
     pub id: usize,
     pub name: String,
     pub variant_name: String,
+    /// The culture the state promotes, if any.
+    pub official_culture: Option<usize>,
+    /// The ideology the state promotes, if any.
+    pub official_ideology: Option<usize>,
+    /// How strongly the state pushes its official culture and ideology on
+    /// those in its markets. 0 is no pressure.
+    pub conversion_policy: f64,
 }
 
 impl State {
@@ -2,6 +2,7 @@ pub mod item;
 pub mod process_node;
 pub mod process;
 pub mod product;
+pub mod skill;
 pub mod technology;
 pub mod technology_family;
 pub mod want_info;
@@ -339,7 +339,9 @@ impl Process {
     /// 
     /// TODO Include logic for process part tags: Consumption, Investment, Pollutant, Chance(char, usize)
     /// 
-    /// TODO pop_skill and other_efficiency_boni are currently not taken into account.
+    /// other_efficiency_boni increases all outputs by that fraction (0.1 gives
+    /// 10% more outputs) without changing inputs or capital. Pop skill enters 
+    /// here through Pop::skill_efficiency.
     /// 
     /// TODO hard_cap is not taken into account, assumed to always be true currently.
     /// 
@@ -352,7 +354,7 @@ impl Process {
     /// FIXME Does not handle Overlapping or duplicate products at all. Do Not Use Overlapping Classes or Duplicate Products
     pub fn do_process(&self, available_products: &HashMap<usize, f64>, 
     available_wants: &HashMap<usize, f64>, 
-    _other_efficiency_boni: f64, target: Option<f64>, _hard_cap: bool, 
+    other_efficiency_boni: f64, target: Option<f64>, _hard_cap: bool, 
     data: &DataManager) 
     -> ProcessOutputs {
         let mut results = ProcessOutputs::new();
@@ -559,7 +561,7 @@ impl Process {
                     continue;
                 },
                 ProcessSectionTag::Input => { in_out_sign = -1.0; }, // subtract inputs
-                ProcessSectionTag::Output => { in_out_sign = 1.0; }, // add outputs
+                ProcessSectionTag::Output => { in_out_sign = 1.0 + other_efficiency_boni; }, // add outputs, boosted by efficiency
             } 
             // if not capital, add to appropriate input_output
             match process_part.item {
@@ -718,7 +720,9 @@ impl Process {
     /// 
     /// TODO Include logic for process part tags: Consumption, Optional(f64), Fixed, Investment, Pollutant, Chance(char, usize)
     /// 
-    /// TODO pop_skill and other_efficiency_boni are currently not taken into account.
+    /// other_efficiency_boni increases all outputs by that fraction (0.1 gives
+    /// 10% more outputs) without changing inputs or capital. Pop skill enters 
+    /// here through Pop::skill_efficiency.
     /// 
     /// TODO hard_cap is not taken into account, assumed to always be true currently.
     /// 
@@ -730,7 +734,7 @@ impl Process {
     /// 
     /// FIXME Does not handle Overlapping or duplicate products at all. Do Not Use Overlapping Classes or Duplicate Products
     pub fn do_process_with_property(&self, available_products: &HashMap<usize, PropertyInfo>, 
-    available_wants: &HashMap<usize, f64>, _other_efficiency_boni: f64, 
+    available_wants: &HashMap<usize, f64>, other_efficiency_boni: f64, 
     target: Option<f64>, _hard_cap: bool, data: &DataManager,
     allow_reserves: bool) 
     -> ProcessOutputs {
@@ -822,7 +826,7 @@ impl Process {
                     continue;
                 },
                 ProcessSectionTag::Input => { _in_out_sign = -1.0; }, // subtract inputs
-                ProcessSectionTag::Output => { _in_out_sign = 1.0; }, // add outputs
+                ProcessSectionTag::Output => { _in_out_sign = 1.0 + other_efficiency_boni; }, // add outputs, boosted by efficiency
             } 
             // if not capital, add to appropriate input_output
             match process_part.item {
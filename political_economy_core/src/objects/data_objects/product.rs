@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
 
+use crate::constants::{HANDLING_BULK_COST, HANDLING_MASS_COST};
+
 // use crate::data_manager::DataManager;
 
 use super::process::Process;
@@ -133,6 +135,22 @@ impl Product {
         0.0
     }
 
+    /// # Is Transportable
+    /// 
+    /// Whether the product can be moved out of it's market. Fixed and 
+    /// Service products cannot.
+    pub fn is_transportable(&self) -> bool {
+        !self.tags.iter().any(|x| matches!(x, ProductTag::Fixed | ProductTag::Service))
+    }
+
+    /// # Handling Cost
+    /// 
+    /// The time needed to handle the given quantity of this product in an 
+    /// exchange, scaling with it's mass and bulk.
+    pub fn handling_cost(&self, quantity: f64) -> f64 {
+        quantity.abs() * (self.mass * HANDLING_MASS_COST + self.bulk * HANDLING_BULK_COST)
+    }
+
     /// # Product New Function
     /// 
     /// Creates a new empty prdouct. Created to allow for possible sanity 
@@ -0,0 +1,67 @@
+/// A Skill is a learned ability which pops develop by working in a job
+/// which uses it.
+///
+/// Skills may be attached to a product (typically a fractional,
+/// NonTransferrable one like Discernment, see DataManager::required_items)
+/// which processes can use as an optional capital, allowing more skilled
+/// pops to get more out of the same process.
+#[derive(Debug)]
+pub struct Skill {
+    /// The unique id of the skill.
+    pub id: usize,
+    /// The name of the skill.
+    pub name: String,
+    /// A short description of the skill.
+    pub description: String,
+    /// The product which represents the skill in processes, if any.
+    pub product: Option<usize>,
+    /// The Skill Groups this skill belongs to.
+    pub groups: Vec<usize>,
+}
+
+impl Skill {
+    pub fn new(id: usize, name: String, description: String,
+    product: Option<usize>) -> Result<Self, String> {
+        if name.is_empty() {
+            return Result::Err(String::from("Skill must have a name."));
+        }
+        Result::Ok(Self { id, name, description, product, groups: vec![] })
+    }
+}
+
+/// A Skill Group is a collection of related skills, such as farming or
+/// crafting. Species may be more or less talented at entire groups of
+/// skills, which modifies how quickly they learn any skill in the group.
+#[derive(Debug)]
+pub struct SkillGroup {
+    /// The unique id of the skill group.
+    pub id: usize,
+    /// The name of the skill group.
+    pub name: String,
+    /// A short description of the skill group.
+    pub description: String,
+    /// The skills which are within this group.
+    pub skills: Vec<usize>,
+}
+
+impl SkillGroup {
+    pub fn new(id: usize, name: String, description: String) -> Result<Self, String> {
+        if name.is_empty() {
+            return Result::Err(String::from("Skill Group must have a name."));
+        }
+        Result::Ok(Self { id, name, description, skills: vec![] })
+    }
+
+    /// # Add Skill
+    ///
+    /// Connects a skill to this group, recording the connection in both
+    /// the group and the skill. Duplicates are ignored.
+    pub fn add_skill(&mut self, skill: &mut Skill) {
+        if !self.skills.contains(&skill.id) {
+            self.skills.push(skill.id);
+        }
+        if !skill.groups.contains(&self.id) {
+            skill.groups.push(self.id);
+        }
+    }
+}
@@ -83,7 +83,7 @@ impl WantInfo {
         debug_assert!(value > 0.0, "Value is negative.");
         self.total_current -= value;
         self.expended += value;
-        debug_assert!( self.expendable() > 0.0, 
+        debug_assert!( self.expendable() >= 0.0, 
             "Expended more than was available.");
     }
 
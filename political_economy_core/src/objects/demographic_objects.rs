@@ -1,4 +1,9 @@
+pub mod civilization;
+pub mod conversion;
 pub mod culture;
 pub mod ideology;
+pub mod ideology_spread;
+pub mod movement;
 pub mod pop_breakdown_table;
+pub mod reproduction;
 pub mod species;
\ No newline at end of file
@@ -0,0 +1,136 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::objects::data_objects::technology::Technology;
+
+/// A Civilization, the shared technological and cultural base of a group of
+/// markets. 
+/// 
+/// Civilizations move through Epochs (ages) as they learn the technologies
+/// of each tier.
+#[derive(Debug)]
+pub struct Civilization {
+    /// The Id of the civilization.
+    pub id: usize,
+    /// The name of the civilization.
+    pub name: String,
+    /// The variant name of the civilization, used to distinguish it from 
+    /// its siblings.
+    pub variant_name: String,
+    /// The epochs of the civilization, in the order in which they occur.
+    pub epochs: Vec<Epoch>,
+    /// The epoch the civilization is currently in. None if it has not yet 
+    /// reached its first epoch.
+    pub current_epoch: Option<usize>,
+    /// The technologies the civilization knows.
+    pub known_technologies: HashSet<usize>,
+    /// The markets which belong to the civilization.
+    pub markets: HashSet<usize>,
+}
+
+impl Civilization {
+    pub fn new(id: usize, name: String, variant_name: String) -> Result<Self, String> {
+        if name.trim().is_empty() {
+            return Err("'name' cannot be empty or whitespace.".into());
+        }
+        Ok(Self { id, name, variant_name, epochs: vec![], current_epoch: None,
+            known_technologies: HashSet::new(), markets: HashSet::new() })
+    }
+
+    pub fn get_name(&self) -> String {
+        format!("{}({})", self.name, self.variant_name)
+    }
+
+    /// # Add Epoch
+    /// 
+    /// Adds an epoch after those already in the civilization.
+    pub fn add_epoch(&mut self, epoch: Epoch) {
+        self.epochs.push(epoch);
+    }
+
+    /// The epoch the civilization is currently in, if any.
+    pub fn epoch(&self) -> Option<&Epoch> {
+        self.current_epoch.map(|x| &self.epochs[x])
+    }
+
+    /// # Known Share
+    /// 
+    /// The share of the technologies in the tier given which the 
+    /// civilization knows. A tier with no technologies is fully known.
+    pub fn known_share(&self, tier: i64, technology: &HashMap<usize, Technology>) -> f64 {
+        let in_tier: Vec<usize> = technology.values()
+            .filter(|x| x.tier == tier)
+            .map(|x| x.id)
+            .collect();
+        if in_tier.is_empty() {
+            return 1.0;
+        }
+        in_tier.iter().filter(|x| self.known_technologies.contains(x)).count() as f64
+            / in_tier.len() as f64
+    }
+
+    /// # Advance Epoch
+    /// 
+    /// Moves the civilization into the next epoch for as long as it knows 
+    /// enough of the technologies in that epoch's tier. Entering an epoch
+    /// teaches the civilization the technologies of it's level.
+    /// 
+    /// Returns true if the epoch changed.
+    pub fn advance_epoch(&mut self, technology: &HashMap<usize, Technology>) -> bool {
+        let start = self.current_epoch;
+        loop {
+            let next = self.current_epoch.map_or(0, |x| x + 1);
+            match self.epochs.get(next) {
+                Some(epoch) if self.known_share(epoch.tier, technology) >= epoch.threshold => {
+                    self.known_technologies.extend(epoch.technologies.iter());
+                    self.current_epoch = Some(next);
+                },
+                _ => break,
+            }
+        }
+        self.current_epoch != start
+    }
+
+    /// # Learn Technologies
+    /// 
+    /// Adds the technologies known in each market to what the civilization
+    /// knows, for those markets which belong to it.
+    pub fn learn_technologies(&mut self, market_technologies: &HashMap<usize, HashSet<usize>>) {
+        for market in self.markets.iter() {
+            if let Some(known) = market_technologies.get(market) {
+                self.known_technologies.extend(known.iter());
+            }
+        }
+    }
+}
+
+/// An Epoch is an age of a civilization. It is reached once enough of the 
+/// technologies in its tier are known, bringing it's technology level with 
+/// it, and sets the defaults for those in the civilization while it lasts.
+#[derive(Debug)]
+pub struct Epoch {
+    /// The name of the epoch.
+    pub name: String,
+    /// The technology tier which defines the epoch.
+    pub tier: i64,
+    /// The share of the tier's technologies which must be known to enter 
+    /// the epoch. Between 0 and 1.
+    pub threshold: f64,
+    /// The technology level of the epoch, the technologies everyone in the
+    /// civilization knows once it's reached.
+    pub technologies: HashSet<usize>,
+    /// The culture newborns without one are raised in during this epoch.
+    pub default_culture: Option<usize>,
+}
+
+impl Epoch {
+    pub fn new(name: String, tier: i64, threshold: f64, technologies: HashSet<usize>,
+        default_culture: Option<usize>) -> Result<Self, String> {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err("'threshold' must be between 0 and 1.".into());
+            }
+            else if name.trim().is_empty() {
+                return Err("'name' cannot be empty or whitespace.".into());
+            }
+            Ok(Self { name, tier, threshold, technologies, default_culture })
+        }
+}
@@ -0,0 +1,128 @@
+//! People adopting the culture or ideology of those around them.
+//!
+//! Over time, people are drawn toward the dominant culture and ideology of
+//! their market, and toward those the local states promote. Those already
+//! close to the target, being related to it, convert more easily.
+
+use std::collections::HashMap;
+
+use crate::{constants::{CONVERSION_RATE, RELATED_CONVERSION_MULTIPLIER},
+    objects::actor_objects::state::State};
+
+use super::pop_breakdown_table::PopBreakdownTable;
+
+/// Whether a conversion was of culture or ideology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConversionKind {
+    /// Cultural assimilation.
+    Culture,
+    /// Religious or ideological conversion.
+    Ideology,
+}
+
+/// # Conversion Pressure
+///
+/// The pull toward a culture or ideology in a market.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConversionPressure {
+    /// The culture or ideology people are drawn to.
+    pub target: usize,
+    /// The share of the market which already has the target.
+    pub share: f64,
+    /// How strongly the local states promote the target. 0 if they don't.
+    pub policy: f64,
+}
+
+impl ConversionPressure {
+    /// # Yearly Rate
+    ///
+    /// The share of people who convert to the target each year. Grows with
+    /// how common the target is and state promotion of it, and is 
+    /// multiplied by RELATED_CONVERSION_MULTIPLIER if they're related to it.
+    pub fn yearly_rate(&self, related: bool) -> f64 {
+        let rate = CONVERSION_RATE * (self.share + self.policy);
+        if related { rate * RELATED_CONVERSION_MULTIPLIER } else { rate }
+    }
+}
+
+/// # Conversion Pressures
+///
+/// All the pulls toward cultures and ideologies in a market.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConversionPressures {
+    /// The cultures people are drawn to.
+    pub cultures: Vec<ConversionPressure>,
+    /// The ideologies people are drawn to.
+    pub ideologies: Vec<ConversionPressure>,
+}
+
+impl ConversionPressures {
+    /// # Of Market
+    ///
+    /// Gets the pressures in a market from its combined breakdown and the
+    /// states present in it. The dominant culture and ideology pull on
+    /// everyone, as do those the states make official.
+    pub fn of_market<'a>(breakdown: &PopBreakdownTable, 
+    states: impl Iterator<Item = &'a State>) -> Self {
+        let culture_shares = breakdown.culture_division();
+        let ideology_shares = breakdown.ideology_division();
+        let mut result = ConversionPressures::default();
+        if let Some((target, share)) = ConversionPressures::dominant(&culture_shares) {
+            result.cultures.push(ConversionPressure { target, share, policy: 0.0 });
+        }
+        if let Some((target, share)) = ConversionPressures::dominant(&ideology_shares) {
+            result.ideologies.push(ConversionPressure { target, share, policy: 0.0 });
+        }
+        for state in states {
+            if let Some(target) = state.official_culture {
+                let share = culture_shares.get(&Some(target)).copied().unwrap_or(0.0);
+                ConversionPressures::promote(&mut result.cultures, target, 
+                    share, state.conversion_policy);
+            }
+            if let Some(target) = state.official_ideology {
+                let share = ideology_shares.get(&Some(target)).copied().unwrap_or(0.0);
+                ConversionPressures::promote(&mut result.ideologies, target, 
+                    share, state.conversion_policy);
+            }
+        }
+        result
+    }
+
+    /// The most common culture or ideology in the shares, ignoring those 
+    /// without one. Ties go to the lowest id.
+    fn dominant(shares: &HashMap<Option<usize>, f64>) -> Option<(usize, f64)> {
+        shares.iter()
+            .filter_map(|(id, share)| id.map(|id| (id, *share)))
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+    }
+
+    /// Adds the state promotion to the target's pressure, adding the 
+    /// target if it isn't there yet.
+    fn promote(pressures: &mut Vec<ConversionPressure>, target: usize, 
+    share: f64, policy: f64) {
+        if let Some(existing) = pressures.iter_mut().find(|x| x.target == target) {
+            existing.policy += policy;
+        } else {
+            pressures.push(ConversionPressure { target, share, policy });
+        }
+    }
+}
+
+/// # Conversion Record
+///
+/// A log entry for people in a pop who changed their culture or ideology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConversionRecord {
+    /// The pop the converts are in.
+    pub pop: usize,
+    /// The market the pop is in.
+    pub market: usize,
+    /// Whether they changed culture or ideology.
+    pub kind: ConversionKind,
+    /// The culture or ideology they left, if any.
+    pub from: Option<usize>,
+    /// The culture or ideology they joined.
+    pub to: usize,
+    /// How many people converted.
+    pub count: usize,
+}
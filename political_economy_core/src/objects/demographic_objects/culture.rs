@@ -5,7 +5,9 @@ use crate::objects::actor_objects::desire::Desire;
 /// 
 /// Can be changed occasionally throughout a pop's life.
 /// 
-/// Will eventually contain data for Classes and Generations.
+/// Cultures are divided into Classes (social strata) and Generations (the
+/// era someone was raised in). Their combined effects are summarized in the
+/// culture's data table.
 #[derive(Debug)]
 pub struct Culture{
     /// The Id of the culture.
@@ -37,6 +39,32 @@ pub struct Culture{
     /// The ids of cultures which are related to this one. Parents, Siblings, 
     /// and Children.
     pub relations: Vec<usize>,
+    /// The social classes of the culture.
+    pub classes: Vec<CultureClass>,
+    /// The generations of the culture, in the order in which they occur.
+    pub generations: Vec<Generation>,
+    /// The generation which newborns of this culture are raised in.
+    /// None if the culture has no generations.
+    pub current_generation: Option<usize>,
+    /// How many days the current generation has been the one being born into.
+    pub generation_progress: f64,
+    /// The summarized effects of a culture, class, and generation combo.
+    /// The indices of these are defined such that
+    /// class_slot + (generation_slot * (len(classes) + 1)) = index of class + generation.
+    /// 
+    /// Slot 0 is for no class (or generation), slot i + 1 is for class i.
+    pub data_table: Vec<CultureSubentry>,
+}
+
+/// The summarized effects of a culture, class, and generation combination.
+#[derive(Debug, Clone)]
+pub struct CultureSubentry {
+    /// The summarized desires for a member of this culture+class+generation.
+    pub desires: Vec<Desire>,
+    /// The final birth rate modifier for a member of this culture+class+generation.
+    pub birth_rate_modifier: f64,
+    /// The final mortality rate modifier for a member of this culture+class+generation.
+    pub mortality_rate_modifier: f64,
 }
 
 impl Culture {
@@ -47,12 +75,179 @@ impl Culture {
             if name.trim().is_empty() {
                 return Err("'name' cannot be empty or contain only whitespace.".into());
             }
-            Ok(Self { id, name, variant_name, birth_rate_modifier, 
+            let mut result = Self { id, name, variant_name, birth_rate_modifier, 
                 mortality_rate_modifier, productivity_modifier, 
-                desires, relations })
+                desires, relations, classes: vec![], generations: vec![],
+                current_generation: None, generation_progress: 0.0,
+                data_table: vec![] };
+            result.update_data_table();
+            Ok(result)
             }
 
     pub fn get_name(&self) -> String {
         format!("{}({})", self.name, self.variant_name)
     }
+
+    /// # Add Class
+    /// 
+    /// Adds a class to the culture, then updates the data table.
+    pub fn add_class(&mut self, class: CultureClass) {
+        self.classes.push(class);
+        self.update_data_table();
+    }
+
+    /// # Add Generation
+    /// 
+    /// Adds a generation after those already in the culture, then updates 
+    /// the data table. If it's our first generation, it becomes the current 
+    /// one.
+    pub fn add_generation(&mut self, generation: Generation) {
+        self.generations.push(generation);
+        if self.current_generation.is_none() {
+            self.current_generation = Some(0);
+        }
+        self.update_data_table();
+    }
+
+    /// Updates the data table in the culture, consolidating the class and 
+    /// generation effects into a singular lookup table.
+    pub fn update_data_table(&mut self) {
+        self.data_table.clear();
+        for generation_slot in 0..=self.generations.len() {
+            for class_slot in 0..=self.classes.len() {
+                let mut entry = CultureSubentry {
+                    desires: self.desires.clone(),
+                    birth_rate_modifier: self.birth_rate_modifier,
+                    mortality_rate_modifier: self.mortality_rate_modifier,
+                };
+                if class_slot > 0 {
+                    let class = &self.classes[class_slot - 1];
+                    entry.birth_rate_modifier += class.birth_rate_modifier;
+                    entry.mortality_rate_modifier += class.mortality_rate_modifier;
+                    Desire::overlay_desires(&mut entry.desires, &class.desires);
+                }
+                if generation_slot > 0 {
+                    let generation = &self.generations[generation_slot - 1];
+                    Desire::overlay_desires(&mut entry.desires, &generation.desires);
+                }
+                self.data_table.push(entry);
+            }
+        }
+    }
+
+    /// # Get Subentry
+    /// 
+    /// Gets the summarized data for the class and generation given.
+    pub fn get_subentry(&self, class: Option<usize>, 
+    generation: Option<usize>) -> Result<&CultureSubentry, String> {
+        let class_slot = class.map_or(0, |x| x + 1);
+        let generation_slot = generation.map_or(0, |x| x + 1);
+        if class_slot > self.classes.len() || generation_slot > self.generations.len() {
+            return Err("Invalid Class or Generation Index given".into());
+        }
+        match self.data_table.get(class_slot + (generation_slot * (self.classes.len() + 1))) {
+            Some(val) => Ok(val),
+            None => Err("Invalid Class or Generation Index given".into())
+        }
+    }
+
+    /// # Class Can Work
+    /// 
+    /// Whether members of the class given may take the job given. Those 
+    /// without a class, or in a class without listed jobs, can work any job.
+    pub fn class_can_work(&self, class: Option<usize>, job: usize) -> bool {
+        match class {
+            Some(idx) => {
+                let class = self.classes.get(idx).expect("Class Not Found!");
+                class.jobs.is_empty() || class.jobs.contains(&job)
+            },
+            None => true,
+        }
+    }
+
+    /// # Advance Generation
+    /// 
+    /// Moves time forward for the culture by the days given. Once the current
+    /// generation's span has passed, newborns are raised in the next 
+    /// generation. The last generation continues indefinitely.
+    /// 
+    /// Returns true if the current generation changed.
+    pub fn advance_generation(&mut self, days: f64) -> bool {
+        let mut current = match self.current_generation {
+            Some(current) => current,
+            None => return false,
+        };
+        let start = current;
+        self.generation_progress += days;
+        while current + 1 < self.generations.len() && 
+        self.generation_progress >= self.generations[current].span as f64 {
+            self.generation_progress -= self.generations[current].span as f64;
+            current += 1;
+        }
+        self.current_generation = Some(current);
+        current != start
+    }
+}
+
+/// A Class is a social stratum within a culture, such as peasants or nobles.
+/// Members are born into their parents' class.
+#[derive(Debug)]
+pub struct CultureClass {
+    /// The name of the class.
+    pub name: String,
+    /// The jobs which members of this class may take. If empty, they may
+    /// take any job.
+    pub jobs: Vec<usize>,
+    /// The birth rate modifier of the class, added to the culture's modifier.
+    pub birth_rate_modifier: f64,
+    /// The mortality rate modifier of the class, added to the culture's 
+    /// modifier.
+    pub mortality_rate_modifier: f64,
+    /// The desires the class has different from the base culture.
+    /// 
+    /// Desires here may have negative values, and if they do they cancel out 
+    /// the desire from the culture. This subtraction cannot push the desire 
+    /// below 0.
+    pub desires: Vec<Desire>,
+}
+
+impl CultureClass {
+    pub fn new(name: String, jobs: Vec<usize>, birth_rate_modifier: f64,
+        mortality_rate_modifier: f64, desires: Vec<Desire>) -> Result<Self, String> {
+            if name.trim().is_empty() {
+                return Err("'name' cannot be empty or whitespace.".into());
+            }
+            Ok(Self { name, jobs, birth_rate_modifier, 
+                mortality_rate_modifier, desires })
+        }
+}
+
+/// A Generation is the era in which members of a culture were raised. 
+/// Newborns are raised in the culture's current generation, and as that 
+/// changes, the culture's desires shift as older generations die off.
+#[derive(Debug)]
+pub struct Generation {
+    /// The name of the generation.
+    pub name: String,
+    /// How long newborns are raised in this generation before the next
+    /// begins, measured in standard in game days.
+    pub span: u64,
+    /// The desires the generation has different from the base culture.
+    /// 
+    /// Desires here may have negative values, and if they do they cancel out 
+    /// the desire from the culture. This subtraction cannot push the desire 
+    /// below 0.
+    pub desires: Vec<Desire>,
+}
+
+impl Generation {
+    pub fn new(name: String, span: u64, desires: Vec<Desire>) -> Result<Self, String> {
+            if span == 0 {
+                return Err("'span' must be greater than 0.".into());
+            }
+            else if name.trim().is_empty() {
+                return Err("'name' cannot be empty or whitespace.".into());
+            }
+            Ok(Self { name, span, desires })
+        }
 }
\ No newline at end of file
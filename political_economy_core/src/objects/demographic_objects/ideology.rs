@@ -9,7 +9,9 @@ use crate::objects::actor_objects::desire::Desire;
 /// Can change relatively often, many people should be expected to change
 /// this throughout their life.
 /// 
-/// Will eventually contain data for Waves and Factions.
+/// Ideologies are divided into Waves (successive versions of the ideology)
+/// and Factions (groups within it). Their combined desires are summarized in
+/// the ideology's data table.
 #[derive(Debug)]
 pub struct Ideology {
     /// The id of the Ideology.
@@ -39,6 +41,26 @@ pub struct Ideology {
     /// The Related Ideologies. Parents, children, and siblings.
     pub relations: Vec<usize>,
     // tags
+    /// The waves of the ideology, in the order in which they occured.
+    pub waves: Vec<IdeologyWave>,
+    /// The factions within the ideology.
+    pub factions: Vec<Faction>,
+    /// The wave which new converts join, the latest wave. None if the 
+    /// ideology has no waves.
+    pub current_wave: Option<usize>,
+    /// The summarized desires of an ideology, faction, and wave combo.
+    /// The indices of these are defined such that
+    /// faction_slot + (wave_slot * (len(factions) + 1)) = index of faction + wave.
+    /// 
+    /// Slot 0 is for no faction (or wave), slot i + 1 is for faction i.
+    pub data_table: Vec<IdeologySubentry>,
+}
+
+/// The summarized effects of an ideology, wave, and faction combination.
+#[derive(Debug, Clone)]
+pub struct IdeologySubentry {
+    /// The summarized desires for a member of this ideology+wave+faction.
+    pub desires: Vec<Desire>,
 }
 
 impl Ideology {
@@ -49,12 +71,138 @@ impl Ideology {
             if name.trim().is_empty() {
                 return Err("'name' cannot be empty or whitespace.".into());
             }
-            Ok(Self { id, name, variant_name,
+            let mut result = Self { id, name, variant_name,
                 birth_rate_modifier, mortality_rate_modifier, 
-                productivity_modifier, desires, relations } )
+                productivity_modifier, desires, relations,
+                waves: vec![], factions: vec![], current_wave: None,
+                data_table: vec![] };
+            result.update_data_table();
+            Ok(result)
             }
 
     pub fn get_name(&self) -> String {
         format!("{}({})", self.name, self.variant_name)
     }
+
+    /// # Add Wave
+    /// 
+    /// Adds a new wave to the ideology, which becomes the wave new converts
+    /// join, then updates the data table.
+    pub fn add_wave(&mut self, wave: IdeologyWave) {
+        self.waves.push(wave);
+        self.current_wave = Some(self.waves.len() - 1);
+        self.update_data_table();
+    }
+
+    /// # Add Faction
+    /// 
+    /// Adds a faction to the ideology, then updates the data table.
+    pub fn add_faction(&mut self, faction: Faction) {
+        self.factions.push(faction);
+        self.update_data_table();
+    }
+
+    /// Updates the data table in the ideology, consolidating the wave and 
+    /// faction desires into a singular lookup table.
+    pub fn update_data_table(&mut self) {
+        self.data_table.clear();
+        for wave_slot in 0..=self.waves.len() {
+            for faction_slot in 0..=self.factions.len() {
+                let mut entry = IdeologySubentry { desires: self.desires.clone() };
+                if wave_slot > 0 {
+                    let wave = &self.waves[wave_slot - 1];
+                    Desire::overlay_desires(&mut entry.desires, &wave.desires);
+                }
+                if faction_slot > 0 {
+                    let faction = &self.factions[faction_slot - 1];
+                    Desire::overlay_desires(&mut entry.desires, &faction.desires);
+                }
+                self.data_table.push(entry);
+            }
+        }
+    }
+
+    /// # Get Subentry
+    /// 
+    /// Gets the summarized data for the wave and faction given.
+    pub fn get_subentry(&self, wave: Option<usize>, 
+    faction: Option<usize>) -> Result<&IdeologySubentry, String> {
+        let wave_slot = wave.map_or(0, |x| x + 1);
+        let faction_slot = faction.map_or(0, |x| x + 1);
+        if wave_slot > self.waves.len() || faction_slot > self.factions.len() {
+            return Err("Invalid Wave or Faction Index given".into());
+        }
+        match self.data_table.get(faction_slot + (wave_slot * (self.factions.len() + 1))) {
+            Some(val) => Ok(val),
+            None => Err("Invalid Wave or Faction Index given".into())
+        }
+    }
+
+    /// # Convert Factions
+    /// 
+    /// How new converts divide between our factions, based on their weights.
+    /// If we have no factions, all join without one.
+    pub fn convert_factions(&self) -> Vec<(Option<usize>, f64)> {
+        let total: f64 = self.factions.iter().map(|x| x.weight).sum();
+        if self.factions.is_empty() || total <= 0.0 {
+            return vec![(None, 1.0)];
+        }
+        self.factions.iter().enumerate()
+            .map(|(idx, x)| (Some(idx), x.weight / total))
+            .collect()
+    }
+}
+
+/// A Wave is a version of an ideology. As the ideology is revised or 
+/// revived, new waves are added, and new converts join the latest one. 
+/// Those already converted stay in their wave.
+#[derive(Debug)]
+pub struct IdeologyWave {
+    /// The name of the wave.
+    pub name: String,
+    /// The desires the wave has different from the base ideology.
+    /// 
+    /// Desires here may have negative values, and if they do they cancel out 
+    /// the desire from the ideology. This subtraction cannot push the desire
+    /// below 0.
+    pub desires: Vec<Desire>,
+}
+
+impl IdeologyWave {
+    pub fn new(name: String, desires: Vec<Desire>) -> Result<Self, String> {
+        if name.trim().is_empty() {
+            return Err("'name' cannot be empty or whitespace.".into());
+        }
+        Ok(Self { name, desires })
+    }
+}
+
+/// A Faction is a group within an ideology which shares the ideology's core
+/// but differs in its details.
+#[derive(Debug)]
+pub struct Faction {
+    /// The name of the faction.
+    pub name: String,
+    /// The weight chance of a new convert joining this faction.
+    /// 
+    /// Chance = weight / sum(ideology factions)
+    pub weight: f64,
+    /// The desires the faction has different from the base ideology.
+    /// 
+    /// Desires here may have negative values, and if they do they cancel out 
+    /// the desire from the ideology. This subtraction cannot push the desire
+    /// below 0.
+    pub desires: Vec<Desire>,
+}
+
+impl Faction {
+    pub fn new(name: String, weight: f64, desires: Vec<Desire>) -> Result<Self, String> {
+        if weight < 0.0 {
+            return Err("'weight' must be 0 or greater.".into());
+        }
+        else if name.trim().is_empty() {
+            return Err("'name' cannot be empty or whitespace.".into());
+        }
+        Ok(Self { name, weight, desires })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+//! Ideologies spreading between pops.
+//!
+//! People talk to those around them. When they see those of another
+//! ideology in their market doing better than those of their own, some of
+//! them convert. The more common the other ideology, the more contact they
+//! have with it, and the faster it spreads.
+
+use std::collections::HashMap;
+
+use crate::{constants::{IDEOLOGY_SPREAD_RATE, MAX_IDEOLOGY_SPREAD_FRACTION},
+    objects::actor_objects::pop::Pop};
+
+/// # Ideology Appeal
+///
+/// How common an ideology is in a market, and how well off its followers
+/// there are, per person.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct IdeologyAppeal {
+    /// The share of the market's people who follow the ideology.
+    pub share: f64,
+    /// The market satisfaction of desires, in AMV, per follower.
+    pub satisfaction: f64,
+}
+
+impl IdeologyAppeal {
+    /// # Of Pops
+    ///
+    /// Gets the appeal of each ideology (including none) across all the 
+    /// pops given. A pop's satisfaction is shared evenly between its people.
+    pub fn of_pops<'a>(pops: impl Iterator<Item = &'a Pop>) -> HashMap<Option<usize>, Self> {
+        let mut people = 0;
+        let mut followers: HashMap<Option<usize>, (usize, f64)> = HashMap::new();
+        for pop in pops {
+            if pop.count() == 0 {
+                continue;
+            }
+            let per_person = pop.property.market_satisfaction / pop.count() as f64;
+            for row in pop.breakdown_table.table.iter() {
+                let entry = followers.entry(row.ideology).or_insert((0, 0.0));
+                entry.0 += row.count;
+                entry.1 += per_person * row.count as f64;
+            }
+            people += pop.count();
+        }
+        followers.into_iter()
+            .map(|(ideology, (count, satisfaction))| (ideology, IdeologyAppeal {
+                share: count as f64 / people as f64,
+                satisfaction: satisfaction / count as f64,
+            }))
+            .collect()
+    }
+
+    /// # Conversion Fraction
+    ///
+    /// How much of the followers with this appeal would convert to the 
+    /// target each day.
+    ///
+    /// If the target's followers are no better off, no one converts. 
+    /// Otherwise the fraction grows with the gap relative to what they have
+    /// now and how common the target is, up to MAX_IDEOLOGY_SPREAD_FRACTION.
+    pub fn conversion_fraction(&self, target: &IdeologyAppeal) -> f64 {
+        let gap = target.satisfaction - self.satisfaction;
+        if gap <= 0.0 {
+            return 0.0;
+        }
+        (IDEOLOGY_SPREAD_RATE * target.share * gap / (self.satisfaction.abs() + gap))
+            .min(MAX_IDEOLOGY_SPREAD_FRACTION)
+    }
+}
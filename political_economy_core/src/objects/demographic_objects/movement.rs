@@ -0,0 +1,135 @@
+//! Movements, political and social groups formed from shared discontent.
+//!
+//! When people sharing a culture or ideology are persistently worse off,
+//! they organize. The movement gathers support in every market where the
+//! group is unhappy, makes demands of the firms employing them and the 
+//! states ruling them, and escalates the longer it goes unanswered.
+
+use std::collections::HashMap;
+
+/// # Discontent
+///
+/// How many people of a culture and ideology in a market ended the day 
+/// worse off than they started it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discontent {
+    /// The culture of the group.
+    pub culture: Option<usize>,
+    /// The ideology of the group.
+    pub ideology: Option<usize>,
+    /// The market they are in.
+    pub market: usize,
+    /// How many of them are worse off.
+    pub unsatisfied: usize,
+    /// How many of them there are.
+    pub total: usize,
+    /// The firms which employ those who are worse off.
+    pub firms: Vec<usize>,
+    /// The states in the market.
+    pub states: Vec<usize>,
+}
+
+/// What a movement wants, and who from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MovementDemand {
+    /// Higher wages from the firm.
+    Wages { firm: usize },
+    /// Price controls or relief from the state.
+    Prices { state: usize },
+    /// Policy changes from the state, favoring the movement's ideology.
+    Policy { state: usize },
+}
+
+/// How far a movement has gone to get its demands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MovementEscalation {
+    /// Asking nicely.
+    Petition,
+    /// Public demonstrations.
+    Protest,
+    /// Refusing to work.
+    Strike,
+    /// Open revolt.
+    Revolt,
+}
+
+impl MovementEscalation {
+    /// The next step up, if there is one.
+    pub fn escalate(&self) -> Self {
+        match self {
+            MovementEscalation::Petition => MovementEscalation::Protest,
+            MovementEscalation::Protest => MovementEscalation::Strike,
+            MovementEscalation::Strike | 
+            MovementEscalation::Revolt => MovementEscalation::Revolt,
+        }
+    }
+
+    /// The next step down, None if the movement is at it's lowest and 
+    /// would dissolve.
+    pub fn deescalate(&self) -> Option<Self> {
+        match self {
+            MovementEscalation::Petition => None,
+            MovementEscalation::Protest => Some(MovementEscalation::Petition),
+            MovementEscalation::Strike => Some(MovementEscalation::Protest),
+            MovementEscalation::Revolt => Some(MovementEscalation::Strike),
+        }
+    }
+}
+
+/// A Movement is a political or social group which has formed from 
+/// discontent among people sharing a culture and ideology.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Movement {
+    /// The id of the movement.
+    pub id: usize,
+    /// The culture the movement was formed from.
+    pub culture: Option<usize>,
+    /// The ideology the movement was formed from.
+    pub ideology: Option<usize>,
+    /// The number of supporters in each market.
+    pub support: HashMap<usize, usize>,
+    /// What the movement wants, and who from.
+    pub demands: Vec<MovementDemand>,
+    /// How far the movement has gone to get its demands.
+    pub escalation: MovementEscalation,
+    /// How many days the movement has been at its current escalation.
+    pub days_at_escalation: u64,
+}
+
+impl Movement {
+    /// # Total Support
+    /// 
+    /// The number of supporters across all markets.
+    pub fn total_support(&self) -> usize {
+        self.support.values().sum()
+    }
+
+    /// # Gather Support
+    /// 
+    /// Updates support from the group's discontent, and rebuilds demands 
+    /// against the firms and states of the markets they're in. Demands 
+    /// where the group is no longer worse off are dropped, as they've been
+    /// answered.
+    pub fn gather_support(&mut self, reports: &[&Discontent]) {
+        self.support.clear();
+        self.demands.clear();
+        for report in reports.iter().filter(|x| x.unsatisfied > 0) {
+            *self.support.entry(report.market).or_insert(0) += report.unsatisfied;
+            let mut demands = vec![];
+            for firm in report.firms.iter() {
+                demands.push(MovementDemand::Wages { firm: *firm });
+            }
+            for state in report.states.iter() {
+                demands.push(MovementDemand::Prices { state: *state });
+                if self.ideology.is_some() {
+                    demands.push(MovementDemand::Policy { state: *state });
+                }
+            }
+            for demand in demands {
+                if !self.demands.contains(&demand) {
+                    self.demands.push(demand);
+                }
+            }
+        }
+    }
+}
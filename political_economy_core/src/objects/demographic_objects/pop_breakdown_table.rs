@@ -1,5 +1,7 @@
 use std::{collections::HashMap, ops::{AddAssign}};
 
+use crate::demographics::Demographics;
+
 /// Pop Breakdown Table
 /// 
 /// Stores the information and 'rows' of a population's data table. 
@@ -50,6 +52,50 @@ impl PopBreakdownTable {
         }
     }
 
+    /// # Split Off
+    /// 
+    /// Removes the fraction given of the table, rounded to the nearest 
+    /// person on the total, and returns them in a new table.
+    /// 
+    /// Each row gives up it's fraction rounded down, and those left over 
+    /// are taken from the rows with the largest remainders (earliest first), 
+    /// so small rows still give up their people.
+    pub fn split_off(&mut self, fraction: f64) -> PopBreakdownTable {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let target = (self.total as f64 * fraction).round() as usize;
+        let shares = self.table.iter()
+            .map(|row| row.count as f64 * fraction)
+            .collect::<Vec<f64>>();
+        let mut counts = shares.iter()
+            .map(|x| x.floor() as usize)
+            .collect::<Vec<usize>>();
+        let mut leftover = target.saturating_sub(counts.iter().sum());
+        let mut by_remainder = (0..shares.len()).collect::<Vec<usize>>();
+        by_remainder.sort_by(|a, b| (shares[*b] - shares[*b].floor())
+            .total_cmp(&(shares[*a] - shares[*a].floor())));
+        for idx in by_remainder {
+            if leftover == 0 {
+                break;
+            }
+            if counts[idx] < self.table[idx].count {
+                counts[idx] += 1;
+                leftover -= 1;
+            }
+        }
+        let mut result = PopBreakdownTable { table: vec![], total: 0 };
+        for (row, count) in self.table.iter().zip(counts) {
+            if count > 0 {
+                let mut taken = *row;
+                taken.count = count;
+                result.insert_pops(taken);
+            }
+        }
+        for row in result.table.iter() {
+            self.remove_pops(row);
+        }
+        result
+    }
+
     /// Gets the number of pops in each species.
     pub fn species_makeup(&self) -> HashMap<usize, usize> {
         let mut result = HashMap::new();
@@ -68,6 +114,27 @@ impl PopBreakdownTable {
         result
     }
 
+    /// Gets the number of pops in each class, keyed by culture and class.
+    pub fn culture_class_makeup(&self) -> HashMap<(Option<usize>, Option<usize>), usize> {
+        let mut result = HashMap::new();
+        for row in self.table.iter() {
+            result.entry((row.culture, row.culture_class)).or_insert(0)
+                .add_assign(row.count);
+        }
+        result
+    }
+
+    /// Gets the number of pops in each generation, keyed by culture and 
+    /// generation.
+    pub fn culture_generation_makeup(&self) -> HashMap<(Option<usize>, Option<usize>), usize> {
+        let mut result = HashMap::new();
+        for row in self.table.iter() {
+            result.entry((row.culture, row.culture_generation)).or_insert(0)
+                .add_assign(row.count);
+        }
+        result
+    }
+
     /// Gets the number of pops in each species.
     pub fn ideology_makeup(&self) -> HashMap<Option<usize>, usize> {
         let mut result = HashMap::new();
@@ -110,9 +177,16 @@ impl PopBreakdownTable {
     }
 
     /// Gets the average productivity of the pops here (weighted average between all options)
-    pub(crate) fn average_productivity(&self) -> f64 {
-        // TODO Upgrade this to actually take the breakdown of the pop into account
-        1.0
+    /// 
+    /// Takes species, cohort, and subtype into account.
+    pub fn average_productivity(&self, demos: &Demographics) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let total: f64 = self.table.iter()
+            .map(|row| demos.species_subentry(row).productivity * row.count as f64)
+            .sum();
+        total / self.total as f64
     }
 }
 
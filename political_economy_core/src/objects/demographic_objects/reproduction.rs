@@ -0,0 +1,110 @@
+//! Reproduction covers the rules for which members of a species are needed
+//! to produce children, and the eggs of those species which lay them.
+
+use std::collections::HashMap;
+
+use super::pop_breakdown_table::PBRow;
+
+/// # Reproductive Group
+///
+/// One grouping parsed from a SpeciesTag::ReproductiveGroup.
+///
+/// Each role is a set of sexes, any one of which can fill it. A child
+/// needs one member from every role, and the first role is the one which
+/// carries the child (or lays the egg).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReproductiveGroup {
+    /// The roles needed to produce a child, each listing the sexes which
+    /// can fill it.
+    pub roles: Vec<Vec<String>>,
+}
+
+impl ReproductiveGroup {
+    /// # Parse
+    ///
+    /// Parses the rule of a SpeciesTag::ReproductiveGroup into it's groups.
+    ///
+    /// Groupings are wrapped in (), and if there are none the whole rule
+    /// is a single group. Roles within a group are divided by /, and each
+    /// character in a role is a sex which can fill it.
+    ///
+    /// "(h)(f/lm)" is two groups, h alone, and f with either l or m.
+    pub fn parse(rule: &str) -> Result<Vec<ReproductiveGroup>, String> {
+        let rule = rule.trim();
+        if !rule.starts_with('(') {
+            return Ok(vec![ReproductiveGroup::parse_group(rule)?]);
+        }
+        let mut result = vec![];
+        let mut remainder = rule;
+        while !remainder.is_empty() {
+            let inner = remainder.strip_prefix('(')
+                .ok_or(format!("Expected '(' in reproductive group '{}'.", rule))?;
+            let end = inner.find(')')
+                .ok_or(format!("Unclosed '(' in reproductive group '{}'.", rule))?;
+            result.push(ReproductiveGroup::parse_group(&inner[..end])?);
+            remainder = inner[end + 1..].trim_start();
+        }
+        Ok(result)
+    }
+
+    /// Parses a single group, without parentheses.
+    fn parse_group(group: &str) -> Result<ReproductiveGroup, String> {
+        let mut roles = vec![];
+        for role in group.split('/') {
+            let role = role.trim();
+            if role.is_empty() || !role.chars().all(|x| x.is_alphanumeric()) {
+                return Err(format!("Invalid role '{}' in reproductive group '{}'.",
+                    role, group));
+            }
+            roles.push(role.chars().map(|x| x.to_string()).collect());
+        }
+        Ok(ReproductiveGroup { roles })
+    }
+
+    /// The sexes which carry the children of this group.
+    pub fn carriers(&self) -> &[String] {
+        &self.roles[0]
+    }
+
+    /// # Pairings
+    ///
+    /// How many complete sets of parents can be made from the members of
+    /// each sex given, limited by the role with the fewest members.
+    pub fn pairings(&self, sexes: &HashMap<String, usize>) -> usize {
+        self.roles.iter()
+            .map(|role| role.iter()
+                .map(|sex| sexes.get(sex).copied().unwrap_or(0))
+                .sum::<usize>())
+            .min().unwrap_or(0)
+    }
+}
+
+/// # Clutch
+///
+/// A batch of eggs laid by a pop. The eggs themselves are kept in the pop's
+/// property as the species' egg product, where they may be traded, eaten,
+/// or lost like anything else. When the clutch is ready only as many eggs
+/// as remain will hatch.
+#[derive(Debug, Clone, Copy)]
+pub struct Clutch {
+    /// Who will hatch from the eggs, including how many.
+    pub row: PBRow,
+    /// The egg product laid.
+    pub egg: usize,
+    /// How many days until the eggs hatch.
+    pub days_left: f64,
+}
+
+impl Clutch {
+    /// # Split Off
+    /// 
+    /// Splits the share given of the eggs off into a new clutch, rounded to
+    /// whole eggs, which hatches on the same day.
+    pub fn split_off(&mut self, share: f64) -> Clutch {
+        let mut result = *self;
+        result.row.count = ((self.row.count as f64 * share.clamp(0.0, 1.0))
+            .round() as usize).min(self.row.count);
+        self.row.count -= result.row.count;
+        result
+    }
+}
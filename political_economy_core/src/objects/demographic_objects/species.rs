@@ -1,14 +1,17 @@
 //! Species covers the biological nature of a pop.
 
+use std::collections::HashMap;
+
 use crate::objects::actor_objects::desire::Desire;
 
+use super::reproduction::ReproductiveGroup;
+
 /// A Species, the biological Reality of a Pop which they are built with.
 /// 
 /// Requires either long time frames or biotech to overwrite biology.
 /// 
-/// Will eventually contain data for Cohorts and Subtypes.
-/// 
-/// Some of it is written, but it has been commented out.
+/// Species are divided into Cohorts (life phases) and Subtypes (sexes or 
+/// castes). Their combined effects are summarized in the species' data table.
 #[derive(Debug)]
 pub struct Species {
     /// The Id of the species.
@@ -28,9 +31,9 @@ pub struct Species {
     /// Related variant species, used for organization.
     pub relations: Vec<usize>,
     /// The cohorts which make up this species, aranged by the order in which they occur.
-    //pub cohorts: Vec<Cohort>,
+    pub cohorts: Vec<Cohort>,
     /// The Subtypes which the species can have and be born into.
-    //pub subtypes: Vec<Subtype>,
+    pub subtypes: Vec<Subtype>,
     /// How productive the species is at a basic level. 
     /// 
     /// A value of 1 representing being able to do 1 human hour's worth of work per hour.
@@ -41,6 +44,9 @@ pub struct Species {
     /// contravening effects. This is per annum growth rate.
     /// 
     /// Cannot be negative, a value of 0 means no new pops are born naturally.
+    /// 
+    /// For species with a SpeciesTag::ReproductiveGroup, this is the rate for
+    /// carriers who have the partners they need.
     pub birth_rate: f64,
     /// The base mortality rate of the species, representing the base line chance of
     /// death for the species.
@@ -49,14 +55,16 @@ pub struct Species {
     pub mortality_rate: f64,
     // Preferences, Fill in later and Iron them out properly.
 
-    // The summarized effects of a species and cohort combo. 
-    // The indices of these are defined such that
-    // cohort_index + (subtype_index * len(cohorts)) = index of cohort + subtype.
-    // pub data_table: Vec<SpeciesSubentry>,
+    /// The summarized effects of a species, cohort, and subtype combo. 
+    /// The indices of these are defined such that
+    /// cohort_slot + (subtype_slot * (len(cohorts) + 1)) = index of cohort + subtype.
+    /// 
+    /// Slot 0 is for no cohort (or subtype), slot i + 1 is for cohort i.
+    pub data_table: Vec<SpeciesSubentry>,
 }
 
-/*
-#[derive(Debug)]
+/// The summarized effects of a species, cohort, and subtype combination.
+#[derive(Debug, Clone)]
 pub struct SpeciesSubentry {
     /// The summarized desires for a member of this species+cohort+subtype
     pub desires: Vec<Desire>,
@@ -70,8 +78,10 @@ pub struct SpeciesSubentry {
     pub skill_modifiers: HashMap<u64, f64>,
     /// The final skill group modifiers for a member of this species+cohort+subtype.
     pub skill_group_modifiers: HashMap<u64, f64>,
+    /// Whether a member of this species+cohort+subtype is a drone, and so
+    /// is directed by their owner rather than acting for themselves.
+    pub is_drone: bool,
 }
-*/
 
 impl Species {
     pub fn new(id: usize, name: String, 
@@ -91,46 +101,224 @@ impl Species {
             else if name.trim().is_empty() {
                 return Err("'name' cannot be empty or whitespace.".into());
             }
+            for tag in tags.iter() {
+                if let SpeciesTag::ReproductiveGroup(rule) = tag {
+                    ReproductiveGroup::parse(rule)?;
+                }
+            }
 
-            Ok(Self { 
+            let mut result = Self { 
                 id, name, variant_name, desires, 
                 tags, relations, base_productivity, 
-                birth_rate, mortality_rate } )
+                birth_rate, mortality_rate,
+                cohorts: vec![], subtypes: vec![], data_table: vec![] };
+            result.update_data_table();
+            Ok(result)
             }
 
     pub fn get_name(&self) -> String {
         format!("{}({})", self.name, self.variant_name)
     }
 
-    /*
+    /// # Add Cohort
+    /// 
+    /// Adds a cohort after those already in the species, then updates the
+    /// data table.
+    pub fn add_cohort(&mut self, cohort: Cohort) {
+        self.cohorts.push(cohort);
+        self.update_data_table();
+    }
+
+    /// # Add Subtype
+    /// 
+    /// Adds a subtype to the species, then updates the data table.
+    pub fn add_subtype(&mut self, subtype: Subtype) {
+        self.subtypes.push(subtype);
+        self.update_data_table();
+    }
+
     /// Updates the data table in the species, consolidating the cohort and subtype effects
     /// into a singular lookup table.
     pub fn update_data_table(&mut self) {
         // clear out the old data for simplicity
         self.data_table.clear();
-        // iterate over both and summarize their effects.
-        for cohort in self.cohorts.iter() {
-            for subtype in self.subtypes.iter() {
+        // iterate over both and summarize their effects, slot 0 being none.
+        for subtype_slot in 0..=self.subtypes.len() {
+            for cohort_slot in 0..=self.cohorts.len() {
                 let mut entry = SpeciesSubentry{ 
-                    desires: vec![], 
+                    desires: self.desires.clone(), 
                     productivity: self.base_productivity, 
                     birth_rate: self.birth_rate, 
                     mortality_rate: self.mortality_rate, 
                     skill_modifiers: HashMap::new(), 
-                    skill_group_modifiers: HashMap::new()
+                    skill_group_modifiers: HashMap::new(),
+                    is_drone: false,
                 };
+                for tag in self.tags.iter() {
+                    match tag {
+                        SpeciesTag::SkillModifier { skill, modifier } =>
+                            { entry.skill_modifiers.insert(*skill, *modifier); },
+                        SpeciesTag::SkillGroupModifier { skill_group, modifier } =>
+                            { entry.skill_group_modifiers.insert(*skill_group, *modifier); },
+                        SpeciesTag::Drone => entry.is_drone = true,
+                        _ => (),
+                    }
+                }
+                if cohort_slot > 0 {
+                    let cohort = &self.cohorts[cohort_slot - 1];
+                    entry.productivity *= cohort.productivity_modifier;
+                    entry.birth_rate += cohort.birth_rate;
+                    entry.mortality_rate += cohort.mortality_rate;
+                    Desire::overlay_desires(&mut entry.desires, &cohort.desires);
+                    for tag in cohort.tags.iter() {
+                        match tag {
+                            CohortTag::SkillModifier { skill, modifier } =>
+                                *entry.skill_modifiers.entry(*skill).or_insert(1.0) *= modifier,
+                            CohortTag::SkillGroupModifier { skill_group, modifier } =>
+                                *entry.skill_group_modifiers.entry(*skill_group).or_insert(1.0) *= modifier,
+                            CohortTag::Drone => entry.is_drone = true,
+                            _ => (),
+                        }
+                    }
+                }
+                if subtype_slot > 0 {
+                    let subtype = &self.subtypes[subtype_slot - 1];
+                    entry.productivity *= subtype.productivity_modifier;
+                    entry.birth_rate += subtype.birth_rate;
+                    entry.mortality_rate += subtype.mortality_rate;
+                    Desire::overlay_desires(&mut entry.desires, &subtype.desires);
+                    for tag in subtype.subtype_tags.iter() {
+                        match tag {
+                            SubtypeTag::SkillModifier { skill, modifier } =>
+                                *entry.skill_modifiers.entry(*skill).or_insert(1.0) *= modifier,
+                            SubtypeTag::SkillGroupModifier { skill_group, modifier } =>
+                                *entry.skill_group_modifiers.entry(*skill_group).or_insert(1.0) *= modifier,
+                            SubtypeTag::Drone => entry.is_drone = true,
+                            _ => (),
+                        }
+                    }
+                }
+                entry.birth_rate = entry.birth_rate.max(0.0);
+                entry.mortality_rate = entry.mortality_rate.max(0.0);
+                self.data_table.push(entry);
             }
         }
     }
 
-    pub fn get_subentry(&self, cohort_idx: usize, subtype_idx: usize) -> Result<&SpeciesSubentry, String> {
-        match self.data_table.get(cohort_idx + (subtype_idx * self.cohorts.len())) {
+    /// # Get Subentry
+    /// 
+    /// Gets the summarized data for the cohort and subtype given.
+    pub fn get_subentry(&self, cohort: Option<usize>, 
+    subtype: Option<usize>) -> Result<&SpeciesSubentry, String> {
+        let cohort_slot = cohort.map_or(0, |x| x + 1);
+        let subtype_slot = subtype.map_or(0, |x| x + 1);
+        if cohort_slot > self.cohorts.len() || subtype_slot > self.subtypes.len() {
+            return Err("Invalid Cohort or Subtype Index given".into());
+        }
+        match self.data_table.get(cohort_slot + (subtype_slot * (self.cohorts.len() + 1))) {
             Some(val) => Ok(val),
-            None => Err("Invalid Cohort or Species Index given".into())
+            None => Err("Invalid Cohort or Subtype Index given".into())
+        }
+    }
+
+    /// # Reproductive Groups
+    /// 
+    /// The groups parsed from our SpeciesTag::ReproductiveGroup. If we have
+    /// none, we reproduce asexually.
+    pub fn reproductive_groups(&self) -> Vec<ReproductiveGroup> {
+        self.tags.iter()
+            .filter_map(|tag| match tag {
+                SpeciesTag::ReproductiveGroup(rule) => Some(rule),
+                _ => None,
+            })
+            .flat_map(|rule| ReproductiveGroup::parse(rule)
+                .expect("Invalid Reproductive Group!"))
+            .collect()
+    }
+
+    /// The sex of the subtype given, if it has one.
+    pub fn subtype_sex(&self, subtype: Option<usize>) -> Option<&str> {
+        subtype.and_then(|idx| self.subtypes.get(idx))
+            .and_then(|subtype| subtype.subtype_tags.iter()
+                .find_map(|tag| match tag {
+                    SubtypeTag::Sex(sex) => Some(sex.as_str()),
+                    _ => None,
+                }))
+    }
+
+    /// # Breeding Shares
+    /// 
+    /// Given how many fertile members of each subtype there are, finds
+    /// what share of each subtype is able to carry children.
+    /// 
+    /// Carriers are limited by the pairings their group can make, so if
+    /// there are 10 f and 5 m in a "f/m" species, half of the f can have
+    /// children. Sexes which don't carry in any group, and subtypes without
+    /// a sex, have a share of 0. A sex which carries in multiple groups
+    /// uses the first.
+    /// 
+    /// Returns None if we reproduce asexually, meaning everyone can have 
+    /// children.
+    pub fn breeding_shares(&self, fertile: &HashMap<Option<usize>, usize>) 
+    -> Option<HashMap<Option<usize>, f64>> {
+        let groups = self.reproductive_groups();
+        if groups.is_empty() {
+            return None;
+        }
+        let mut sexes: HashMap<String, usize> = HashMap::new();
+        for (&subtype, &count) in fertile.iter() {
+            if let Some(sex) = self.subtype_sex(subtype) {
+                *sexes.entry(sex.to_string()).or_insert(0) += count;
+            }
+        }
+        let mut carrier_shares: HashMap<&str, f64> = HashMap::new();
+        for group in groups.iter() {
+            let carriers: usize = group.carriers().iter()
+                .map(|x| sexes.get(x).copied().unwrap_or(0)).sum();
+            if carriers == 0 {
+                continue;
+            }
+            let share = group.pairings(&sexes) as f64 / carriers as f64;
+            for sex in group.carriers().iter() {
+                carrier_shares.entry(sex.as_str()).or_insert(share);
+            }
         }
-    } */
+        Some(fertile.keys()
+            .map(|&subtype| (subtype, self.subtype_sex(subtype)
+                .and_then(|sex| carrier_shares.get(sex))
+                .copied().unwrap_or(0.0)))
+            .collect())
+    }
+
+    /// The egg product we lay and how many days it takes to hatch, if we
+    /// are Oviparous.
+    pub fn egg(&self) -> Option<(usize, u64)> {
+        self.tags.iter().find_map(|tag| match tag {
+            SpeciesTag::Oviparous { egg, incubation } => Some((*egg as usize, *incubation)),
+            _ => None,
+        })
+    }
+
+    /// The cohort newborns start in, if the species has cohorts.
+    pub fn birth_cohort(&self) -> Option<usize> {
+        if self.cohorts.is_empty() { None } else { Some(0) }
+    }
+
+    /// # Birth Subtypes
+    /// 
+    /// How newborns divide between our subtypes, based on their weights.
+    /// If we have no subtypes, all are born without one.
+    pub fn birth_subtypes(&self) -> Vec<(Option<usize>, f64)> {
+        let total: f64 = self.subtypes.iter().map(|x| x.weight).sum();
+        if self.subtypes.is_empty() || total <= 0.0 {
+            return vec![(None, 1.0)];
+        }
+        self.subtypes.iter().enumerate()
+            .map(|(idx, x)| (Some(idx), x.weight / total))
+            .collect()
+    }
 }
-/*
+
 /// Cohort is a subdivision of a Species. Pops are born into the first cohort listed
 /// in the Species, and as time passes they eventually move upwards into different
 /// cohorts. The last cohort is the one which continues and it's average span is the
@@ -167,6 +355,21 @@ pub struct Cohort {
 }
 
 impl Cohort {
+    pub fn new(name: String, span: u64, birth_rate: f64, 
+        mortality_rate: f64, productivity_modifier: f64, 
+        tags: Vec<CohortTag>, desires: Vec<Desire>) -> Result<Self, String> {
+            if span == 0 {
+                return Err("'span' must be greater than 0.".into());
+            }
+            else if productivity_modifier < 0.0 {
+                return Err("'productivity_modifier' must be 0 or greater.".into());
+            }
+            else if name.trim().is_empty() {
+                return Err("'name' cannot be empty or whitespace.".into());
+            }
+            Ok(Self { name, span, birth_rate, mortality_rate, 
+                productivity_modifier, tags, desires })
+        }
 }
 
 /// Subtype is a subgroup of the species which divides based on innate phenotypes.
@@ -198,7 +401,22 @@ pub struct Subtype {
 }
 
 impl Subtype {
-} */
+    pub fn new(name: String, weight: f64, birth_rate: f64, 
+        mortality_rate: f64, productivity_modifier: f64, 
+        subtype_tags: Vec<SubtypeTag>, desires: Vec<Desire>) -> Result<Self, String> {
+            if weight < 0.0 {
+                return Err("'weight' must be 0 or greater.".into());
+            }
+            else if productivity_modifier < 0.0 {
+                return Err("'productivity_modifier' must be 0 or greater.".into());
+            }
+            else if name.trim().is_empty() {
+                return Err("'name' cannot be empty or whitespace.".into());
+            }
+            Ok(Self { name, weight, birth_rate, mortality_rate, 
+                productivity_modifier, subtype_tags, desires })
+        }
+}
 
 /// Tags which are available to a species and modify how they work in the system.
 #[derive(Debug)]
@@ -216,20 +434,25 @@ pub enum SpeciesTag {
     /// # Examples
     /// 
     /// "x", asexual reproduction. Not necissary if alone, but still valid.
-    /// "f/m", standard Female-male pairing. (only valid option currently)
+    /// "f/m", standard Female-male pairing.
     /// "f/lm", three sexes, f is always needed, l or m are needed to complete the pairing.
     /// "(h)(f/m)", three sexes, one which reproduces hermaphroditically or asexually, the other two more standard.
     /// "(x/q)(a/t)" 4 sexes, x and a are the 'females' and need q and t to reproduce respectively.
     /// "(a/b/c/d)" 4 sexes, a carries the egg, but all of them are needed to produce children.
+    /// 
+    /// Each character is matched to a subtype's SubtypeTag::Sex. Only the carriers 
+    /// have children, limited by how many complete groupings can be made.
     ReproductiveGroup(String),
     /// The species are egg laying, producing an egg instead of live children.
     /// 
-    /// The value contained represents the egg they produce.
-    /// 
-    /// This system in non-functional.
-    Oviparous(u64),
+    /// egg is the product laid into the pop's property, and incubation is how
+    /// many days it takes to hatch. Eggs which are gone by then don't hatch.
+    Oviparous{egg: u64, incubation: u64},
     /// The species is a mindless drone, effectively incapable of meaningful
     /// individual action.
+    /// 
+    /// Drone pops don't shop or sell for themselves. They work as their
+    /// owning firm directs and ask it to provide for their desires.
     Drone,
     /// This species or subgroup has a specific civilization which is biologically
     /// ingrained into them.
@@ -259,7 +482,7 @@ pub enum SpeciesTag {
     /// If above 1 they are particularly gifted in that skill and pick it up faster.
     SkillGroupModifier{skill_group: u64, modifier: f64}
 }
-/*
+
 /// Tags which are available to a Species' Cohort and modify how they work in the system.
 #[derive(Debug)]
 pub enum CohortTag {
@@ -334,4 +557,3 @@ pub enum SubtypeTag {
     /// If above 1 they are particularly gifted in that skill and pick it up faster.
     SkillGroupModifier{skill_group: u64, modifier: f64}
 }
-*/
\ No newline at end of file
@@ -1,2 +1,6 @@
+pub mod labor_market;
 pub mod market;
+pub mod migration;
+pub mod order_book;
+pub mod shipment;
 pub mod territory;
\ No newline at end of file
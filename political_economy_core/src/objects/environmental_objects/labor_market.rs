@@ -0,0 +1,73 @@
+//! The labor market, where pops look for work and firms hire and fire.
+//!
+//! At the end of the day, firms which aren't losing money post their open
+//! jobs, and those which are lay off part of their workers. Pops which are
+//! unemployed, or unhappy with their work, look over the openings in their
+//! market and take the best paying one which beats what they make now.
+//! Unemployed pops take the job whole, while only part of an employed pop
+//! leaves, splitting off into a new pop for the new job.
+
+use crate::objects::actor_objects::firm_job::{FirmJob, WageType};
+
+/// # Job Opening
+///
+/// An open job posted by a firm, which a pop may take.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobOpening {
+    /// The firm offering the job.
+    pub firm: usize,
+    /// Where the job is in the firm's jobs.
+    pub index: usize,
+    /// The job (id) being offered.
+    pub job: usize,
+    /// How the job pays.
+    pub wage_type: WageType,
+    /// The wage offered per worker, in AMV.
+    pub wage: f64,
+}
+
+impl JobOpening {
+    /// # Of Jobs
+    ///
+    /// The openings among the jobs of the firm given, those without a pop
+    /// working them. Sorted from best to worst paying.
+    pub fn of_jobs(firm: usize, jobs: &[FirmJob]) -> Vec<JobOpening> {
+        let mut result: Vec<JobOpening> = jobs.iter()
+            .enumerate()
+            .filter(|(_, job)| job.is_open())
+            .map(|(index, job)| JobOpening { firm, index, job: job.job,
+                wage_type: job.wage_type, wage: job.wage })
+            .collect();
+        result.sort_by(|a, b| b.wage.total_cmp(&a.wage));
+        result
+    }
+}
+
+/// Whether people were hired into or fired from a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaborChange {
+    /// The workers took an opening.
+    Hired,
+    /// The workers were laid off.
+    Fired,
+}
+
+/// # Labor Record
+///
+/// A log entry for people who were hired or fired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaborRecord {
+    /// The pop the workers came from.
+    pub pop: usize,
+    /// The pop the workers are now in. The same as pop if the whole pop
+    /// changed.
+    pub workers: usize,
+    /// The firm they were hired into or fired from.
+    pub firm: usize,
+    /// The job they were hired into or fired from.
+    pub job: usize,
+    /// How many people changed.
+    pub count: usize,
+    /// Whether they were hired or fired.
+    pub change: LaborChange,
+}
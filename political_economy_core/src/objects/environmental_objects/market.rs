@@ -1,11 +1,12 @@
-use core::panic;
-use std::collections::{HashMap, HashSet};
+use std::{collections::{HashMap, HashSet}, future::Future, pin::Pin, sync::Arc, 
+    task::{Context, Poll, Wake, Waker}};
 use barrage::{Sender, Receiver};
 use crossbeam::thread;
 use itertools::Itertools;
 use rand::{Rng, thread_rng};
 
-use crate::{constants::{SALABILITY_THRESHOLD, STD_PRICE_CHANGE}, data_manager::DataManager, demographics::Demographics, objects::{actor_objects::{actor::Actor, actor_message::{ActorInfo, ActorMessage, OfferResult, WantSource}, firm::Firm, institution::Institution, pop::Pop, seller::Seller, state::State}, data_objects::item::Item}};
+use crate::{constants::{SALABILITY_THRESHOLD, STD_PRICE_CHANGE, TRAVEL_DISTANCE_PER_DAY, TRANSPORT_BULK_COST, TRANSPORT_MASS_COST}, data_manager::DataManager, demographics::Demographics, objects::{actor_objects::{actor::Actor, actor_message::{ActorInfo, ActorMessage, AuctionKind, FirmHierarchyAction, OfferResult, WantSource}, firm::Firm, institution::Institution, pop::Pop, seller::Seller, state::State}, data_objects::{item::Item, product::Product}}};
+use super::{order_book::{OrderBook, Trade}, shipment::Shipment};
 use crate::constants;
 
 /// # The Market
@@ -68,6 +69,8 @@ pub struct Market {
     /// The Markets which are immediately adjacent to this market,
     /// This contains the ID and the connection type/data between them.
     pub neighbors: HashMap<usize, MarketConnection>,
+    /// Shipments sent from this market which have yet to arrive.
+    pub in_transit: Vec<Shipment>,
     /// The open resources of the market, these are the trash items
     /// which are available for anyone to pick up, and includes surface
     /// resources from the environment.
@@ -113,22 +116,80 @@ pub struct Market {
     /// The info of the market from yesterday, stored for general
     /// information.
     pub previous_day: MarketHistory,
+    /// How buyers and sellers are matched in this market.
+    pub exchange_mode: ExchangeMode,
 
     /// Stores products offered for sale, and a list of weighted actors to 
     /// help with selection. Values are the total weight available, followed by
     /// the list of available sellers.
     seller_weights: HashMap<usize, (f64, Vec<WeightedActor>)>,
-    /// All pops in the system, weighted by their current wealth.
+    /// All non-drone pops in the system, weighted by their current wealth.
     /// 
     /// TODO May be updated to a rolling average instead of a daily, perfectly
     /// accurate, measure.
     pop_wealth_weight: Vec<WeightedActor>,
+    /// How much of each product each actor has tried to buy today. Used
+    /// alongside wealth when finding buyers for salesmen.
+    buyer_demand: HashMap<(usize, ActorInfo), f64>,
     /// Ongoing record of deals, used to keep track more easily and allows us to update
     /// market data more easily. Why send the same messages twice afterall?.
-    ongoing_deals: Vec<DealRecord>
+    ongoing_deals: Vec<DealRecord>,
+    /// The asks and bids of the day, used when exchange_mode is 
+    /// ExchangeMode::OrderBook.
+    order_book: OrderBook,
+    /// The auctions and reverse auctions which are currently taking bids.
+    ongoing_auctions: Vec<AuctionRecord>,
+    /// The id to give the next auction opened.
+    next_auction_id: usize,
 }
 
 impl Market {
+    /// # New Market
+    /// 
+    /// Creates a new, empty market, without any actors, neighbors, goods,
+    /// or history.
+    pub fn new(id: usize, name: String, exchange_mode: ExchangeMode) -> Self {
+        Self {
+            id,
+            name,
+            firms: vec![],
+            pops: vec![],
+            institutions: vec![],
+            states: vec![],
+            territories: vec![],
+            neighbors: HashMap::new(),
+            in_transit: vec![],
+            resources: HashMap::new(),
+            prices: HashMap::new(),
+            products_for_sale: HashMap::new(),
+            product_demanded: HashMap::new(),
+            product_sold: HashMap::new(),
+            product_output: HashMap::new(),
+            product_exchanged_total: HashMap::new(),
+            salability: HashMap::new(),
+            want_prices: HashMap::new(),
+            want_requests: HashMap::new(),
+            want_sources: HashMap::new(),
+            state_currencies: vec![],
+            previous_day: MarketHistory { product_info: HashMap::new(),
+                class_info: HashMap::new(),
+                want_info: HashMap::new(),
+                sale_priority: vec![],
+                currencies: vec![],
+                exchange_mode,
+                neighbors: HashMap::new(),
+            },
+            exchange_mode,
+            seller_weights: HashMap::new(),
+            pop_wealth_weight: vec![],
+            buyer_demand: HashMap::new(),
+            ongoing_deals: vec![],
+            order_book: OrderBook::new(),
+            ongoing_auctions: vec![],
+            next_auction_id: 0,
+        }
+    }
+
     /// Runs the market day for this market. This manages the various actors in the market
     pub fn run_market_day(&mut self, 
         sender: Sender<MarketMessage>,
@@ -144,6 +205,11 @@ impl Market {
         let firm_count = firms.len();
         let institution_count = institutions.len();
         let state_count = states.len();
+        // note which firms may ship goods to other markets.
+        let traders: HashSet<usize> = firms.iter()
+            .filter(|x| x.firm_kind.trades_between_markets())
+            .map(|x| x.id)
+            .collect();
         // get our thread scope for our children.
         thread::scope(|scope| {
             // make our history immutable so we can hand it out elsewhere.
@@ -167,9 +233,12 @@ impl Market {
             }
             for pop in pops.iter_mut() {
                 // add pop to popWealthWeight for later possible use
+                // drones don't buy for themselves, so salesmen skip them.
                 let history = shared_history.clone();
-                self.pop_wealth_weight.push(WeightedActor { actor: pop.actor_info(), 
-                    weight: pop.total_wealth(&history) });
+                if !pop.is_drone(demos) {
+                    self.pop_wealth_weight.push(WeightedActor { actor: pop.actor_info(), 
+                        weight: pop.total_wealth(&history) });
+                }
                 let mut pop_sender = lcl_sender.clone();
                 let mut pop_recv = lcl_receiver.clone();
                 threads.push(scope.spawn(move |_| {
@@ -200,16 +269,24 @@ impl Market {
             // once we spin up all actors, send them the OK message.
             lcl_sender.send(ActorMessage::StartDay).expect("Somehow Closed. Panic!");
 
-            // Enter holding pattern while the children do their work
+            // Enter holding pattern while the children do their work, 
+            // staying in it until every actor of every kind has finished.
             let mut completed_firms = HashSet::new();
             let mut completed_pops = HashSet::new();
             let mut completed_insts = HashSet::new();
             let mut completed_states = HashSet::new();
-            while completed_firms.len() >= firm_count && 
-                completed_pops.len() >= pop_count && 
-                completed_insts.len() >= institution_count &&
-                completed_states.len() >= state_count {
-                let msg = lcl_receiver.recv().expect("Unexpected Disconnect!");
+            while completed_firms.len() < firm_count || 
+                completed_pops.len() < pop_count || 
+                completed_insts.len() < institution_count ||
+                completed_states.len() < state_count {
+                // sleep until either other markets or our actors have something for us.
+                let msg = match recv_either(reciever, &lcl_receiver) {
+                    Incoming::Routed(routed) => {
+                        self.process_market_message(routed, &sender, &lcl_sender);
+                        continue;
+                    },
+                    Incoming::Local(msg) => msg,
+                };
                 match msg {
                     ActorMessage::Finished { sender } => { // actor is done, mark them.
                         match sender {
@@ -221,7 +298,123 @@ impl Market {
                     },
 
                     ActorMessage::SellOrder { sender, product, 
-                    quantity, amv } => self.add_seller_weight(&sender, product,quantity, amv),
+                    quantity, amv } => {
+                        self.add_seller_weight(&sender, product,quantity, amv);
+                        if let ExchangeMode::OrderBook { currency } = self.exchange_mode {
+                            // post the ask in currency units, it may fill resting bids.
+                            let price = amv / self.currency_price(currency);
+                            let trades = self.order_book
+                                .submit_ask(sender, product, quantity, price);
+                            self.settle_trades(&trades, currency, &lcl_sender);
+                        }
+                    },
+                    ActorMessage::BidOrder { sender, product, quantity, 
+                    price } => {
+                        *self.product_demanded.entry(product).or_insert(0.0) += quantity;
+                        *self.buyer_demand.entry((product, sender)).or_insert(0.0) += quantity;
+                        let mut filled = 0.0;
+                        if let ExchangeMode::OrderBook { currency } = self.exchange_mode {
+                            let trades = self.order_book
+                                .submit_bid(sender, product, quantity, price);
+                            // buyers don't wait on resting bids, cancel the remainder.
+                            self.order_book.cancel_bid(sender, product);
+                            filled = trades.iter().map(|x| x.quantity).sum();
+                            self.settle_trades(&trades, currency, &lcl_sender);
+                        }
+                        lcl_sender.send(ActorMessage::BidClosed { buyer: sender, 
+                            product, filled }).expect("Send Error!");
+                    },
+
+                    ActorMessage::OpenAuction { organizer, kind, product, 
+                    quantity, currency, reserve } => {
+                        // record the auction, then let everyone know it's open.
+                        let auction = self.next_auction_id;
+                        self.next_auction_id += 1;
+                        match kind {
+                            AuctionKind::Auction => 
+                                *self.products_for_sale.entry(product).or_insert(0.0) += quantity,
+                            AuctionKind::ReverseAuction => 
+                                *self.product_demanded.entry(product).or_insert(0.0) += quantity,
+                        }
+                        self.ongoing_auctions.push(AuctionRecord::new(auction, 
+                            organizer, kind, product, quantity, currency, reserve));
+                        lcl_sender.send(ActorMessage::AuctionOpened { auction, 
+                            organizer, kind, product, quantity, currency, reserve })
+                            .expect("Send Error!");
+                    },
+                    ActorMessage::AuctionBid { auction, bidder, price } => {
+                        // bids which arrive after closing are ignored.
+                        if let Some(record) = self.ongoing_auctions.iter_mut()
+                        .find(|x| x.id == auction) {
+                            record.add_bid(bidder, price);
+                        }
+                    },
+                    ActorMessage::CloseAuction { auction, organizer } => {
+                        let idx = match self.ongoing_auctions.iter()
+                        .position(|x| x.id == auction && x.organizer == organizer) {
+                            Some(idx) => idx,
+                            None => {
+                                // already closed or not theirs, let them know.
+                                lcl_sender.send(ActorMessage::AuctionNotFound { 
+                                    auction, organizer }).expect("Send Error!");
+                                continue;
+                            }
+                        };
+                        let record = self.ongoing_auctions.remove(idx);
+                        let winner = record.winning_bid();
+                        let price = winner.map(|x| x.1).unwrap_or(0.0);
+                        if winner.is_some() {
+                            self.record_currency_exchange(record.product, 
+                                record.quantity, record.currency, price);
+                        }
+                        lcl_sender.send(ActorMessage::AuctionClosed { auction, 
+                            organizer, winner: winner.map(|x| x.0), 
+                            kind: record.kind, product: record.product, 
+                            quantity: record.quantity, currency: record.currency, 
+                            price }).expect("Send Error!");
+                    },
+
+                    ActorMessage::ShipGoods { sender, destination, product, 
+                    quantity } => {
+                        let result = self.ship_goods(data, &traders, sender, 
+                            destination, product, quantity);
+                        lcl_sender.send(result).expect("Send Error!");
+                    },
+
+                    ActorMessage::QueryPrice { sender: asker, market, product } => {
+                        if market == self.id { // it's us, answer directly.
+                            lcl_sender.send(ActorMessage::PriceInfo { reciever: asker, 
+                                market, product, price: self.prices.get(&product).copied() })
+                                .expect("Send Error!");
+                        } else { // send it along to the other market.
+                            sender.send(MarketMessage { sender: self.id, reciever: market,
+                                message: MarketMessageEnum::PriceQuery { asker, product } })
+                                .expect("Send Error!");
+                        }
+                    },
+                    ActorMessage::RequestTrade { sender: requester, market, product, 
+                    quantity } => {
+                        if market == self.id {
+                            lcl_sender.send(ActorMessage::TradeInfo { reciever: requester, 
+                                market, product, available: self.available_for_trade(product, quantity),
+                                price: self.prices.get(&product).copied() })
+                                .expect("Send Error!");
+                        } else {
+                            sender.send(MarketMessage { sender: self.id, reciever: market,
+                                message: MarketMessageEnum::TradeRequest { requester, 
+                                    product, quantity } })
+                                .expect("Send Error!");
+                        }
+                    },
+                    ActorMessage::FirmToFirm { sender: from, reciever: to, action } => {
+                        // if they aren't here, pass it up to be routed to their market.
+                        if !self.firms.contains(&to) {
+                            sender.send(MarketMessage { sender: self.id, reciever: 0, 
+                                message: MarketMessageEnum::FirmMessage { sender: from, 
+                                    reciever: to, action } })
+                                .expect("Send Error!");
+                        }
+                    },
 
                     ActorMessage::DumpProduct { sender: _, product, amount } => {
                         // product dumped into the environment
@@ -238,6 +431,20 @@ impl Market {
                         let result = self.find_want_seller(want, sender);
                         lcl_sender.send(result).expect("Send Error!");
                     }
+                    ActorMessage::FindBuyer { product, sender } => {
+                        // seller is salesmanning, send them to a buyer.
+                        let result = self.find_buyer(product, sender);
+                        lcl_sender.send(result).expect("Send Error!");
+                    },
+                    ActorMessage::FoundBuyer { seller, buyer, product } => {
+                        // A buyer was previously found, the deal runs as normal from here.
+                        self.ongoing_deals.push(DealRecord::new(
+                            vec![seller, buyer], 
+                            product, 0.0, 
+                            0.0, 
+                            HashMap::new(), 
+                            OfferResult::Incomplete));
+                    },
                     // Product not found, no reaction needed to that message.
                     ActorMessage::FoundProduct { seller, buyer, 
                         product } => {
@@ -276,6 +483,7 @@ impl Market {
                         deal.request_quantity = quantity;
                         deal.current_result = price_opinion;
                         *self.product_demanded.entry(product).or_insert(0.0) += quantity;
+                        *self.buyer_demand.entry((product, buyer)).or_insert(0.0) += quantity;
                     },
                     ActorMessage::BuyOfferFollowup { buyer, seller, product, 
                     offer_product, offer_quantity, followup: _  } => {
@@ -346,6 +554,10 @@ impl Market {
                 }
             }
 
+            // everyone is done, let them close out for the day.
+            lcl_sender.send(ActorMessage::AllFinished).expect("Send Error!");
+            // we no longer read what our actors send, so don't hold them up.
+            drop(lcl_receiver);
             // after all actors message done, send our message done up and wait
             sender.send(MarketMessage { sender: self.id, reciever: 0,
                  message: MarketMessageEnum::CloseMarket}).expect("Closed, Big Problem.");
@@ -353,16 +565,17 @@ impl Market {
                 // with the close sent, read messages and wait for the all clear. 
                 // respond to any messages directed to us.
                 let result = reciever.recv().expect("Unexpected Close.");
-                match result.message {
-                    MarketMessageEnum::ConfirmClose => {
-                        break;
-                    },
-                    _ => panic!("Message other than confirm close found after end of day.")// do nothing on everything else.
+                if self.process_market_message(result, &sender, &lcl_sender) {
+                    break;
                 }
             }
             // if we got here, then we're done. Do any clean and info 
             // clean out sellers
             self.seller_weights.clear();
+            self.order_book.clear();
+            self.ongoing_auctions.clear();
+            self.pop_wealth_weight.clear();
+            self.buyer_demand.clear();
             // product info will be needed for later use, so don't clear out just yet.
             // consolidation outside of this thread scope so we can edit stuff.
         }).unwrap();
@@ -383,6 +596,139 @@ impl Market {
         *self.products_for_sale.entry(product).or_default() += quantity;
     }
 
+    /// # Process Market Message
+    /// 
+    /// Handles a message routed to us from another market. Queries are
+    /// answered from our own records, responses and firm messages are passed
+    /// on to our actors.
+    /// 
+    /// Messages for other markets are ignored.
+    /// 
+    /// Returns true if the message was our ConfirmClose.
+    fn process_market_message(&mut self, message: MarketMessage, 
+    sender: &Sender<MarketMessage>, lcl_sender: &Sender<ActorMessage>) -> bool {
+        if message.reciever != self.id {
+            return false;
+        }
+        match message.message {
+            MarketMessageEnum::ConfirmClose => return true,
+            MarketMessageEnum::PriceQuery { asker, product } => {
+                sender.send(MarketMessage { sender: self.id, reciever: message.sender,
+                    message: MarketMessageEnum::PriceResponse { asker, product, 
+                        price: self.prices.get(&product).copied() } })
+                    .expect("Send Error!");
+            },
+            MarketMessageEnum::PriceResponse { asker, product, price } => {
+                lcl_sender.send(ActorMessage::PriceInfo { reciever: asker, 
+                    market: message.sender, product, price })
+                    .expect("Send Error!");
+            },
+            MarketMessageEnum::TradeRequest { requester, product, quantity } => {
+                sender.send(MarketMessage { sender: self.id, reciever: message.sender,
+                    message: MarketMessageEnum::TradeResponse { requester, product, 
+                        available: self.available_for_trade(product, quantity), 
+                        price: self.prices.get(&product).copied() } })
+                    .expect("Send Error!");
+            },
+            MarketMessageEnum::TradeResponse { requester, product, available, 
+            price } => {
+                lcl_sender.send(ActorMessage::TradeInfo { reciever: requester, 
+                    market: message.sender, product, available, price })
+                    .expect("Send Error!");
+            },
+            MarketMessageEnum::FirmMessage { sender: from, reciever: to, action } => {
+                lcl_sender.send(ActorMessage::FirmToFirm { sender: from, 
+                    reciever: to, action })
+                    .expect("Send Error!");
+            },
+            // closing is only for the manager.
+            MarketMessageEnum::CloseMarket => (),
+        }
+        false
+    }
+
+    /// # Available For Trade
+    /// 
+    /// How much of the quantity requested is currently for sale here.
+    fn available_for_trade(&self, product: usize, quantity: f64) -> f64 {
+        self.products_for_sale.get(&product).copied()
+            .unwrap_or(0.0)
+            .min(quantity)
+    }
+
+    /// # Ship Goods
+    /// 
+    /// Tries to send a shipment from a firm to a neighboring market. Only
+    /// Merchant and Trader firms may ship goods.
+    /// 
+    /// The firm holds the goods, and the time to move them, before asking,
+    /// so we only check that it may ship them and where.
+    /// 
+    /// Returns ShipmentSent with the transport cost if it was sent, 
+    /// ShipmentRefused otherwise.
+    fn ship_goods(&mut self, data: &DataManager, traders: &HashSet<usize>,
+    sender: ActorInfo, destination: usize, product: usize, 
+    quantity: f64) -> ActorMessage {
+        let refused = ActorMessage::ShipmentRefused { sender, destination, product };
+        let owner = match sender {
+            ActorInfo::Firm(id) if traders.contains(&id) => id,
+            _ => return refused,
+        };
+        let connection = match self.neighbors.get(&destination) {
+            Some(connection) => connection,
+            None => return refused,
+        };
+        match Shipment::new(data, connection, owner, self.id, destination, 
+        product, quantity) {
+            Ok(shipment) => {
+                let cost = shipment.cost;
+                self.in_transit.push(shipment);
+                ActorMessage::ShipmentSent { sender, destination, product, 
+                    quantity, cost }
+            },
+            Err(_) => refused,
+        }
+    }
+
+    /// # Settle Trades
+    /// 
+    /// Records trades made in the order book into the market's daily data
+    /// and tells both buyer and seller to exchange the goods.
+    /// 
+    /// The last trade price (in AMV) becomes the market price of the product.
+    fn settle_trades(&mut self, trades: &[Trade], currency: usize, 
+    sender: &Sender<ActorMessage>) {
+        for trade in trades.iter() {
+            self.record_currency_exchange(trade.product, trade.quantity, 
+                currency, trade.price);
+            sender.send(ActorMessage::OrderFilled { buyer: trade.buyer, 
+                seller: trade.seller, product: trade.product, 
+                quantity: trade.quantity, currency, price: trade.price })
+                .expect("Send Error!");
+        }
+    }
+
+    /// # Record Currency Exchange
+    /// 
+    /// Records the sale of a product for a currency into the market's daily
+    /// data. The price is per unit, in the currency, and becomes the market 
+    /// price of the product.
+    fn record_currency_exchange(&mut self, product: usize, quantity: f64, 
+    currency: usize, price: f64) {
+        let currency_price = self.currency_price(currency);
+        *self.product_exchanged_total.entry(product).or_insert(0.0) += quantity;
+        *self.product_exchanged_total.entry(currency).or_insert(0.0) += quantity * price;
+        *self.product_sold.entry(product).or_insert(0.0) += quantity;
+        self.products_for_sale.entry(product)
+            .and_modify(|x| *x = (*x - quantity).max(0.0));
+        self.prices.insert(product, price * currency_price);
+    }
+
+    /// The AMV price of a currency used in settling trades.
+    fn currency_price(&self, currency: usize) -> f64 {
+        *self.prices.get(&currency).unwrap_or(&1.0)
+    }
+
     /// Does the work of finding a seller for a buyer as well as recording 
     /// their demand for future needs.
     pub fn find_seller(&mut self, product: usize, sender: ActorInfo) -> ActorMessage {
@@ -418,6 +764,38 @@ impl Market {
         }
     }
 
+    /// # Find Buyer
+    /// 
+    /// Finds a buyer for a salesman. Buyers are picked at random from the 
+    /// pops in the market, weighted by their wealth and how much of the 
+    /// product they've tried to buy today.
+    /// 
+    /// Returns BuyerNotFound if there is no one (other than the seller) 
+    /// with any weight.
+    pub fn find_buyer(&self, product: usize, sender: ActorInfo) -> ActorMessage {
+        let weights = self.pop_wealth_weight.iter()
+            .filter(|x| x.actor != sender)
+            .map(|x| (x.actor, x.weight * 
+                (1.0 + self.buyer_demand.get(&(product, x.actor)).unwrap_or(&0.0))))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect_vec();
+        let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        if weights.is_empty() {
+            return ActorMessage::BuyerNotFound { product, seller: sender };
+        }
+        let select = thread_rng().gen::<f64>() * total;
+        let mut sum = 0.0;
+        for (actor, weight) in weights.iter() {
+            sum += weight;
+            if sum > select {
+                return ActorMessage::FoundBuyer { seller: sender, buyer: *actor, product };
+            }
+        }
+        // floating point fallout, give it to the last.
+        ActorMessage::FoundBuyer { seller: sender, 
+            buyer: weights.last().unwrap().0, product }
+    }
+
     /// # Find Want Seller
     /// 
     /// A helper function which looks at available possible items which satisfy 
@@ -585,6 +963,11 @@ pub struct MarketHistory {
     /// The products which are Currencies in our market for whatever reason.
     /// Sorted by Salability (highest to lowest)
     pub currencies: Vec<usize>,
+    /// How the market matches buyers and sellers.
+    pub exchange_mode: ExchangeMode,
+    /// The markets next to ours, and how we connect to them, for those
+    /// who ship goods there.
+    pub neighbors: HashMap<usize, MarketConnection>,
 }
 
 impl MarketHistory {
@@ -600,6 +983,8 @@ impl MarketHistory {
             class_info: HashMap::new(),
             sale_priority: vec![],
             currencies: vec![],
+            exchange_mode: market.exchange_mode,
+            neighbors: market.neighbors.clone(),
         };
         // go through each product and copy over it's info from the market.
         // also add class prices.
@@ -729,6 +1114,45 @@ impl MarketHistory {
     }
 }
 
+/// A message recieved by the market during its day, tagged by where it came from.
+enum Incoming {
+    /// A message routed to us from another market.
+    Routed(MarketMessage),
+    /// A message from an actor within this market.
+    Local(ActorMessage),
+}
+
+/// Wakes the market's thread when either of its channels has a message.
+struct ThreadWaker(std::thread::Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Helper function
+/// 
+/// Blocks until either channel has a message, preferring those routed from 
+/// other markets, without spinning while both are empty.
+fn recv_either(routed: &Receiver<MarketMessage>, 
+local: &Receiver<ActorMessage>) -> Incoming {
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut routed_fut = routed.recv_async();
+    let mut local_fut = local.recv_async();
+    loop {
+        if let Poll::Ready(msg) = Pin::new(&mut routed_fut).poll(&mut cx) {
+            return Incoming::Routed(msg.expect("Unexpected Disconnect!"));
+        }
+        if let Poll::Ready(msg) = Pin::new(&mut local_fut).poll(&mut cx) {
+            return Incoming::Local(msg.expect("Unexpected Disconnect!"));
+        }
+        // a wake between polling and parking leaves the token, so this can't miss it.
+        std::thread::park();
+    }
+}
+
 /// Helper function
 /// 
 /// Calculates the value of a want given the market data and id of the want in question.
@@ -778,7 +1202,7 @@ impl ClassInfo {
 
 /// The Ways in which a market can connect to another market directly.
 /// Each has a values attached to them for additional information.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MarketConnection{
     /// A Land Connection, the value is the estimated average distance between
     /// all points in this market to any point in the other market.
@@ -806,6 +1230,89 @@ pub enum MarketConnection{
     Portal(f64, f64, f64),
 }
 
+/// How a market matches it's buyers and sellers.
+/// 
+/// Markets may be set to either so their outcomes can be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExchangeMode {
+    /// Buyers ask the market for a seller, who is picked by weighted random,
+    /// then the two bargain directly through offers and followups.
+    #[default]
+    Bargaining,
+    /// Sellers post asks (from their SellOrders) and buyers post bids into
+    /// an order book, matched in a continuous double auction. Trades are
+    /// settled in the currency product given.
+    OrderBook { currency: usize },
+}
+
+impl MarketConnection {
+    /// # Distance
+    /// 
+    /// The distance a shipment travels along this connection.
+    pub fn distance(&self) -> f64 {
+        match self {
+            MarketConnection::Land(distance) |
+            MarketConnection::Water(distance) |
+            MarketConnection::Sea(distance) |
+            MarketConnection::Air(distance) |
+            MarketConnection::Tunnel(distance) |
+            MarketConnection::Space(distance, _) |
+            MarketConnection::Portal(distance, _, _) => *distance,
+        }
+    }
+
+    /// # Travel Days
+    /// 
+    /// How many days a shipment along this connection spends in transit.
+    /// Always at least 1, save for Portals, which arrive at the end of the
+    /// day they are sent.
+    pub fn travel_days(&self) -> usize {
+        let speed = match self {
+            MarketConnection::Land(_) => 1.0,
+            MarketConnection::Water(_) => 2.0,
+            MarketConnection::Sea(_) => 4.0,
+            MarketConnection::Air(_) => 10.0,
+            MarketConnection::Tunnel(_) => 1.0,
+            MarketConnection::Space(_, _) => 10.0,
+            MarketConnection::Portal(_, _, _) => return 0,
+        };
+        ((self.distance() / (TRAVEL_DISTANCE_PER_DAY * speed)).ceil() as usize).max(1)
+    }
+
+    /// # Transport Cost
+    /// 
+    /// The time needed to move the quantity of the product along this 
+    /// connection, scaling with it's mass and bulk and the distance.
+    /// 
+    /// Water and Sea are cheaper than Land, Air is much more expensive, and
+    /// Space replaces distance with the Delta-V required.
+    /// 
+    /// Returns None if the product can't be transported, or the shipment
+    /// is larger than a Portal's throughput.
+    pub fn transport_cost(&self, product: &Product, quantity: f64) -> Option<f64> {
+        if !product.is_transportable() {
+            return None;
+        }
+        let mass = product.mass * quantity;
+        let bulk = product.bulk * quantity;
+        let (distance, rate) = match self {
+            MarketConnection::Land(distance) => (*distance, 1.0),
+            MarketConnection::Water(distance) => (*distance, 0.5),
+            MarketConnection::Sea(distance) => (*distance, 0.25),
+            MarketConnection::Air(distance) => (*distance, 5.0),
+            MarketConnection::Tunnel(distance) => (*distance, 1.0),
+            MarketConnection::Space(_, delta_v) => (*delta_v, 1.0),
+            MarketConnection::Portal(distance, mass_limit, bulk_limit) => {
+                if mass > *mass_limit || bulk > *bulk_limit {
+                    return None;
+                }
+                (*distance, 1.0)
+            },
+        };
+        Some(distance * rate * (mass * TRANSPORT_MASS_COST + bulk * TRANSPORT_BULK_COST))
+    }
+}
+
 /// Messages meant to be passed between markets.
 #[derive(Debug, Clone, Copy)]
 pub struct MarketMessage {
@@ -827,6 +1334,21 @@ pub enum MarketMessageEnum {
     /// Tells the market threads that they will not recieve any more messages 
     /// and to close out.
     ConfirmClose,
+    /// Asks the reciever what the price of a product is there, on behalf of
+    /// an actor in the sending market.
+    PriceQuery { asker: ActorInfo, product: usize },
+    /// The answer to a PriceQuery. None if the market has no price for it.
+    PriceResponse { asker: ActorInfo, product: usize, price: Option<f64> },
+    /// Asks the reciever how much of a product is available there, on behalf
+    /// of an actor in the sending market.
+    TradeRequest { requester: ActorInfo, product: usize, quantity: f64 },
+    /// The answer to a TradeRequest, how much of the quantity requested is
+    /// available and at what price.
+    TradeResponse { requester: ActorInfo, product: usize, available: f64,
+        price: Option<f64> },
+    /// A message between a parent firm and it's child. The manager routes it
+    /// to the market which contains the reciever.
+    FirmMessage { sender: usize, reciever: usize, action: FirmHierarchyAction },
 }
 
 /// helper struct for storing actors and their weight. Primarily used for 
@@ -867,6 +1389,81 @@ impl DealRecord {
     }
 }
 
+/// # Auction Record
+/// 
+/// Tracks an auction or reverse auction while it is open, including all 
+/// the bids made on it so far.
+#[derive(Debug, Clone)]
+pub struct AuctionRecord {
+    /// The id given to the auction by the market.
+    pub id: usize,
+    /// The actor who opened the auction.
+    pub organizer: ActorInfo,
+    /// Whether the organizer is selling or buying.
+    pub kind: AuctionKind,
+    /// The product being auctioned.
+    pub product: usize,
+    /// How much of the product is being auctioned.
+    pub quantity: f64,
+    /// The currency bids are made in.
+    pub currency: usize,
+    /// The worst unit price the organizer will accept.
+    pub reserve: f64,
+    /// The bids recieved, in the order they arrived.
+    pub bids: Vec<(ActorInfo, f64)>,
+}
+
+impl AuctionRecord {
+    pub fn new(id: usize,
+    organizer: ActorInfo,
+    kind: AuctionKind,
+    product: usize,
+    quantity: f64,
+    currency: usize,
+    reserve: f64) -> Self {
+        Self {
+            id,
+            organizer,
+            kind,
+            product,
+            quantity,
+            currency,
+            reserve,
+            bids: vec![]
+        }
+    }
+
+    /// Adds a bid to the auction. Bids from the organizer are ignored.
+    pub fn add_bid(&mut self, bidder: ActorInfo, price: f64) {
+        if bidder != self.organizer {
+            self.bids.push((bidder, price));
+        }
+    }
+
+    /// # Winning Bid
+    /// 
+    /// Gets the best bid which meets the reserve. For an Auction this is 
+    /// the highest, for a Reverse Auction the lowest. Ties go to whoever 
+    /// bid first.
+    /// 
+    /// Returns None if no bid meets the reserve.
+    pub fn winning_bid(&self) -> Option<(ActorInfo, f64)> {
+        let mut best: Option<(ActorInfo, f64)> = None;
+        for &(bidder, price) in self.bids.iter() {
+            let (meets_reserve, beats_best) = match self.kind {
+                AuctionKind::Auction => (price >= self.reserve,
+                    best.is_none_or(|x| price > x.1)),
+                AuctionKind::ReverseAuction => (price <= self.reserve,
+                    best.is_none_or(|x| price < x.1)),
+            };
+            if meets_reserve && beats_best {
+                best = Some((bidder, price));
+            }
+        }
+        best
+    }
+}
+
 /// Market History for wants to make estimating the price of a want easier 
 /// to find.
 #[derive(Debug, Clone, Copy)]
@@ -0,0 +1,89 @@
+//! Pops moving between markets.
+//!
+//! At the end of the day pops compare how well off they are against the
+//! people in neighboring markets. If a neighbor looks better, even after
+//! the cost of getting there, some share of the pop leaves for it.
+
+use crate::{constants::{MAX_MIGRATION_FRACTION, MIGRATION_RATE, MIGRATION_TRAVEL_COST},
+    objects::actor_objects::pop::Pop};
+
+use super::market::MarketConnection;
+
+/// # Market Appeal
+///
+/// How well off the people in a market (or pop) are, per person. This is
+/// what potential migrants compare when deciding whether to move.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MarketAppeal {
+    /// The market satisfaction of desires, in AMV, per person.
+    pub satisfaction: f64,
+    /// The wage estimate, in AMV, per person.
+    pub wage: f64,
+}
+
+impl MarketAppeal {
+    /// # Of Pops
+    ///
+    /// Gets the per person appeal across all the pops given, weighted by
+    /// their size.
+    pub fn of_pops<'a>(pops: impl Iterator<Item = &'a Pop>) -> Self {
+        let mut satisfaction = 0.0;
+        let mut wage = 0.0;
+        let mut people = 0;
+        for pop in pops {
+            satisfaction += pop.property.market_satisfaction;
+            wage += pop.property.wage_estimate;
+            people += pop.count();
+        }
+        if people == 0 {
+            return MarketAppeal::default();
+        }
+        MarketAppeal {
+            satisfaction: satisfaction / people as f64,
+            wage: wage / people as f64,
+        }
+    }
+
+    /// The total value of the appeal.
+    pub fn value(&self) -> f64 {
+        self.satisfaction + self.wage
+    }
+
+    /// # Migration Fraction
+    ///
+    /// How much of a pop with this appeal would leave for the destination
+    /// along the connection given.
+    ///
+    /// The gain of moving is the difference in value, less the cost of the
+    /// days spent travelling. If there's no gain, no one leaves. Otherwise
+    /// the fraction grows with the gain relative to what they have now, up
+    /// to MAX_MIGRATION_FRACTION.
+    pub fn migration_fraction(&self, destination: &MarketAppeal,
+    connection: &MarketConnection) -> f64 {
+        let travel = connection.travel_days() as f64 * MIGRATION_TRAVEL_COST;
+        let gain = destination.value() - self.value() - travel;
+        if gain <= 0.0 {
+            return 0.0;
+        }
+        (MIGRATION_RATE * gain / (self.value().abs() + gain))
+            .min(MAX_MIGRATION_FRACTION)
+    }
+}
+
+/// # Migration Record
+///
+/// A log entry for people who moved between markets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationRecord {
+    /// The pop the migrants came from.
+    pub pop: usize,
+    /// The pop the migrants are now in. The same as pop if the whole pop
+    /// moved.
+    pub migrants: usize,
+    /// The market they left.
+    pub origin: usize,
+    /// The market they moved to.
+    pub destination: usize,
+    /// How many people moved.
+    pub count: usize,
+}
@@ -0,0 +1,215 @@
+//! The Order Book used by markets which exchange via a continuous double
+//! auction rather than through random seller bargaining.
+//!
+//! Sellers post Asks, Buyers post Bids, and whenever a new order arrives it
+//! is matched against the best resting orders on the other side of the book.
+//! Any portion not matched rests in the book until it is either matched,
+//! cancelled, or the book is cleared at the end of the day.
+
+use std::collections::HashMap;
+
+use crate::objects::actor_objects::actor_message::ActorInfo;
+
+/// # Order Book
+///
+/// Stores the resting Asks and Bids for each product, priced in units of
+/// the market's settlement currency.
+///
+/// Asks are kept sorted from lowest to highest price, Bids from highest to
+/// lowest. Orders at the same price are kept in the order they arrived, so
+/// earlier orders are filled first.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    /// The resting sell orders for each product.
+    asks: HashMap<usize, Vec<Order>>,
+    /// The resting buy orders for each product.
+    bids: HashMap<usize, Vec<Order>>,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Submit Ask
+    ///
+    /// Takes in a sell order, matching it against any bids at or above it's
+    /// price. Whatever is not matched rests in the book.
+    ///
+    /// Trades are made at the price of the resting bid.
+    ///
+    /// Returns the trades made, in the order they were made.
+    pub fn submit_ask(&mut self, seller: ActorInfo, product: usize,
+    quantity: f64, price: f64) -> Vec<Trade> {
+        let mut remaining = quantity;
+        let mut trades = vec![];
+        if let Some(bids) = self.bids.get_mut(&product) {
+            while remaining > 0.0 {
+                // get the best bid which isn't our own.
+                let idx = match bids.iter()
+                .position(|x| x.actor != seller) {
+                    Some(idx) => idx,
+                    None => break,
+                };
+                if bids[idx].price < price {
+                    break; // best bid is below our ask, nothing more to do.
+                }
+                let fill = remaining.min(bids[idx].quantity);
+                trades.push(Trade { buyer: bids[idx].actor, seller, product,
+                    quantity: fill, price: bids[idx].price });
+                remaining -= fill;
+                bids[idx].quantity -= fill;
+                if bids[idx].quantity <= 0.0 {
+                    bids.remove(idx);
+                }
+            }
+        }
+        if remaining > 0.0 {
+            let asks = self.asks.entry(product).or_default();
+            // insert after all asks at or below our price, keeping time priority.
+            let idx = asks.iter().position(|x| x.price > price)
+                .unwrap_or(asks.len());
+            asks.insert(idx, Order { actor: seller, quantity: remaining, price });
+        }
+        trades
+    }
+
+    /// # Submit Bid
+    ///
+    /// Takes in a buy order, matching it against any asks at or below it's
+    /// price. Whatever is not matched rests in the book.
+    ///
+    /// Trades are made at the price of the resting ask.
+    ///
+    /// Returns the trades made, in the order they were made.
+    pub fn submit_bid(&mut self, buyer: ActorInfo, product: usize,
+    quantity: f64, price: f64) -> Vec<Trade> {
+        let mut remaining = quantity;
+        let mut trades = vec![];
+        if let Some(asks) = self.asks.get_mut(&product) {
+            while remaining > 0.0 {
+                // get the best ask which isn't our own.
+                let idx = match asks.iter()
+                .position(|x| x.actor != buyer) {
+                    Some(idx) => idx,
+                    None => break,
+                };
+                if asks[idx].price > price {
+                    break; // best ask is above our bid, nothing more to do.
+                }
+                let fill = remaining.min(asks[idx].quantity);
+                trades.push(Trade { buyer, seller: asks[idx].actor, product,
+                    quantity: fill, price: asks[idx].price });
+                remaining -= fill;
+                asks[idx].quantity -= fill;
+                if asks[idx].quantity <= 0.0 {
+                    asks.remove(idx);
+                }
+            }
+        }
+        if remaining > 0.0 {
+            let bids = self.bids.entry(product).or_default();
+            // insert after all bids at or above our price, keeping time priority.
+            let idx = bids.iter().position(|x| x.price < price)
+                .unwrap_or(bids.len());
+            bids.insert(idx, Order { actor: buyer, quantity: remaining, price });
+        }
+        trades
+    }
+
+    /// # Cancel Bid
+    ///
+    /// Removes any resting bids from the buyer for the product, returning
+    /// the quantity which was cancelled.
+    pub fn cancel_bid(&mut self, buyer: ActorInfo, product: usize) -> f64 {
+        OrderBook::cancel(&mut self.bids, buyer, product)
+    }
+
+    /// # Cancel Ask
+    ///
+    /// Removes any resting asks from the seller for the product, returning
+    /// the quantity which was cancelled.
+    pub fn cancel_ask(&mut self, seller: ActorInfo, product: usize) -> f64 {
+        OrderBook::cancel(&mut self.asks, seller, product)
+    }
+
+    fn cancel(side: &mut HashMap<usize, Vec<Order>>, actor: ActorInfo,
+    product: usize) -> f64 {
+        let mut cancelled = 0.0;
+        if let Some(orders) = side.get_mut(&product) {
+            cancelled = orders.iter()
+                .filter(|x| x.actor == actor)
+                .map(|x| x.quantity)
+                .sum();
+            orders.retain(|x| x.actor != actor);
+        }
+        cancelled
+    }
+
+    /// The lowest price currently being asked for a product, if any.
+    pub fn best_ask(&self, product: usize) -> Option<f64> {
+        self.asks.get(&product)
+            .and_then(|x| x.first())
+            .map(|x| x.price)
+    }
+
+    /// The highest price currently being bid for a product, if any.
+    pub fn best_bid(&self, product: usize) -> Option<f64> {
+        self.bids.get(&product)
+            .and_then(|x| x.first())
+            .map(|x| x.price)
+    }
+
+    /// The total quantity of a product currently resting in asks.
+    pub fn ask_depth(&self, product: usize) -> f64 {
+        self.asks.get(&product)
+            .map(|x| x.iter().map(|o| o.quantity).sum())
+            .unwrap_or(0.0)
+    }
+
+    /// The total quantity of a product currently resting in bids.
+    pub fn bid_depth(&self, product: usize) -> f64 {
+        self.bids.get(&product)
+            .map(|x| x.iter().map(|o| o.quantity).sum())
+            .unwrap_or(0.0)
+    }
+
+    /// Clears out all resting orders, used at the end of the day.
+    pub fn clear(&mut self) {
+        self.asks.clear();
+        self.bids.clear();
+    }
+}
+
+/// A resting order in the order book.
+#[derive(Debug, Clone, Copy)]
+pub struct Order {
+    /// Who placed the order.
+    pub actor: ActorInfo,
+    /// How many units remain to be filled.
+    pub quantity: f64,
+    /// The unit price in the settlement currency.
+    pub price: f64,
+}
+
+/// A completed match between a bid and an ask.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    /// The actor recieving the product and paying the currency.
+    pub buyer: ActorInfo,
+    /// The actor giving the product and recieving the currency.
+    pub seller: ActorInfo,
+    /// The product exchanged.
+    pub product: usize,
+    /// How many units were exchanged.
+    pub quantity: f64,
+    /// The unit price in the settlement currency.
+    pub price: f64,
+}
+
+impl Trade {
+    /// The total currency paid by the buyer to the seller.
+    pub fn payment(&self) -> f64 {
+        self.quantity * self.price
+    }
+}
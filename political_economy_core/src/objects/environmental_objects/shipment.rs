@@ -0,0 +1,88 @@
+//! Goods in transit between markets.
+//!
+//! Shipments are created when a merchant or trader sends goods along a
+//! MarketConnection. They are held by the origin market until they arrive,
+//! after which the goods are stocked in the destination by the owner's firm
+//! there, see ActorManager::move_shipments.
+
+use crate::data_manager::DataManager;
+
+use super::market::MarketConnection;
+
+/// # Shipment
+///
+/// A quantity of a product being moved from one market to a neighbor by
+/// the firm which owns it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shipment {
+    /// The id of the firm which owns the goods.
+    pub owner: usize,
+    /// The market the goods were sent from.
+    pub origin: usize,
+    /// The market the goods are going to.
+    pub destination: usize,
+    /// The product being shipped.
+    pub product: usize,
+    /// How much of the product is being shipped.
+    pub quantity: f64,
+    /// The transport cost paid to send it.
+    pub cost: f64,
+    /// How many more days the shipment will be in transit after the current
+    /// one.
+    pub days_remaining: usize,
+}
+
+impl Shipment {
+    /// # New
+    ///
+    /// Creates a shipment of the product along the connection given,
+    /// calculating it's cost and travel time.
+    ///
+    /// Fails if the product does not exist, cannot be transported (Fixed or
+    /// Service products), or cannot fit through the connection.
+    pub fn new(data: &DataManager,
+    connection: &MarketConnection,
+    owner: usize,
+    origin: usize,
+    destination: usize,
+    product: usize,
+    quantity: f64) -> Result<Self, String> {
+        if quantity <= 0.0 {
+            return Err(String::from("Shipment must have a positive quantity."));
+        }
+        let product_info = match data.products.get(&product) {
+            Some(info) => info,
+            None => return Err(String::from("Product not found.")),
+        };
+        if !product_info.is_transportable() {
+            return Err(String::from("Product cannot be moved out of it's market."));
+        }
+        let cost = match connection.transport_cost(product_info, quantity) {
+            Some(cost) => cost,
+            None => return Err(String::from("Shipment cannot travel along this connection.")),
+        };
+        Ok(Self {
+            owner,
+            origin,
+            destination,
+            product,
+            quantity,
+            cost,
+            days_remaining: connection.travel_days(),
+        })
+    }
+
+    /// # Advance Day
+    ///
+    /// Called at the end of each day. If no days remain the shipment has 
+    /// arrived, otherwise it moves a day further along it's way.
+    ///
+    /// Returns true if it has arrived.
+    pub fn advance_day(&mut self) -> bool {
+        if self.days_remaining == 0 {
+            return true;
+        }
+        self.days_remaining -= 1;
+        false
+    }
+}
@@ -18,15 +18,15 @@
 //!   - Species
 //!     - Subtypes (Sex/Caste)
 //!     - Cohorts (Life Phases)
-//!   - Civilization (Not Built)
+//!   - Civilization
 //!     - Epoch (Ages)
 //!   - Culture
 //!     - Class
 //!     - Generation
-//!   - Ideology (Not Built)
+//!   - Ideology
 //!     - Wave
 //!     - Faction
-//!   - Movements (Not Built)
+//!   - Movements
 //! - Environmental
 //!   - Territories
 //!   - Markets
@@ -104,6 +104,8 @@ pub struct Runner {
     pub demographics: Demographics,
     pub map: (),
     pub actors: ActorManager,
+    /// How many days have passed in the simulation.
+    pub day: u64,
 }
 
 impl Runner {
@@ -114,7 +116,8 @@ impl Runner {
                 data_manager, 
                 demographics, 
                 map: (),
-                actors 
+                actors,
+                day: 0,
             } 
         }
 
@@ -129,12 +132,35 @@ impl Runner {
     pub fn data_update_phase(&mut self) {
     }
 
+    /// The Population Phase
+    /// 
+    /// Pops are born and die, based on their demographics and how well they
+    /// are doing, then assimilate and convert. Movements form and change
+    /// from how the last day went. Runs once per day.
+    pub fn population_phase(&mut self) {
+        let discontent = self.actors.discontent();
+        self.demographics.update_movements(&discontent);
+        self.demographics.advance_generations(1.0);
+        self.demographics.learn_technologies(
+            &self.actors.market_technologies(&self.data_manager));
+        self.demographics.advance_epochs(&self.data_manager.technology);
+        self.actors.practice_skills(&self.data_manager, &self.demographics, 1.0);
+        self.actors.population_phase(&self.demographics, 1.0);
+        self.demographics.record_conversions(&self.actors.conversion_log);
+    }
+
     /// The Market Day
     /// 
-    /// Calls the Actor to run a market day.
+    /// Decides which desires pops have today, then calls the Actor to run 
+    /// a market day. Pops update their mood from how the day went, then
+    /// look for work in the labor market, and the day counter advances.
     pub fn market_day(&mut self) {
-        self.actors.run_market_day(&self.data_manager,
+        self.actors.activate_desires(self.day);
+        self.actors.run_market_day(&mut self.data_manager,
             &self.demographics,
             &mut self.map);
+        self.actors.update_moods();
+        self.actors.labor_market_phase(&mut self.data_manager, &self.demographics);
+        self.day += 1;
     }
 }
\ No newline at end of file
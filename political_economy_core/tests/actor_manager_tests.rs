@@ -0,0 +1,175 @@
+mod route_message_should {
+    use std::collections::{HashMap, HashSet};
+
+    use political_economy_core::{
+        actor_manager::ActorManager,
+        objects::{
+            actor_objects::actor_message::{ActorInfo, FirmHierarchyAction},
+            environmental_objects::market::{MarketMessage, MarketMessageEnum}
+        }
+    };
+
+    fn prepare_routes() -> (HashSet<usize>, HashMap<usize, usize>) {
+        let markets = HashSet::from([1, 2]);
+        let firm_markets = HashMap::from([(10, 1), (20, 2)]);
+        (markets, firm_markets)
+    }
+
+    #[test]
+    pub fn forward_queries_to_the_market_addressed() {
+        let (markets, firm_markets) = prepare_routes();
+        let message = MarketMessage { sender: 1, reciever: 2,
+            message: MarketMessageEnum::PriceQuery { asker: ActorInfo::Pop(5), 
+                product: 3 } };
+
+        let result = ActorManager::route_message(message, &markets, 
+            &firm_markets).unwrap();
+
+        assert_eq!(result.sender, 1);
+        assert_eq!(result.reciever, 2);
+        if let MarketMessageEnum::PriceQuery { asker, product } = result.message {
+            assert_eq!(asker, ActorInfo::Pop(5));
+            assert_eq!(product, 3);
+        } else { panic!("Wrong Message Forwarded."); }
+    }
+
+    #[test]
+    pub fn send_firm_messages_to_the_recievers_market() {
+        let (markets, firm_markets) = prepare_routes();
+        let message = MarketMessage { sender: 1, reciever: 0,
+            message: MarketMessageEnum::FirmMessage { sender: 10, reciever: 20, 
+                action: FirmHierarchyAction::Acknowledge } };
+
+        let result = ActorManager::route_message(message, &markets, 
+            &firm_markets).unwrap();
+        assert_eq!(result.reciever, 2);
+
+        // firms we don't know of are dropped.
+        let message = MarketMessage { sender: 1, reciever: 0,
+            message: MarketMessageEnum::FirmMessage { sender: 10, reciever: 30, 
+                action: FirmHierarchyAction::Acknowledge } };
+        assert!(ActorManager::route_message(message, &markets, 
+            &firm_markets).is_none());
+    }
+
+    #[test]
+    pub fn bounce_queries_to_unknown_markets_with_empty_answers() {
+        let (markets, firm_markets) = prepare_routes();
+        let message = MarketMessage { sender: 1, reciever: 7,
+            message: MarketMessageEnum::TradeRequest { requester: ActorInfo::Firm(10), 
+                product: 3, quantity: 5.0 } };
+
+        let result = ActorManager::route_message(message, &markets, 
+            &firm_markets).unwrap();
+
+        assert_eq!(result.sender, 7);
+        assert_eq!(result.reciever, 1);
+        if let MarketMessageEnum::TradeResponse { requester, product, 
+        available, price } = result.message {
+            assert_eq!(requester, ActorInfo::Firm(10));
+            assert_eq!(product, 3);
+            assert_eq!(available, 0.0);
+            assert_eq!(price, None);
+        } else { panic!("Wrong Message Returned."); }
+    }
+
+    #[test]
+    pub fn not_route_closing_messages() {
+        let (markets, firm_markets) = prepare_routes();
+        let message = MarketMessage { sender: 1, reciever: 0,
+            message: MarketMessageEnum::CloseMarket };
+
+        assert!(ActorManager::route_message(message, &markets, 
+            &firm_markets).is_none());
+    }
+}
+
+mod move_shipments_should {
+    use std::collections::HashMap;
+
+    use political_economy_core::{
+        actor_manager::ActorManager,
+        data_manager::DataManager,
+        objects::{
+            actor_objects::firm::{Firm, FirmKind, OrganizationalStructure, 
+                OwnershipStructure, ProfitStructure},
+            environmental_objects::{market::{ExchangeMode, Market}, 
+                shipment::Shipment}
+        }
+    };
+
+    /// Two markets, with a merchant in the first which has 5 units of 
+    /// product 3 arriving in the second.
+    fn prepare_manager() -> ActorManager {
+        let mut origin = Market::new(1, "Origin".into(), ExchangeMode::Bargaining);
+        let destination = Market::new(2, "Destination".into(), 
+            ExchangeMode::Bargaining);
+        origin.firms.push(10);
+        origin.in_transit.push(Shipment { owner: 10, origin: 1, destination: 2,
+            product: 3, quantity: 5.0, cost: 1.0, days_remaining: 0 });
+        let merchant = Firm::new(10, "Merchant".into(), "".into(), 
+            FirmKind::Merchant, OwnershipStructure::Private, 
+            ProfitStructure::PrivatelyOwned, 
+            OrganizationalStructure::SmallBusiness).expect("Messed up new.");
+        ActorManager {
+            markets: HashMap::from([(1, origin), (2, destination)]),
+            pops: HashMap::new(),
+            firms: HashMap::from([(10, merchant)]),
+            institutions: HashMap::new(),
+            states: HashMap::new(),
+            migration_log: vec![],
+            conversion_log: vec![],
+            labor_log: vec![],
+        }
+    }
+
+    #[test]
+    pub fn stock_arrivals_in_a_branch_opened_in_the_destination() {
+        let mut data = DataManager::new();
+        let mut manager = prepare_manager();
+
+        manager.move_shipments(&mut data);
+
+        assert!(manager.markets[&1].in_transit.is_empty());
+        let destination = &manager.markets[&2];
+        assert_eq!(destination.firms.len(), 1);
+        let branch = &manager.firms[&destination.firms[0]];
+        assert_ne!(branch.id, 10);
+        assert_eq!(branch.parent, Some(10));
+        assert_eq!(branch.firm_kind, FirmKind::Merchant);
+        assert_eq!(branch.variant_name, "Destination");
+        assert_eq!(branch.property.property[&3].total_property, 5.0);
+        assert_eq!(manager.firms[&10].children, vec![branch.id]);
+        // nothing is left with the owner back home.
+        assert!(!manager.firms[&10].property.property.contains_key(&3));
+    }
+
+    #[test]
+    pub fn stock_arrivals_in_an_existing_child_in_the_destination() {
+        let mut data = DataManager::new();
+        let mut manager = prepare_manager();
+        let child = manager.firms[&10].branch(20, "Destination".into());
+        manager.firms.insert(20, child);
+        manager.firms.get_mut(&10).unwrap().children.push(20);
+        manager.markets.get_mut(&2).unwrap().firms.push(20);
+
+        manager.move_shipments(&mut data);
+
+        assert_eq!(manager.markets[&2].firms, vec![20]);
+        assert_eq!(manager.firms.len(), 2);
+        assert_eq!(manager.firms[&20].property.property[&3].total_property, 5.0);
+        assert_eq!(manager.firms[&10].children, vec![20]);
+    }
+
+    #[test]
+    pub fn leave_arrivals_of_missing_owners_for_anyone() {
+        let mut data = DataManager::new();
+        let mut manager = prepare_manager();
+        manager.firms.clear();
+
+        manager.move_shipments(&mut data);
+
+        assert_eq!(manager.markets[&2].resources[&3], 5.0);
+        assert!(manager.markets[&2].firms.is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+mod civilization_tests {
+    use std::collections::{HashMap, HashSet};
+
+    use political_economy_core::{
+        demographics::Demographics,
+        objects::{
+            data_objects::technology::Technology,
+            demographic_objects::civilization::{Civilization, Epoch}
+        }
+    };
+
+    fn make_technology() -> HashMap<usize, Technology> {
+        let mut result = HashMap::new();
+        for (id, tier) in [(0, 0), (1, 1), (2, 1), (3, 1), (4, 1)] {
+            result.insert(id, Technology { id, name: format!("Tech {}", id),
+                description: "".into(), base_cost: 1, tier, 
+                families: HashSet::new(), children: HashSet::new(), 
+                parents: HashSet::new() });
+        }
+        result
+    }
+
+    fn make_civilization() -> Civilization {
+        let mut test = Civilization::new(0, "Civ".into(), "".into())
+            .expect("Messed up new.");
+        test.add_epoch(Epoch::new("Stone".into(), 0, 0.0, HashSet::new(), Some(0))
+            .expect("Messed up new."));
+        test.add_epoch(Epoch::new("Bronze".into(), 1, 0.5, HashSet::new(), Some(1))
+            .expect("Messed up new."));
+        test
+    }
+
+    #[test]
+    pub fn advance_epochs_once_enough_of_a_tier_is_known() {
+        let technology = make_technology();
+        let mut test = make_civilization();
+        assert_eq!(test.current_epoch, None);
+
+        assert!(test.advance_epoch(&technology));
+        assert_eq!(test.current_epoch, Some(0));
+        assert_eq!(test.epoch().unwrap().default_culture, Some(0));
+
+        test.known_technologies.insert(1);
+        assert_eq!(test.known_share(1, &technology), 0.25);
+        assert!(!test.advance_epoch(&technology));
+
+        test.known_technologies.insert(3);
+        assert!(test.advance_epoch(&technology));
+        assert_eq!(test.current_epoch, Some(1));
+        // no more epochs to reach.
+        assert!(!test.advance_epoch(&technology));
+
+        assert!(Epoch::new("Bad".into(), 0, 1.5, HashSet::new(), None).is_err());
+    }
+
+    #[test]
+    pub fn learn_what_their_markets_and_epochs_know() {
+        let technology = make_technology();
+        let mut test = Demographics::new();
+        let mut civ = Civilization::new(0, "Civ".into(), "".into())
+            .expect("Messed up new.");
+        civ.add_epoch(Epoch::new("Stone".into(), 0, 0.0, HashSet::from([0]), None)
+            .expect("Messed up new."));
+        civ.add_epoch(Epoch::new("Bronze".into(), 1, 0.5, HashSet::from([4]), None)
+            .expect("Messed up new."));
+        test.civilizations.insert(civ.id, civ);
+        test.assign_market(5, Some(0));
+
+        // markets not in the civilization teach it nothing.
+        let known = HashMap::from([(5, HashSet::from([1])), (6, HashSet::from([2]))]);
+        test.learn_technologies(&known);
+        assert_eq!(test.civilizations[&0].known_technologies, HashSet::from([1]));
+
+        // entering the stone age teaches it's technology, not yet enough for bronze.
+        test.advance_epochs(&technology);
+        assert_eq!(test.civilizations[&0].current_epoch, Some(0));
+        assert_eq!(test.civilizations[&0].known_technologies, HashSet::from([0, 1]));
+
+        let known = HashMap::from([(5, HashSet::from([3]))]);
+        test.learn_technologies(&known);
+        test.advance_epochs(&technology);
+        assert_eq!(test.civilizations[&0].current_epoch, Some(1));
+        assert!(test.civilizations[&0].known_technologies.contains(&4));
+    }
+
+    #[test]
+    pub fn track_which_markets_belong_to_which_civilization() {
+        let mut test = Demographics::new();
+        let civ = make_civilization();
+        test.civilizations.insert(civ.id, civ);
+        let mut other = make_civilization();
+        other.id = 1;
+        test.civilizations.insert(other.id, other);
+
+        test.assign_market(5, Some(0));
+        assert_eq!(test.market_civilization(5).unwrap().id, 0);
+
+        test.assign_market(5, Some(1));
+        assert_eq!(test.market_civilization(5).unwrap().id, 1);
+        assert!(!test.civilizations[&0].markets.contains(&5));
+
+        test.assign_market(5, None);
+        assert!(test.market_civilization(5).is_none());
+    }
+}
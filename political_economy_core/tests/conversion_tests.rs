@@ -0,0 +1,72 @@
+mod conversion_tests {
+    use political_economy_core::{
+        constants::{CONVERSION_RATE, RELATED_CONVERSION_MULTIPLIER},
+        demographics::Demographics,
+        objects::{
+            actor_objects::state::State,
+            demographic_objects::{
+                conversion::{ConversionKind, ConversionPressure, 
+                    ConversionPressures, ConversionRecord},
+                pop_breakdown_table::{PBRow, PopBreakdownTable}
+            }
+        }
+    };
+
+    fn make_breakdown() -> PopBreakdownTable {
+        let mut result = PopBreakdownTable { table: vec![], total: 0 };
+        result.insert_pops(PBRow::new(0, None, None,
+            Some(0), None, None, Some(1), None, None, 60));
+        result.insert_pops(PBRow::new(0, None, None,
+            Some(1), None, None, None, None, None, 30));
+        result.insert_pops(PBRow::new(0, None, None,
+            None, None, None, None, None, None, 10));
+        result
+    }
+
+    #[test]
+    pub fn convert_faster_when_related() {
+        let test = ConversionPressure { target: 0, share: 0.5, policy: 1.0 };
+
+        assert_eq!(test.yearly_rate(false), CONVERSION_RATE * 1.5);
+        assert_eq!(test.yearly_rate(true), 
+            CONVERSION_RATE * 1.5 * RELATED_CONVERSION_MULTIPLIER);
+    }
+
+    #[test]
+    pub fn pull_toward_dominant_and_official_groups() {
+        let breakdown = make_breakdown();
+        let states = vec![State { id: 0, name: "State".into(), 
+            variant_name: "".into(), official_culture: Some(1), 
+            official_ideology: Some(1), conversion_policy: 0.5 }];
+
+        let result = ConversionPressures::of_market(&breakdown, states.iter());
+
+        assert_eq!(result.cultures.len(), 2);
+        assert_eq!(result.cultures[0], 
+            ConversionPressure { target: 0, share: 0.6, policy: 0.0 });
+        assert_eq!(result.cultures[1], 
+            ConversionPressure { target: 1, share: 0.3, policy: 0.5 });
+        // the official ideology is also dominant, so it's just promoted.
+        assert_eq!(result.ideologies, 
+            vec![ConversionPressure { target: 1, share: 0.6, policy: 0.5 }]);
+
+        let no_states = ConversionPressures::of_market(&breakdown, 
+            Vec::<State>::new().iter());
+        assert_eq!(no_states.cultures.len(), 1);
+        assert_eq!(no_states.ideologies.len(), 1);
+    }
+
+    #[test]
+    pub fn total_conversions_in_demographics() {
+        let mut test = Demographics::new();
+        let record = ConversionRecord { pop: 0, market: 0, 
+            kind: ConversionKind::Culture, from: None, to: 1, count: 5 };
+
+        test.record_conversions(&[record, ConversionRecord { pop: 1, ..record },
+            ConversionRecord { kind: ConversionKind::Ideology, ..record }]);
+
+        assert_eq!(test.conversions.len(), 2);
+        assert_eq!(test.conversions[&(ConversionKind::Culture, None, 1)], 10);
+        assert_eq!(test.conversions[&(ConversionKind::Ideology, None, 1)], 5);
+    }
+}
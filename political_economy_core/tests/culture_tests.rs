@@ -0,0 +1,87 @@
+mod culture_tests {
+    use political_economy_core::objects::{
+        actor_objects::desire::Desire,
+        data_objects::item::Item,
+        demographic_objects::culture::{Culture, CultureClass, Generation}
+    };
+
+    fn fashion(amount: f64) -> Desire {
+        Desire { item: Item::Want(3), start: 20, end: None, amount,
+            satisfaction: 0.0, step: 5, tags: vec![] }
+    }
+
+    fn music(amount: f64) -> Desire {
+        Desire { item: Item::Product(7), start: 15, end: None, amount,
+            satisfaction: 0.0, step: 5, tags: vec![] }
+    }
+
+    fn make_culture() -> Culture {
+        let mut test = Culture::new(0, "Culture".into(), "".into(),
+            0.01, 0.01, 1.0, vec![fashion(1.0)], vec![])
+            .expect("Messed up new.");
+        test.add_class(CultureClass::new("Peasant".into(), vec![0, 1], 0.01, 0.02,
+            vec![fashion(-1.0)]).expect("Messed up new."));
+        test.add_class(CultureClass::new("Noble".into(), vec![], -0.01, -0.01,
+            vec![fashion(1.0)]).expect("Messed up new."));
+        test.add_generation(Generation::new("Elders".into(), 100, vec![])
+            .expect("Messed up new."));
+        test.add_generation(Generation::new("Youth".into(), 50, vec![music(1.0)])
+            .expect("Messed up new."));
+        test
+    }
+
+    #[test]
+    pub fn build_data_table_for_every_combination() {
+        let test = make_culture();
+
+        assert_eq!(test.data_table.len(), 9);
+        let base = test.get_subentry(None, None).unwrap();
+        assert_eq!(base.birth_rate_modifier, 0.01);
+        assert_eq!(base.desires.len(), 1);
+
+        // peasants lose their fashion desire entirely.
+        let peasant = test.get_subentry(Some(0), None).unwrap();
+        assert_eq!(peasant.birth_rate_modifier, 0.02);
+        assert!((peasant.mortality_rate_modifier - 0.03).abs() < 0.00001);
+        assert!(peasant.desires.is_empty());
+
+        // young nobles want more fashion and some music.
+        let young_noble = test.get_subentry(Some(1), Some(1)).unwrap();
+        assert_eq!(young_noble.birth_rate_modifier, 0.0);
+        assert_eq!(young_noble.desires.len(), 2);
+        assert_eq!(young_noble.desires.iter()
+            .find(|x| x.item == Item::Want(3)).unwrap().amount, 2.0);
+
+        assert!(test.get_subentry(Some(2), None).is_err());
+        assert!(test.get_subentry(None, Some(2)).is_err());
+    }
+
+    #[test]
+    pub fn limit_jobs_by_class() {
+        let test = make_culture();
+
+        assert!(test.class_can_work(None, 5));
+        assert!(test.class_can_work(Some(0), 1));
+        assert!(!test.class_can_work(Some(0), 5));
+        assert!(test.class_can_work(Some(1), 5));
+    }
+
+    #[test]
+    pub fn advance_generations_over_time() {
+        let mut test = make_culture();
+        assert_eq!(test.current_generation, Some(0));
+
+        assert!(!test.advance_generation(99.0));
+        assert_eq!(test.current_generation, Some(0));
+        assert!(test.advance_generation(1.0));
+        assert_eq!(test.current_generation, Some(1));
+        // the last generation continues indefinitely.
+        assert!(!test.advance_generation(1000.0));
+        assert_eq!(test.current_generation, Some(1));
+
+        let mut empty = Culture::new(1, "Empty".into(), "".into(),
+            0.0, 0.0, 1.0, vec![], vec![]).expect("Messed up new.");
+        assert!(!empty.advance_generation(1000.0));
+        assert_eq!(empty.current_generation, None);
+    }
+}
@@ -1,5 +1,5 @@
 use political_economy_core::objects::{
-    actor_objects::desire::Desire,
+    actor_objects::desire::{Desire, DesireTag},
     data_objects::item::Item,
 };
 
@@ -291,6 +291,27 @@ mod desire_tests {
         assert_eq!(result.amount, test.amount * 5.0);
     }
 
+    #[test]
+    pub fn overlay_desires_onto_their_base() {
+        let food = Desire{ item: Item::Product(0), start: 0, end: None, 
+            amount: 2.0, satisfaction: 0.0, step: 0, tags: vec![]};
+        let drink = Desire{ item: Item::Product(1), start: 0, end: None, 
+            amount: 1.0, satisfaction: 0.0, step: 0, tags: vec![]};
+        let mut base = vec![food.clone(), drink.clone()];
+        let mut more_food = food.clone();
+        more_food.amount = 1.0;
+        let mut no_drink = drink.clone();
+        no_drink.amount = -1.0;
+        let mut clothes = food.clone();
+        clothes.item = Item::Product(2);
+
+        Desire::overlay_desires(&mut base, &[more_food, no_drink, clothes]);
+
+        assert_eq!(base.len(), 2);
+        assert_eq!(base[0].amount, 3.0);
+        assert_eq!(base[1].item, Item::Product(2));
+    }
+
     #[test]
     pub fn correctly_add_satisfaction() {
         let mut test = Desire{ 
@@ -725,4 +746,36 @@ mod desire_tests {
             assert!(!test.satisfied_at_tier(6));
         }
     }
+
+    pub mod is_active_on_should {
+        use rand::thread_rng;
+
+        use super::super::*;
+
+        fn tagged(tags: Vec<DesireTag>) -> Desire {
+            Desire { item: Item::Want(2), start: 0, end: None, amount: 1.0,
+                satisfaction: 0.0, step: 0, tags }
+        }
+
+        #[test]
+        pub fn only_be_active_on_the_days_needed() {
+            let mut rng = thread_rng();
+            let always = tagged(vec![DesireTag::Toxic]);
+            assert!(always.is_toxic());
+            assert!(always.is_active_on(3, &mut rng));
+
+            let periodic = tagged(vec![DesireTag::Periodic { offset: 2, cycle: 7 }]);
+            assert!(!periodic.is_toxic());
+            assert!(periodic.is_active_on(2, &mut rng));
+            assert!(periodic.is_active_on(9, &mut rng));
+            assert!(!periodic.is_active_on(3, &mut rng));
+
+            // a 1 in 1 chance is always needed, otherwise it's by chance.
+            let sporadic = tagged(vec![DesireTag::Sporadic(1)]);
+            assert!(sporadic.is_active_on(0, &mut rng));
+            let sporadic = tagged(vec![DesireTag::Sporadic(4)]);
+            let active = (0..1000).filter(|x| sporadic.is_active_on(*x, &mut rng)).count();
+            assert!(100 < active && active < 400);
+        }
+    }
 }
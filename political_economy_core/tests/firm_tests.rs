@@ -0,0 +1,738 @@
+mod firm_tests {
+    use std::{collections::{HashMap, VecDeque}, thread};
+
+    use political_economy_core::{
+        constants::{FIRM_MARKET_PRICE_WEIGHT, FIRM_PRICE_STEP, SALARY_HOURS_ADJUSTMENT,
+            SALARY_PAY_PERIOD, TIME_PRODUCT_ID},
+        data_manager::DataManager,
+        demographics::Demographics,
+        objects::{
+            actor_objects::{
+                actor::Actor,
+                actor_message::{ActorInfo, ActorMessage, FirmEmployeeAction,
+                    FirmHierarchyAction, OfferResult},
+                firm::{Firm, FirmKind, OrganizationalStructure,
+                    OwnershipStructure, Paycheck, ProfitStructure},
+                firm_job::{AssignmentInfo, FirmJob, WageType},
+                job::Job,
+                property_info::PropertyInfo,
+                seller::Seller
+            },
+            data_objects::{
+                item::Item,
+                process::{Process, ProcessPart, ProcessSectionTag}
+            },
+            environmental_objects::market::{ExchangeMode, MarketConnection, MarketHistory,
+                ProductInfo}
+        }
+    };
+
+    fn make_firm() -> Firm {
+        Firm::new(0, "Workshop".into(), "".into(), FirmKind::Workshop,
+            OwnershipStructure::Private, ProfitStructure::PrivatelyOwned,
+            OrganizationalStructure::SmallBusiness).expect("Messed up new.")
+    }
+
+    fn make_job(job: usize, wage_type: WageType, wage: f64,
+    pop: Option<usize>) -> FirmJob {
+        FirmJob { job, wage_type, wage, wage_unit_priority: vec![],
+            assignments: HashMap::new(), pop }
+    }
+
+    /// Every product costs 1.0.
+    fn make_history() -> MarketHistory {
+        let mut history = MarketHistory {
+            product_info: HashMap::new(),
+            sale_priority: vec![],
+            currencies: vec![],
+            exchange_mode: ExchangeMode::Bargaining,
+            neighbors: HashMap::new(),
+            class_info: HashMap::new(),
+            want_info: HashMap::new(),
+        };
+        for idx in 0..26 {
+            history.product_info.insert(idx, ProductInfo { available: 0.0,
+                price: 1.0, offered: 0.0, sold: 0.0, salability: 0.5,
+                is_currency: false });
+        }
+        history
+    }
+
+    fn part(product: usize, amount: f64, part: ProcessSectionTag) -> ProcessPart {
+        ProcessPart { item: Item::Product(product), amount, part_tags: vec![],
+            part }
+    }
+
+    /// Adds a job with a single process, which takes 1 time and 1 stone
+    /// (13) with an axe (12) as capital to make 2 hooks (9).
+    fn add_hook_making(data: &mut DataManager) -> (usize, usize) {
+        let process = data.new_process_id();
+        data.processes.insert(process, Process { id: process,
+            name: "Hook Making".into(), variant_name: "".into(),
+            description: "".into(), minimum_time: 1.0,
+            process_parts: vec![part(TIME_PRODUCT_ID, 1.0, ProcessSectionTag::Input),
+                part(13, 1.0, ProcessSectionTag::Input),
+                part(12, 1.0, ProcessSectionTag::Capital),
+                part(9, 2.0, ProcessSectionTag::Output)],
+            process_tags: vec![], technology_requirement: None,
+            tertiary_tech: None });
+        let job = data.jobs.keys().max().unwrap() + 1;
+        let mut hook_maker = Job::new(job, "Hook Maker".into(), "".into(), 0);
+        hook_maker.insert_process(process);
+        data.jobs.insert(job, hook_maker);
+        (job, process)
+    }
+
+    #[test]
+    pub fn require_a_name_and_start_empty() {
+        let firm = make_firm();
+
+        assert!(firm.jobs.is_empty());
+        assert!(firm.property.property.is_empty());
+        assert!(firm.outputs().is_empty());
+        assert!(Firm::new(1, " ".into(), "".into(), FirmKind::Workshop,
+            OwnershipStructure::Private, ProfitStructure::PrivatelyOwned,
+            OrganizationalStructure::SmallBusiness).is_err());
+    }
+
+    #[test]
+    pub fn run_assigned_processes_and_plan_their_inputs() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, process) = add_hook_making(&mut data);
+        let mut firm = make_firm();
+        let mut worked = make_job(job, WageType::Daily, 0.0, Some(5));
+        worked.assignments.insert(process, AssignmentInfo::new(3.0, 0.0));
+        firm.jobs.push(worked);
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(10.0));
+        firm.property.property.insert(13, PropertyInfo::new(5.0));
+        firm.property.property.insert(12, PropertyInfo::new(3.0));
+
+        firm.plan_processes(&data);
+        let targets = firm.input_targets(&data);
+        let produced = firm.run_processes(&data);
+
+        assert_eq!(firm.property.process_plan, HashMap::from([(process, 3.0)]));
+        assert_eq!(firm.property.product_expectations,
+            HashMap::from([(TIME_PRODUCT_ID, -3.0), (13, -3.0), (9, 6.0)]));
+        assert_eq!(targets, HashMap::from([(13, 3.0), (12, 3.0)]));
+        assert_eq!(produced, HashMap::from([(9, 6.0)]));
+        assert_eq!(firm.outputs(), &[9]);
+        assert_eq!(firm.property.property[&TIME_PRODUCT_ID].total_property, 7.0);
+        assert_eq!(firm.property.property[&13].total_property, 2.0);
+        assert_eq!(firm.property.property[&12].total_property, 3.0);
+        assert_eq!(firm.property.property[&9].total_property, 6.0);
+        // capital and leftovers are kept back for tomorrow, outputs are not.
+        assert_eq!(firm.property.property[&12].available(), 0.0);
+        assert_eq!(firm.property.property[&13].available(), 0.0);
+        assert_eq!(firm.property.property[&9].available(), 6.0);
+        // open jobs do no work.
+        firm.jobs[0].pop = None;
+        firm.plan_processes(&data);
+        assert!(firm.property.process_plan.is_empty());
+        assert!(firm.run_processes(&data).is_empty());
+        // nor do empty plans, even with someone assigned to them.
+        firm.jobs[0].pop = Some(5);
+        firm.property.process_plan.insert(process, 0.0);
+        assert!(firm.run_processes(&data).is_empty());
+    }
+
+    #[test]
+    pub fn boost_outputs_by_the_skill_of_those_working() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, process) = add_hook_making(&mut data);
+        let mut firm = make_firm();
+        let mut worked = make_job(job, WageType::Daily, 0.0, Some(5));
+        worked.assignments.insert(process, AssignmentInfo::new(3.0, 0.0));
+        firm.jobs.push(worked);
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(10.0));
+        firm.property.property.insert(13, PropertyInfo::new(5.0));
+        firm.property.property.insert(12, PropertyInfo::new(3.0));
+        firm.worker_efficiency.insert(5, 0.5);
+
+        firm.plan_processes(&data);
+        let produced = firm.run_processes(&data);
+
+        // the same inputs, half again the hooks.
+        assert_eq!(produced, HashMap::from([(9, 9.0)]));
+        assert_eq!(firm.property.property[&13].total_property, 2.0);
+    }
+
+    #[test]
+    pub fn pay_each_wage_type_its_way() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, _) = add_hook_making(&mut data);
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(10.0));
+        firm.property.property.insert(12, PropertyInfo::new(1.0));
+        firm.property.property.insert(13, PropertyInfo::new(10.0));
+        firm.property.property.insert(2, PropertyInfo::new(3.0));
+        let mut daily = make_job(job, WageType::Daily, 5.0, Some(1));
+        daily.wage_unit_priority.push(2);
+        firm.jobs.push(daily);
+        firm.jobs.push(make_job(job, WageType::Slave, 5.0, Some(2)));
+        firm.ownership.push(make_job(job, WageType::ProfitSharing, 0.0, Some(3)));
+        firm.ownership.push(make_job(job, WageType::ProfitSharing, 0.0, Some(4)));
+        firm.hours_worked.insert(1, 8.0);
+        let produced = firm.run_processes(&data);
+
+        let payroll = firm.payroll(&produced, &history);
+
+        // the wage unit first, then what we make, but never time. The 
+        // owners split the 8 hooks made, less the 5 paid out.
+        assert_eq!(payroll, vec![
+            Paycheck { pop: 1, goods: vec![(2, 3.0), (9, 2.0)], wage: 5.0, period: 1 },
+            Paycheck { pop: 3, goods: vec![(9, 1.5)], wage: 1.5, period: 1 },
+            Paycheck { pop: 4, goods: vec![(9, 1.5)], wage: 1.5, period: 1 }]);
+    }
+
+    #[test]
+    pub fn pay_by_hours_output_and_salary() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, _) = add_hook_making(&mut data);
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(1.0));
+        firm.property.property.insert(12, PropertyInfo::new(1.0));
+        firm.property.property.insert(13, PropertyInfo::new(1.0));
+        firm.property.property.insert(2, PropertyInfo::new(100.0));
+        let mut contractor = make_job(99, WageType::Contractor, 4.0, Some(1));
+        contractor.wage_unit_priority.push(2);
+        firm.jobs.push(contractor);
+        let mut productive = make_job(job, WageType::Productivity, 0.5, Some(2));
+        productive.wage_unit_priority.push(2);
+        firm.jobs.push(productive);
+        let mut salaried = make_job(99, WageType::Salary, 8.0, Some(3));
+        salaried.wage_unit_priority.push(2);
+        firm.jobs.push(salaried);
+        firm.hours_worked.insert(1, 4.0);
+        firm.hours_worked.insert(3, 4.0);
+        let produced = firm.run_processes(&data);
+
+        // half a day for the contractor, 2 hooks for the productive worker.
+        firm.day = 1;
+        let payroll = firm.payroll(&produced, &history);
+        assert_eq!(payroll, vec![
+            Paycheck { pop: 1, goods: vec![(2, 2.0)], wage: 2.0, period: 1 },
+            Paycheck { pop: 2, goods: vec![(2, 1.0)], wage: 1.0, period: 1 }]);
+        // salaries wait for payday, paid on their usual hours, not today's.
+        for day in 2..SALARY_PAY_PERIOD {
+            firm.day = day;
+            assert_eq!(firm.payroll(&produced, &history).len(), 2);
+        }
+        firm.day = SALARY_PAY_PERIOD;
+        firm.hours_worked.insert(3, 8.0);
+        let payroll = firm.payroll(&produced, &history);
+        let last_day = 8.0 * (4.0 + 4.0 * SALARY_HOURS_ADJUSTMENT) / 8.0;
+        let salary = 4.0 * (SALARY_PAY_PERIOD - 1) as f64 + last_day;
+        assert_eq!(payroll[2], Paycheck { pop: 3, goods: vec![(2, salary)], 
+            wage: salary, period: SALARY_PAY_PERIOD });
+    }
+
+    #[test]
+    pub fn settle_salaries_with_those_leaving_and_forget_those_gone() {
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(2, PropertyInfo::new(100.0));
+        let mut salaried = make_job(99, WageType::Salary, 8.0, Some(3));
+        salaried.wage_unit_priority.push(2);
+        firm.jobs.push(salaried);
+        firm.hours_worked.insert(3, 8.0);
+        firm.day = 1;
+        assert!(firm.payroll(&HashMap::new(), &history).is_empty());
+        assert_eq!(firm.owed_salary(3), 8.0);
+
+        // a quarter of them leave, taking a quarter of what's owed.
+        let check = firm.settle_salary(3, 0.25, &history).expect("Nothing paid.");
+        assert_eq!(check, Paycheck { pop: 3, goods: vec![(2, 2.0)], wage: 2.0, 
+            period: 1 });
+        assert_eq!(firm.owed_salary(3), 6.0);
+        assert_eq!(firm.property.property[&2].total_property, 98.0);
+        // the rest leave, taking the rest.
+        let check = firm.settle_salary(3, 1.0, &history).expect("Nothing paid.");
+        assert_eq!(check.wage, 6.0);
+        assert_eq!(firm.owed_salary(3), 0.0);
+        assert!(firm.settle_salary(3, 1.0, &history).is_none());
+
+        // those who left without being paid are forgotten.
+        firm.day = 2;
+        firm.payroll(&HashMap::new(), &history);
+        assert_eq!(firm.owed_salary(3), 8.0);
+        firm.jobs[0].pop = None;
+        firm.payroll(&HashMap::new(), &history);
+        assert_eq!(firm.owed_salary(3), 0.0);
+    }
+
+    #[test]
+    pub fn share_losses_only_with_loss_sharers() {
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(2, PropertyInfo::new(10.0));
+        firm.ownership.push(make_job(99, WageType::LossSharing, 0.0, Some(1)));
+        firm.ownership.push(make_job(99, WageType::ProfitSharing, 0.0, Some(2)));
+        firm.profit = -10.0;
+
+        let payroll = firm.payroll(&HashMap::new(), &history);
+
+        assert_eq!(payroll, vec![
+            Paycheck { pop: 1, goods: vec![], wage: -5.0, period: 1 },
+            Paycheck { pop: 2, goods: vec![], wage: 0.0, period: 1 }]);
+    }
+
+    #[test]
+    pub fn keep_reserved_inputs_out_of_payroll() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, process) = add_hook_making(&mut data);
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(13, PropertyInfo::new(5.0));
+        let mut daily = make_job(job, WageType::Daily, 5.0, Some(1));
+        daily.wage_unit_priority.push(13);
+        daily.assignments.insert(process, AssignmentInfo::new(3.0, 0.0));
+        firm.jobs.push(daily);
+        firm.hours_worked.insert(1, 8.0);
+        firm.plan_processes(&data);
+        firm.reserve_inputs(&data);
+
+        let payroll = firm.payroll(&HashMap::new(), &history);
+
+        // 3 stone are set aside for hook making, leaving only 2 to pay.
+        assert_eq!(payroll, vec![Paycheck { pop: 1, goods: vec![(13, 2.0)],
+            wage: 2.0, period: 1 }]);
+    }
+
+    #[test]
+    pub fn adjust_prices_to_sales_and_market() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, _) = add_hook_making(&mut data);
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(1.0));
+        firm.property.property.insert(12, PropertyInfo::new(1.0));
+        firm.property.property.insert(13, PropertyInfo::new(1.0));
+        firm.jobs.push(make_job(job, WageType::Daily, 0.0, Some(1)));
+        firm.run_processes(&data);
+
+        // nothing offered, so we stay with the market.
+        firm.update_prices(&history);
+        assert_eq!(firm.price_of(9, &history), 1.0);
+        // sold out, so we raise our price, pulled back toward the market.
+        firm.prices.insert(9, 2.0);
+        firm.offered.insert(9, 2.0);
+        firm.property.property.get_mut(&9).unwrap().spent = 2.0;
+        firm.update_prices(&history);
+        let raised = 2.0 * (1.0 + FIRM_PRICE_STEP);
+        assert_eq!(firm.price_of(9, &history),
+            raised + (1.0 - raised) * FIRM_MARKET_PRICE_WEIGHT);
+        // hardly sold, so we lower it.
+        firm.prices.insert(9, 2.0);
+        firm.property.property.get_mut(&9).unwrap().spent = 0.0;
+        firm.update_prices(&history);
+        let lowered = 2.0 * (1.0 - FIRM_PRICE_STEP);
+        assert_eq!(firm.price_of(9, &history),
+            lowered + (1.0 - lowered) * FIRM_MARKET_PRICE_WEIGHT);
+    }
+
+    #[test]
+    pub fn collect_time_then_pay_and_close_out() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let demos = Demographics::new();
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.property.property.insert(2, PropertyInfo::new(10.0));
+        // no processes, so all it does is collect time and pay.
+        let mut daily = make_job(99, WageType::Daily, 4.0, Some(7));
+        daily.wage_unit_priority.push(2);
+        firm.jobs.push(daily);
+        let firm_info = firm.actor_info();
+        let pop_info = ActorInfo::Pop(7);
+        let (tx, rx) = barrage::bounded(10);
+        let mut passed_tx = tx.clone();
+        let mut passed_rx = rx.clone();
+
+        let handle = thread::spawn(move || {
+            firm.run_market_day(&mut passed_tx, &mut passed_rx, &data,
+                &demos, &history);
+            firm
+        });
+        tx.send(ActorMessage::StartDay).unwrap();
+        // read what the firm sends, skipping what we send.
+        let next = || loop {
+            let msg = rx.recv().unwrap();
+            match msg {
+                ActorMessage::FirmToEmployee { .. } |
+                ActorMessage::Finished { .. } => return msg,
+                ActorMessage::SendProduct { sender, .. }
+                if sender == firm_info => return msg,
+                _ => (),
+            }
+        };
+
+        assert_eq!(next(), ActorMessage::FirmToEmployee { firm: firm_info,
+            employee: pop_info, action: FirmEmployeeAction::RequestTime });
+        tx.send(ActorMessage::SendProduct { sender: pop_info,
+            reciever: firm_info, product: TIME_PRODUCT_ID, amount: 8.0 }).unwrap();
+        assert_eq!(next(), ActorMessage::FirmToEmployee { firm: firm_info,
+            employee: pop_info, action: FirmEmployeeAction::WorkDayEnded });
+        assert_eq!(next(), ActorMessage::SendProduct { sender: firm_info,
+            reciever: pop_info, product: 2, amount: 4.0 });
+        assert_eq!(next(), ActorMessage::FirmToEmployee { firm: firm_info,
+            employee: pop_info, action: FirmEmployeeAction::PayStub { wage: 4.0,
+                period: 1 } });
+        assert_eq!(next(), ActorMessage::Finished { sender: firm_info });
+        tx.send(ActorMessage::AllFinished).unwrap();
+        let firm = handle.join().unwrap();
+
+        assert_eq!(firm.property.property[&TIME_PRODUCT_ID].total_property, 8.0);
+        assert_eq!(firm.property.property[&2].total_property, 6.0);
+        assert_eq!(firm.hours_worked[&7], 8.0);
+        assert_eq!(firm.profit, -4.0);
+        assert_eq!(firm.profitability, -4.0);
+    }
+
+    #[test]
+    pub fn hand_over_goods_when_a_resting_ask_is_filled() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let demos = Demographics::new();
+        let mut history = make_history();
+        history.exchange_mode = ExchangeMode::OrderBook { currency: 2 };
+        let mut firm = make_firm();
+        firm.recieve_shipment(9, 5.0);
+        let firm_info = firm.actor_info();
+        let (tx, rx) = barrage::bounded(10);
+        let mut passed_tx = tx.clone();
+        let mut passed_rx = rx.clone();
+
+        let handle = thread::spawn(move || {
+            firm.run_market_day(&mut passed_tx, &mut passed_rx, &data,
+                &demos, &history);
+            firm
+        });
+        tx.send(ActorMessage::StartDay).unwrap();
+        let next = || loop {
+            let msg = rx.recv().unwrap();
+            match msg {
+                ActorMessage::SellOrder { .. } |
+                ActorMessage::Finished { .. } => return msg,
+                _ => (),
+            }
+        };
+        assert_eq!(next(), ActorMessage::SellOrder { sender: firm_info, 
+            product: 9, quantity: 5.0, amv: 1.0 });
+        assert_eq!(next(), ActorMessage::Finished { sender: firm_info });
+        tx.send(ActorMessage::OrderFilled { buyer: ActorInfo::Pop(3), 
+            seller: firm_info, product: 9, quantity: 2.0, currency: 2, 
+            price: 1.5 }).unwrap();
+        tx.send(ActorMessage::AllFinished).unwrap();
+        let firm = handle.join().unwrap();
+
+        assert_eq!(firm.property.property[&9].total_property, 3.0);
+        assert_eq!(firm.property.property[&9].spent, 2.0);
+        assert_eq!(firm.property.property[&2].total_property, 3.0);
+        assert_eq!(firm.profit, 3.0);
+    }
+
+    #[test]
+    pub fn hand_back_what_is_left_to_loss_sharers() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let demos = Demographics::new();
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.ownership.push(make_job(99, WageType::LossSharing, 0.0, Some(7)));
+        let firm_info = firm.actor_info();
+        let pop_info = ActorInfo::Pop(7);
+        let (tx, rx) = barrage::bounded(20);
+        let mut passed_tx = tx.clone();
+        let mut passed_rx = rx.clone();
+
+        let handle = thread::spawn(move || {
+            firm.run_market_day(&mut passed_tx, &mut passed_rx, &data,
+                &demos, &history);
+            firm
+        });
+        tx.send(ActorMessage::StartDay).unwrap();
+        let next = || loop {
+            let msg = rx.recv().unwrap();
+            match msg {
+                ActorMessage::FirmToEmployee { .. } |
+                ActorMessage::Finished { .. } => return msg,
+                ActorMessage::SendProduct { sender, .. } |
+                ActorMessage::SendWant { sender, .. }
+                if sender == firm_info => return msg,
+                _ => (),
+            }
+        };
+
+        assert_eq!(next(), ActorMessage::FirmToEmployee { firm: firm_info,
+            employee: pop_info, action: FirmEmployeeAction::RequestEverything });
+        tx.send(ActorMessage::SendProduct { sender: pop_info,
+            reciever: firm_info, product: TIME_PRODUCT_ID, amount: 8.0 }).unwrap();
+        tx.send(ActorMessage::SendProduct { sender: pop_info,
+            reciever: firm_info, product: 2, amount: 10.0 }).unwrap();
+        tx.send(ActorMessage::SendWant { sender: pop_info,
+            reciever: firm_info, want: 1, amount: 3.0 }).unwrap();
+        tx.send(ActorMessage::EmployeeToFirm { employee: pop_info, 
+            firm: firm_info, action: FirmEmployeeAction::RequestSent }).unwrap();
+        assert_eq!(next(), ActorMessage::FirmToEmployee { firm: firm_info,
+            employee: pop_info, action: FirmEmployeeAction::WorkDayEnded });
+        assert_eq!(next(), ActorMessage::FirmToEmployee { firm: firm_info,
+            employee: pop_info, action: FirmEmployeeAction::PayStub { wage: 0.0,
+                period: 1 } });
+        // nothing was used, so they get all of their savings back.
+        assert_eq!(next(), ActorMessage::SendProduct { sender: firm_info,
+            reciever: pop_info, product: 2, amount: 10.0 });
+        assert_eq!(next(), ActorMessage::SendWant { sender: firm_info,
+            reciever: pop_info, want: 1, amount: 3.0 });
+        assert_eq!(next(), ActorMessage::Finished { sender: firm_info });
+        tx.send(ActorMessage::AllFinished).unwrap();
+        let firm = handle.join().unwrap();
+
+        assert_eq!(firm.property.property[&2].total_property, 0.0);
+        assert_eq!(firm.hours_worked[&7], 8.0);
+        assert!(firm.contributions.is_empty());
+        // handing back their savings isn't a loss.
+        assert_eq!(firm.profitability, 0.0);
+    }
+
+    #[test]
+    pub fn hire_and_lay_off_by_worth_gained_not_sales() {
+        let history = make_history();
+        let mut firm = make_firm();
+        firm.jobs.push(make_job(99, WageType::Daily, 4.0, Some(7)));
+        firm.jobs.push(make_job(99, WageType::Daily, 4.0, None));
+        firm.property.property.insert(9, PropertyInfo::new(6.0));
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(8.0));
+        // the hooks we made and kept are worth something, time isn't.
+        assert_eq!(firm.holdings_value(&history), 6.0);
+
+        // nothing sold, but what we made is worth more than we paid.
+        firm.profit = -4.0;
+        firm.profitability = 2.0;
+        assert_eq!(firm.openings().len(), 1);
+        assert!(firm.layoffs().is_empty());
+
+        // losing worth, we stop hiring and let people go.
+        firm.profit = 4.0;
+        firm.profitability = -1.0;
+        assert!(firm.openings().is_empty());
+        assert_eq!(firm.layoffs(), vec![(0, 7, WageType::Daily.layoff_fraction())]);
+    }
+
+    #[test]
+    pub fn salesman_outputs_and_hear_out_salesmen_once_idle() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let (job, process) = add_hook_making(&mut data);
+        let history = make_history();
+        let mut firm = make_firm();
+        let mut worked = make_job(job, WageType::Daily, 0.0, Some(5));
+        worked.assignments.insert(process, AssignmentInfo::new(3.0, 0.0));
+        firm.jobs.push(worked);
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(10.0));
+        firm.property.property.insert(13, PropertyInfo::new(5.0));
+        firm.property.property.insert(12, PropertyInfo::new(3.0));
+        // make 6 hooks to sell, leaving 2 of the 3 stone we want for tomorrow.
+        firm.plan_processes(&data);
+        firm.run_processes(&data);
+        let firm_info = firm.actor_info();
+        let buyer = ActorInfo::Pop(1);
+        let salesman = ActorInfo::Pop(3);
+        let (tx, rx) = barrage::bounded(10);
+        let passed_tx = tx.clone();
+        let mut passed_rx = rx.clone();
+
+        let handle = thread::spawn(move || {
+            firm.salesmanning(&mut passed_rx, &passed_tx, &data, &history);
+            let salesmen = firm.salesmen.clone();
+            firm.hear_out_salesmen(&mut passed_rx, &passed_tx, &data, &history);
+            (firm, salesmen)
+        });
+        // read what the firm sends, skipping what we send.
+        let next = || loop {
+            let msg = rx.recv().unwrap();
+            match msg {
+                ActorMessage::FindBuyer { sender, .. } if sender == firm_info => return msg,
+                ActorMessage::InStock { seller, .. } if seller == firm_info => return msg,
+                ActorMessage::BuyOffer { buyer, .. } if buyer == firm_info => return msg,
+                _ => (),
+            }
+        };
+
+        assert_eq!(next(), ActorMessage::FindBuyer { product: 9, sender: firm_info });
+        // a salesman comes to us while we're out selling.
+        tx.send(ActorMessage::FoundBuyer { seller: salesman, buyer: firm_info,
+            product: 13 }).unwrap();
+        tx.send(ActorMessage::FoundBuyer { seller: firm_info, buyer,
+            product: 9 }).unwrap();
+        assert_eq!(next(), ActorMessage::InStock { buyer, seller: firm_info,
+            product: 9, price: 1.0, quantity: 6.0 });
+        tx.send(ActorMessage::RejectPurchase { buyer, seller: firm_info, product: 9,
+            price_opinion: OfferResult::TooExpensive }).unwrap();
+        // once done selling, we hear them out, only buying the stone we're missing.
+        tx.send(ActorMessage::InStock { buyer: firm_info, seller: salesman,
+            product: 13, price: 1.0, quantity: 10.0 }).unwrap();
+        if let ActorMessage::BuyOffer { seller, product, quantity, .. } = next() {
+            assert_eq!(seller, salesman);
+            assert_eq!(product, 13);
+            assert_eq!(quantity, 1.0);
+        } else {
+            assert!(false, "BuyOffer not recieved.");
+        }
+        tx.send(ActorMessage::RejectOffer { buyer: firm_info, seller: salesman,
+            product: 13 }).unwrap();
+        let (firm, salesmen) = handle.join().unwrap();
+
+        assert_eq!(salesmen, VecDeque::from([(salesman, 13)]));
+        assert!(firm.salesmen.is_empty());
+        assert_eq!(firm.property.property[&9].total_property, 6.0);
+    }
+
+    fn make_merchant() -> Firm {
+        let mut firm = Firm::new(0, "Merchant".into(), "".into(), FirmKind::Merchant,
+            OwnershipStructure::Private, ProfitStructure::PrivatelyOwned,
+            OrganizationalStructure::SmallBusiness).expect("Messed up new.");
+        firm.property.property.insert(TIME_PRODUCT_ID, PropertyInfo::new(10.0));
+        firm.property.property.insert(9, PropertyInfo::new(4.0));
+        firm
+    }
+
+    #[test]
+    pub fn ship_goods_worth_more_next_door_paying_for_the_trip() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let mut history = make_history();
+        history.neighbors.insert(1, MarketConnection::Land(10.0));
+        let unit_cost = MarketConnection::Land(10.0)
+            .transport_cost(&data.products[&9], 1.0).unwrap();
+        let mut firm = make_merchant();
+        let firm_info = firm.actor_info();
+        let (tx, rx) = barrage::bounded(10);
+        let passed_tx = tx.clone();
+        let mut passed_rx = rx.clone();
+
+        let handle = thread::spawn(move || {
+            firm.ship_to_neighbors(&mut passed_rx, &passed_tx, &data, &history);
+            firm
+        });
+        assert_eq!(rx.recv().unwrap(), ActorMessage::QueryPrice { sender: firm_info,
+            market: 1, product: 9 });
+        tx.send(ActorMessage::PriceInfo { reciever: firm_info, market: 1, product: 9,
+            price: Some(2.0 + unit_cost) }).unwrap();
+        // skip what we sent.
+        rx.recv().unwrap();
+        // they already have all but 3 of what we could carry.
+        let carried = (10.0 / unit_cost).floor();
+        assert_eq!(rx.recv().unwrap(), ActorMessage::RequestTrade { sender: firm_info,
+            market: 1, product: 9, quantity: carried });
+        tx.send(ActorMessage::TradeInfo { reciever: firm_info, market: 1, product: 9,
+            available: carried - 3.0, price: Some(2.0 + unit_cost) }).unwrap();
+        rx.recv().unwrap();
+        assert_eq!(rx.recv().unwrap(), ActorMessage::ShipGoods { sender: firm_info,
+            destination: 1, product: 9, quantity: 3.0 });
+        tx.send(ActorMessage::ShipmentSent { sender: firm_info, destination: 1,
+            product: 9, quantity: 3.0, cost: 3.0 * unit_cost }).unwrap();
+        let mut firm = handle.join().unwrap();
+
+        assert_eq!(firm.property.property[&9].total_property, 1.0);
+        assert_eq!(firm.property.property[&TIME_PRODUCT_ID].total_property,
+            10.0 - 3.0 * unit_cost);
+        // once they arrive, they're ours to sell.
+        firm.recieve_shipment(9, 3.0);
+        assert_eq!(firm.property.property[&9].total_property, 4.0);
+        assert_eq!(firm.outputs(), &[9]);
+    }
+
+    #[test]
+    pub fn keep_goods_not_worth_the_trip_or_refused() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let mut history = make_history();
+        history.neighbors.insert(1, MarketConnection::Land(10.0));
+        history.neighbors.insert(2, MarketConnection::Land(10.0));
+        let mut firm = make_merchant();
+        let firm_info = firm.actor_info();
+        let (tx, rx) = barrage::bounded(10);
+        let passed_tx = tx.clone();
+        let mut passed_rx = rx.clone();
+
+        let handle = thread::spawn(move || {
+            firm.ship_to_neighbors(&mut passed_rx, &passed_tx, &data, &history);
+            firm
+        });
+        // market 1 pays no more than we do.
+        assert_eq!(rx.recv().unwrap(), ActorMessage::QueryPrice { sender: firm_info,
+            market: 1, product: 9 });
+        tx.send(ActorMessage::PriceInfo { reciever: firm_info, market: 1, product: 9,
+            price: Some(1.0) }).unwrap();
+        rx.recv().unwrap();
+        // market 2 pays more, but won't send it.
+        assert_eq!(rx.recv().unwrap(), ActorMessage::QueryPrice { sender: firm_info,
+            market: 2, product: 9 });
+        tx.send(ActorMessage::PriceInfo { reciever: firm_info, market: 2, product: 9,
+            price: Some(100.0) }).unwrap();
+        rx.recv().unwrap();
+        if let ActorMessage::RequestTrade { market, .. } = rx.recv().unwrap() {
+            assert_eq!(market, 2);
+        } else {
+            assert!(false, "RequestTrade not recieved.");
+        }
+        tx.send(ActorMessage::TradeInfo { reciever: firm_info, market: 2, product: 9,
+            available: 0.0, price: Some(100.0) }).unwrap();
+        rx.recv().unwrap();
+        if let ActorMessage::ShipGoods { destination, quantity, .. } = rx.recv().unwrap() {
+            assert_eq!(destination, 2);
+            assert_eq!(quantity, 4.0);
+        } else {
+            assert!(false, "ShipGoods not recieved.");
+        }
+        tx.send(ActorMessage::ShipmentRefused { sender: firm_info, destination: 2,
+            product: 9 }).unwrap();
+        let firm = handle.join().unwrap();
+
+        assert_eq!(firm.property.property[&9].total_property, 4.0);
+        assert_eq!(firm.property.property[&TIME_PRODUCT_ID].total_property, 10.0);
+    }
+
+    #[test]
+    pub fn let_guilds_and_franchises_set_their_childrens_prices() {
+        let history = make_history();
+        let mut parent = Firm::new(0, "Franchise".into(), "".into(), FirmKind::Workshop,
+            OwnershipStructure::Private, ProfitStructure::PrivatelyOwned,
+            OrganizationalStructure::Franchise).expect("Messed up new.");
+        parent.children.push(1);
+        parent.prices.insert(9, 5.0);
+
+        assert_eq!(parent.price_for_child(1, 9),
+            FirmHierarchyAction::SetPrice { product: 9, price: 5.0 });
+        // not ours, or nothing to set it to.
+        assert_eq!(parent.price_for_child(2, 9), FirmHierarchyAction::Acknowledge);
+        assert_eq!(parent.price_for_child(1, 8), FirmHierarchyAction::Acknowledge);
+        // small businesses leave it to the child.
+        let mut small = make_firm();
+        small.children.push(1);
+        small.prices.insert(9, 5.0);
+        assert_eq!(small.price_for_child(1, 9), FirmHierarchyAction::Acknowledge);
+
+        // the child reports what it sells, and keeps to the price it's set.
+        let mut child = make_firm();
+        child.id = 1;
+        child.parent = Some(0);
+        child.recieve_shipment(9, 2.0);
+        let (tx, rx) = barrage::bounded(10);
+        child.report_to_parent(&rx, &tx, &history);
+        assert_eq!(rx.recv().unwrap(), ActorMessage::FirmToFirm { sender: 1, reciever: 0,
+            action: FirmHierarchyAction::Report { product: 9, price: 1.0 } });
+        child.parent_prices.insert(9, 5.0);
+        child.update_prices(&history);
+        assert_eq!(child.price_of(9, &history), 5.0);
+    }
+}
@@ -0,0 +1,73 @@
+mod ideology_appeal_tests {
+    use std::collections::VecDeque;
+
+    use political_economy_core::{
+        constants::MAX_IDEOLOGY_SPREAD_FRACTION,
+        objects::{
+            actor_objects::{pop::Pop, property::{Property, TieredValue}},
+            demographic_objects::{
+                ideology_spread::IdeologyAppeal,
+                pop_breakdown_table::{PBRow, PopBreakdownTable}
+            }
+        }
+    };
+
+    fn make_pop(id: usize, rows: Vec<(Option<usize>, usize)>, satisfaction: f64) -> Pop {
+        let mut result = Pop {
+            id,
+            job: 0,
+            firm: None,
+            owner: None,
+            market: 0,
+            skill: 0,
+            lower_skill_level: 0.0,
+            higher_skill_level: 0.0,
+            property: Property::new(vec![]),
+            breakdown_table: PopBreakdownTable { table: vec![], total: 0 },
+            is_selling: true,
+            mood: 0.0,
+            current_sat: TieredValue { tier: 0, value: 0.0 },
+            prev_sat: TieredValue { tier: 0, value: 0.0 },
+            hypo_change: TieredValue { tier: 0, value: 0.0 },
+            clutches: vec![],
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new(),
+        };
+        for (ideology, count) in rows {
+            result.breakdown_table.insert_pops(PBRow::new(0, None, None,
+                None, None, None, ideology, None, None, count));
+        }
+        result.property.market_satisfaction = satisfaction;
+        result
+    }
+
+    #[test]
+    pub fn share_and_satisfaction_by_ideology() {
+        let pops = vec![make_pop(0, vec![(None, 10), (Some(0), 10)], 100.0),
+            make_pop(1, vec![(Some(0), 20)], 400.0)];
+
+        let result = IdeologyAppeal::of_pops(pops.iter());
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[&None].share, 0.25);
+        assert_eq!(result[&None].satisfaction, 5.0);
+        assert_eq!(result[&Some(0)].share, 0.75);
+        // 10 at 5 and 20 at 20.
+        assert_eq!(result[&Some(0)].satisfaction, 15.0);
+    }
+
+    #[test]
+    pub fn only_convert_to_better_off_ideologies() {
+        let current = IdeologyAppeal { share: 0.5, satisfaction: 10.0 };
+        let worse = IdeologyAppeal { share: 0.5, satisfaction: 5.0 };
+        let slightly_better = IdeologyAppeal { share: 0.1, satisfaction: 11.0 };
+        let much_better = IdeologyAppeal { share: 0.5, satisfaction: 100.0 };
+
+        assert_eq!(current.conversion_fraction(&worse), 0.0);
+        // 0.05 * 0.1 * 1 / 11
+        let fraction = current.conversion_fraction(&slightly_better);
+        assert!(fraction > 0.0 && fraction < MAX_IDEOLOGY_SPREAD_FRACTION);
+        assert_eq!(current.conversion_fraction(&much_better), 
+            MAX_IDEOLOGY_SPREAD_FRACTION);
+    }
+}
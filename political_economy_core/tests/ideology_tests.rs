@@ -0,0 +1,63 @@
+mod ideology_tests {
+    use political_economy_core::objects::{
+        actor_objects::desire::Desire,
+        data_objects::item::Item,
+        demographic_objects::ideology::{Faction, Ideology, IdeologyWave}
+    };
+
+    fn books(amount: f64) -> Desire {
+        Desire { item: Item::Product(8), start: 20, end: None, amount,
+            satisfaction: 0.0, step: 5, tags: vec![] }
+    }
+
+    fn banners(amount: f64) -> Desire {
+        Desire { item: Item::Product(9), start: 25, end: None, amount,
+            satisfaction: 0.0, step: 5, tags: vec![] }
+    }
+
+    fn make_ideology() -> Ideology {
+        let mut test = Ideology::new(0, "Ideology".into(), "".into(),
+            0.0, 0.0, 1.0, vec![books(1.0)], vec![])
+            .expect("Messed up new.");
+        test.add_wave(IdeologyWave::new("First".into(), vec![])
+            .expect("Messed up new."));
+        test.add_wave(IdeologyWave::new("Second".into(), vec![books(-0.5)])
+            .expect("Messed up new."));
+        test.add_faction(Faction::new("Moderate".into(), 3.0, vec![])
+            .expect("Messed up new."));
+        test.add_faction(Faction::new("Radical".into(), 1.0, vec![banners(1.0)])
+            .expect("Messed up new."));
+        test
+    }
+
+    #[test]
+    pub fn build_data_table_for_every_combination() {
+        let test = make_ideology();
+
+        assert_eq!(test.data_table.len(), 9);
+        let base = test.get_subentry(None, None).unwrap();
+        assert_eq!(base.desires.len(), 1);
+
+        let second_radical = test.get_subentry(Some(1), Some(1)).unwrap();
+        assert_eq!(second_radical.desires.len(), 2);
+        assert_eq!(second_radical.desires.iter()
+            .find(|x| x.item == Item::Product(8)).unwrap().amount, 0.5);
+
+        assert!(test.get_subentry(Some(2), None).is_err());
+        assert!(test.get_subentry(None, Some(2)).is_err());
+    }
+
+    #[test]
+    pub fn bring_converts_into_latest_wave_and_divide_by_faction() {
+        let test = make_ideology();
+
+        assert_eq!(test.current_wave, Some(1));
+        let factions = test.convert_factions();
+        assert_eq!(factions, vec![(Some(0), 0.75), (Some(1), 0.25)]);
+
+        let empty = Ideology::new(1, "Empty".into(), "".into(),
+            0.0, 0.0, 1.0, vec![], vec![]).expect("Messed up new.");
+        assert_eq!(empty.current_wave, None);
+        assert_eq!(empty.convert_factions(), vec![(None, 1.0)]);
+    }
+}
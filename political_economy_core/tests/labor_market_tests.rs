@@ -0,0 +1,44 @@
+mod labor_market_tests {
+    use std::collections::HashMap;
+
+    use political_economy_core::{
+        constants::LAYOFF_FRACTION,
+        objects::{
+            actor_objects::firm_job::{FirmJob, WageType},
+            environmental_objects::labor_market::JobOpening
+        }
+    };
+
+    fn make_job(job: usize, wage_type: WageType, wage: f64, 
+    pop: Option<usize>) -> FirmJob {
+        FirmJob { job, wage_type, wage, wage_unit_priority: vec![],
+            assignments: HashMap::new(), pop }
+    }
+
+    #[test]
+    pub fn post_open_jobs_best_paying_first() {
+        let jobs = vec![make_job(0, WageType::Daily, 5.0, None),
+            make_job(1, WageType::Salary, 20.0, Some(3)),
+            make_job(2, WageType::Contractor, 10.0, None)];
+
+        let result = JobOpening::of_jobs(7, &jobs);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0], JobOpening { firm: 7, index: 2, job: 2, 
+            wage_type: WageType::Contractor, wage: 10.0 });
+        assert_eq!(result[1].index, 0);
+        assert_eq!(result[1].wage, 5.0);
+    }
+
+    #[test]
+    pub fn keep_slaves_and_owners_from_leaving_or_being_let_go() {
+        assert!(!WageType::Slave.can_change_jobs());
+        assert!(WageType::Daily.can_change_jobs());
+
+        assert_eq!(WageType::Slave.layoff_fraction(), 0.0);
+        assert_eq!(WageType::ProfitSharing.layoff_fraction(), 0.0);
+        assert_eq!(WageType::LossSharing.layoff_fraction(), 0.0);
+        assert_eq!(WageType::Salary.layoff_fraction(), LAYOFF_FRACTION);
+        assert_eq!(WageType::Contractor.layoff_fraction(), LAYOFF_FRACTION * 2.0);
+    }
+}
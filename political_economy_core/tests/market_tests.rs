@@ -0,0 +1,47 @@
+mod auction_record_tests {
+    use political_economy_core::objects::{
+        actor_objects::actor_message::{ActorInfo, AuctionKind},
+        environmental_objects::market::AuctionRecord
+    };
+
+    #[test]
+    pub fn pick_highest_bid_meeting_reserve_for_auction() {
+        let mut test = AuctionRecord::new(0, ActorInfo::Pop(0),
+            AuctionKind::Auction, 1, 10.0, 2, 5.0);
+
+        assert_eq!(test.winning_bid(), None);
+
+        test.add_bid(ActorInfo::Pop(1), 4.0);
+        assert_eq!(test.winning_bid(), None);
+
+        test.add_bid(ActorInfo::Pop(2), 6.0);
+        test.add_bid(ActorInfo::Pop(3), 7.0);
+        test.add_bid(ActorInfo::Pop(4), 7.0);
+        assert_eq!(test.winning_bid(), Some((ActorInfo::Pop(3), 7.0)));
+    }
+
+    #[test]
+    pub fn pick_lowest_bid_meeting_reserve_for_reverse_auction() {
+        let mut test = AuctionRecord::new(0, ActorInfo::Pop(0),
+            AuctionKind::ReverseAuction, 1, 10.0, 2, 5.0);
+
+        test.add_bid(ActorInfo::Pop(1), 6.0);
+        assert_eq!(test.winning_bid(), None);
+
+        test.add_bid(ActorInfo::Pop(2), 4.0);
+        test.add_bid(ActorInfo::Pop(3), 3.0);
+        test.add_bid(ActorInfo::Pop(4), 3.0);
+        assert_eq!(test.winning_bid(), Some((ActorInfo::Pop(3), 3.0)));
+    }
+
+    #[test]
+    pub fn ignore_bids_from_the_organizer() {
+        let mut test = AuctionRecord::new(0, ActorInfo::Pop(0),
+            AuctionKind::Auction, 1, 10.0, 2, 5.0);
+
+        test.add_bid(ActorInfo::Pop(0), 100.0);
+
+        assert!(test.bids.is_empty());
+        assert_eq!(test.winning_bid(), None);
+    }
+}
@@ -0,0 +1,85 @@
+mod market_appeal_tests {
+    use std::collections::VecDeque;
+
+    use political_economy_core::objects::{
+        actor_objects::{pop::Pop, property::{Property, TieredValue}},
+        demographic_objects::pop_breakdown_table::{PBRow, PopBreakdownTable},
+        environmental_objects::{market::MarketConnection, migration::MarketAppeal}
+    };
+
+    fn make_pop(id: usize, count: usize, satisfaction: f64, wage: f64) -> Pop {
+        let mut result = Pop {
+            id,
+            job: 0,
+            firm: None,
+            owner: None,
+            market: 0,
+            skill: 0,
+            lower_skill_level: 0.0,
+            higher_skill_level: 0.0,
+            property: Property::new(vec![]),
+            breakdown_table: PopBreakdownTable { table: vec![], total: 0 },
+            is_selling: true,
+            mood: 0.0,
+            current_sat: TieredValue { tier: 0, value: 0.0 },
+            prev_sat: TieredValue { tier: 0, value: 0.0 },
+            hypo_change: TieredValue { tier: 0, value: 0.0 },
+            clutches: vec![],
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new(),
+        };
+        result.breakdown_table.insert_pops(PBRow::new(0, None, None,
+            None, None, None, None, None, None, count));
+        result.property.market_satisfaction = satisfaction;
+        result.property.wage_estimate = wage;
+        result
+    }
+
+    #[test]
+    pub fn average_appeal_per_person() {
+        let pops = vec![make_pop(0, 10, 100.0, 50.0),
+            make_pop(1, 30, 100.0, 150.0)];
+
+        let result = MarketAppeal::of_pops(pops.iter());
+
+        assert_eq!(result.satisfaction, 5.0);
+        assert_eq!(result.wage, 5.0);
+        assert_eq!(result.value(), 10.0);
+
+        let empty = MarketAppeal::of_pops(vec![].iter());
+        assert_eq!(empty, MarketAppeal::default());
+    }
+
+    #[test]
+    pub fn not_migrate_to_worse_or_too_distant_markets() {
+        let current = MarketAppeal { satisfaction: 10.0, wage: 0.0 };
+        let worse = MarketAppeal { satisfaction: 5.0, wage: 0.0 };
+        let better = MarketAppeal { satisfaction: 12.0, wage: 0.0 };
+
+        assert_eq!(current.migration_fraction(&worse, 
+            &MarketConnection::Land(30.0)), 0.0);
+        // 2 better, but 3 days travel costs more.
+        assert_eq!(current.migration_fraction(&better, 
+            &MarketConnection::Land(90.0)), 0.0);
+    }
+
+    #[test]
+    pub fn migrate_more_for_bigger_gains_up_to_the_limit() {
+        let current = MarketAppeal { satisfaction: 10.0, wage: 0.0 };
+        let better = MarketAppeal { satisfaction: 15.0, wage: 0.0 };
+        let best = MarketAppeal { satisfaction: 200.0, wage: 100.0 };
+
+        // gain of 5 - 1 day travel = 4, 0.1 * 4 / 14
+        let result = current.migration_fraction(&better, 
+            &MarketConnection::Land(30.0));
+        assert!((result - 0.4 / 14.0).abs() < 0.00001);
+        // portals are free to travel through.
+        let result = current.migration_fraction(&better, 
+            &MarketConnection::Portal(1.0, 1.0, 1.0));
+        assert!((result - 0.5 / 15.0).abs() < 0.00001);
+        // capped
+        let result = current.migration_fraction(&best, 
+            &MarketConnection::Land(30.0));
+        assert_eq!(result, 0.05);
+    }
+}
@@ -0,0 +1,95 @@
+mod movement_tests {
+    use political_economy_core::{
+        constants::{MOVEMENT_ESCALATION_DAYS, MOVEMENT_FORMATION_DAYS},
+        demographics::Demographics,
+        objects::demographic_objects::movement::{Discontent, MovementDemand, 
+            MovementEscalation}
+    };
+
+    fn discontent(market: usize, unsatisfied: usize) -> Discontent {
+        Discontent { culture: Some(0), ideology: Some(1), market, unsatisfied,
+            total: 10, firms: vec![5], states: vec![7] }
+    }
+
+    #[test]
+    pub fn escalate_and_deescalate_in_steps() {
+        assert_eq!(MovementEscalation::Petition.escalate(), MovementEscalation::Protest);
+        assert_eq!(MovementEscalation::Revolt.escalate(), MovementEscalation::Revolt);
+        assert_eq!(MovementEscalation::Strike.deescalate(), 
+            Some(MovementEscalation::Protest));
+        assert_eq!(MovementEscalation::Petition.deescalate(), None);
+    }
+
+    #[test]
+    pub fn form_movement_from_persistent_discontent() {
+        let mut test = Demographics::new();
+        let reports = vec![discontent(0, 8), discontent(1, 4)];
+
+        for _ in 1..MOVEMENT_FORMATION_DAYS {
+            test.update_movements(&reports);
+        }
+        assert!(test.movements.is_empty());
+        assert_eq!(test.grievances[&(Some(0), Some(1))], MOVEMENT_FORMATION_DAYS - 1);
+
+        test.update_movements(&reports);
+
+        assert_eq!(test.movements.len(), 1);
+        let movement = test.movements.values().next().unwrap();
+        assert_eq!(movement.escalation, MovementEscalation::Petition);
+        assert_eq!(movement.total_support(), 12);
+        assert_eq!(movement.support[&1], 4);
+        assert_eq!(movement.demands, vec![MovementDemand::Wages { firm: 5 },
+            MovementDemand::Prices { state: 7 }, MovementDemand::Policy { state: 7 }]);
+
+        // groups sharing nothing don't organize.
+        let mut test = Demographics::new();
+        let mut report = discontent(0, 10);
+        report.culture = None;
+        report.ideology = None;
+        test.update_movements(&[report]);
+        assert!(test.grievances.is_empty());
+    }
+
+    #[test]
+    pub fn escalate_while_unanswered_and_dissolve_once_content() {
+        let mut test = Demographics::new();
+        let unhappy = vec![discontent(0, 8)];
+        let content = vec![discontent(0, 2)];
+        for _ in 0..(MOVEMENT_FORMATION_DAYS + MOVEMENT_ESCALATION_DAYS) {
+            test.update_movements(&unhappy);
+        }
+        let movement = test.movements.values().next().unwrap();
+        assert_eq!(movement.escalation, MovementEscalation::Protest);
+
+        test.update_movements(&content);
+        assert!(test.grievances.is_empty());
+        let movement = test.movements.values().next().unwrap();
+        assert_eq!(movement.escalation, MovementEscalation::Petition);
+        assert_eq!(movement.total_support(), 2);
+
+        test.update_movements(&content);
+        assert!(test.movements.is_empty());
+
+        // a new movement doesn't take the old one's id.
+        for _ in 0..MOVEMENT_FORMATION_DAYS {
+            test.update_movements(&unhappy);
+        }
+        assert_eq!(test.movements.keys().copied().collect::<Vec<usize>>(), vec![1]);
+    }
+
+    #[test]
+    pub fn drop_demands_once_answered() {
+        let mut test = Demographics::new();
+        let mut reports = vec![discontent(0, 8)];
+        for _ in 0..MOVEMENT_FORMATION_DAYS {
+            test.update_movements(&reports);
+        }
+        // the firm is no longer a problem, another is.
+        reports[0].firms = vec![6];
+        test.update_movements(&reports);
+
+        let movement = test.movements.values().next().unwrap();
+        assert_eq!(movement.demands, vec![MovementDemand::Wages { firm: 6 },
+            MovementDemand::Prices { state: 7 }, MovementDemand::Policy { state: 7 }]);
+    }
+}
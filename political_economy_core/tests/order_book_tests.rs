@@ -0,0 +1,105 @@
+mod order_book_tests {
+    use political_economy_core::objects::{
+        actor_objects::actor_message::ActorInfo,
+        environmental_objects::order_book::{OrderBook, Trade}
+    };
+
+    #[test]
+    pub fn rest_orders_which_do_not_cross() {
+        let mut test = OrderBook::new();
+
+        let trades = test.submit_ask(ActorInfo::Pop(0), 1, 10.0, 2.0);
+        assert!(trades.is_empty());
+        let trades = test.submit_bid(ActorInfo::Pop(1), 1, 5.0, 1.5);
+        assert!(trades.is_empty());
+
+        assert_eq!(test.best_ask(1), Some(2.0));
+        assert_eq!(test.best_bid(1), Some(1.5));
+        assert_eq!(test.ask_depth(1), 10.0);
+        assert_eq!(test.bid_depth(1), 5.0);
+        assert_eq!(test.best_ask(2), None);
+    }
+
+    #[test]
+    pub fn fill_bid_from_cheapest_asks_first_at_ask_price() {
+        let mut test = OrderBook::new();
+
+        test.submit_ask(ActorInfo::Pop(0), 1, 3.0, 2.0);
+        test.submit_ask(ActorInfo::Pop(1), 1, 3.0, 1.0);
+        test.submit_ask(ActorInfo::Firm(2), 1, 3.0, 5.0);
+
+        let trades = test.submit_bid(ActorInfo::Pop(3), 1, 5.0, 3.0);
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0], Trade { buyer: ActorInfo::Pop(3),
+            seller: ActorInfo::Pop(1), product: 1, quantity: 3.0, price: 1.0 });
+        assert_eq!(trades[1], Trade { buyer: ActorInfo::Pop(3),
+            seller: ActorInfo::Pop(0), product: 1, quantity: 2.0, price: 2.0 });
+        assert_eq!(trades[1].payment(), 4.0);
+        // leftover ask and the untouched expensive ask remain.
+        assert_eq!(test.ask_depth(1), 4.0);
+        assert_eq!(test.best_ask(1), Some(2.0));
+        assert_eq!(test.bid_depth(1), 0.0);
+    }
+
+    #[test]
+    pub fn fill_ask_from_highest_bids_first_at_bid_price() {
+        let mut test = OrderBook::new();
+
+        test.submit_bid(ActorInfo::Pop(0), 1, 2.0, 1.0);
+        test.submit_bid(ActorInfo::Pop(1), 1, 2.0, 3.0);
+
+        let trades = test.submit_ask(ActorInfo::Pop(2), 1, 5.0, 0.5);
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].buyer, ActorInfo::Pop(1));
+        assert_eq!(trades[0].price, 3.0);
+        assert_eq!(trades[1].buyer, ActorInfo::Pop(0));
+        assert_eq!(trades[1].price, 1.0);
+        // remainder of the ask rests.
+        assert_eq!(test.ask_depth(1), 1.0);
+        assert_eq!(test.best_ask(1), Some(0.5));
+    }
+
+    #[test]
+    pub fn give_time_priority_to_earlier_orders_at_same_price() {
+        let mut test = OrderBook::new();
+
+        test.submit_ask(ActorInfo::Pop(0), 1, 1.0, 1.0);
+        test.submit_ask(ActorInfo::Pop(1), 1, 1.0, 1.0);
+
+        let trades = test.submit_bid(ActorInfo::Pop(2), 1, 1.0, 1.0);
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].seller, ActorInfo::Pop(0));
+    }
+
+    #[test]
+    pub fn not_match_an_actor_with_themselves() {
+        let mut test = OrderBook::new();
+
+        test.submit_ask(ActorInfo::Pop(0), 1, 1.0, 1.0);
+        let trades = test.submit_bid(ActorInfo::Pop(0), 1, 1.0, 2.0);
+
+        assert!(trades.is_empty());
+        assert_eq!(test.ask_depth(1), 1.0);
+        assert_eq!(test.bid_depth(1), 1.0);
+    }
+
+    #[test]
+    pub fn cancel_only_the_actors_orders() {
+        let mut test = OrderBook::new();
+
+        test.submit_bid(ActorInfo::Pop(0), 1, 2.0, 1.0);
+        test.submit_bid(ActorInfo::Pop(1), 1, 3.0, 1.0);
+        test.submit_ask(ActorInfo::Pop(2), 2, 4.0, 1.0);
+
+        assert_eq!(test.cancel_bid(ActorInfo::Pop(0), 1), 2.0);
+        assert_eq!(test.bid_depth(1), 3.0);
+        assert_eq!(test.cancel_ask(ActorInfo::Pop(2), 2), 4.0);
+        assert_eq!(test.best_ask(2), None);
+
+        test.clear();
+        assert_eq!(test.bid_depth(1), 0.0);
+    }
+}
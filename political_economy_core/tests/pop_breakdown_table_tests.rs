@@ -68,6 +68,37 @@ mod pop_breakdown_table_tests {
         assert_eq!(result[&Some(0)], 30);
     }
 
+    #[test]
+    pub fn should_return_culture_class_and_generation_makeup_correctly() {
+        let mut test = PopBreakdownTable{table: vec![], total: 0};
+
+        test.insert_pops(PBRow::new(1,
+            None,None,Some(0),
+            Some(0),Some(0),
+            None,None,None,
+            10));
+        test.insert_pops(PBRow::new(1,
+            None,None,Some(0),
+            Some(1),Some(0),
+            None,None,None,
+            5));
+        test.insert_pops(PBRow::new(1,
+            None,None,Some(0),
+            Some(1),Some(1),
+            None,None,None,
+            20));
+
+        let classes = test.culture_class_makeup();
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[&(Some(0), Some(0))], 15);
+        assert_eq!(classes[&(Some(0), Some(1))], 20);
+
+        let generations = test.culture_generation_makeup();
+        assert_eq!(generations.len(), 2);
+        assert_eq!(generations[&(Some(0), Some(0))], 10);
+        assert_eq!(generations[&(Some(0), Some(1))], 25);
+    }
+
     #[test]
     pub fn should_return_ideology_makeup_correctly() {
         let mut test = PopBreakdownTable{table: vec![], total: 0};
@@ -309,4 +340,55 @@ mod pop_breakdown_table_tests {
         assert_eq!(test.table[1].ideology_faction, Some(0));
         assert_eq!(test.table[1].ideology_wave, Some(0));
     }
+
+    #[test]
+    pub fn should_split_off_fraction_of_each_row() {
+        let mut test = PopBreakdownTable{table: vec![], total: 0};
+
+        test.insert_pops(PBRow::new(1,
+            None,None,None,
+            None,None,
+            None,None,None,
+            10));
+        test.insert_pops(PBRow::new(2,
+            None,None,None,
+            None,None,
+            None,None,None,
+            2));
+
+        let result = test.split_off(0.3);
+
+        // 3 of 10 and 1 of 2 (0.6 rounds up).
+        assert_eq!(result.total, 4);
+        assert_eq!(result.species_makeup()[&1], 3);
+        assert_eq!(result.species_makeup()[&2], 1);
+        assert_eq!(test.total, 8);
+        assert_eq!(test.species_makeup()[&1], 7);
+        assert_eq!(test.species_makeup()[&2], 1);
+
+        // taking everything empties the table.
+        let result = test.split_off(1.0);
+        assert_eq!(result.total, 8);
+        assert_eq!(test.total, 0);
+        assert!(test.table.is_empty());
+    }
+
+    #[test]
+    pub fn should_round_split_off_on_the_total() {
+        let mut test = PopBreakdownTable{table: vec![], total: 0};
+        for species in 0..8 {
+            test.insert_pops(PBRow::new(species,
+                None,None,None,
+                None,None,
+                None,None,None,
+                1));
+        }
+
+        // a quarter of 8 is 2, though no row of 1 has a whole person to give.
+        let result = test.split_off(0.25);
+        assert_eq!(result.total, 2);
+        assert_eq!(test.total, 6);
+        assert_eq!(result.species_makeup()[&0], 1);
+        assert_eq!(result.species_makeup()[&1], 1);
+    }
 }
@@ -16,25 +16,34 @@ use political_economy_core::{
             pop::*,
             property_info::*,
             property::*,
-            desire::Desire,
+            desire::{Desire, DesireTag},
             seller::Seller,
+            actor::MessageBacklog,
             buy_result::BuyResult,
             actor_message::*,
+            firm_job::WageType,
         },
         demographic_objects::{
             culture::Culture, 
             ideology::Ideology, 
+            ideology_spread::IdeologyAppeal,
+            conversion::{ConversionKind, ConversionPressure, ConversionPressures},
+            civilization::{Civilization, Epoch},
             pop_breakdown_table::{
                 PBRow, 
                 PopBreakdownTable}, 
-            species::Species
+            reproduction::Clutch,
+            species::{Cohort, Species, SpeciesTag, Subtype, SubtypeTag}
         },
-        environmental_objects::market::{MarketHistory, ProductInfo}
+        environmental_objects::{labor_market::JobOpening,
+            market::{ExchangeMode, MarketHistory, ProductInfo},
+            migration::MarketAppeal}
     }
 };
 
 // TODO ALter Pop class and functions to be more invertable, at least with Free_time, Shopping_loop, Try_to_buy, and Standard Buy
 mod pop_tests {
+    use rand::thread_rng;
     use super::*;
 
     /// Makes a pop for testing. The pop will have the following info
@@ -59,16 +68,82 @@ mod pop_tests {
         let mut test = Pop { 
             id: 10, 
             job: 0, 
-            firm: 0, 
+            firm: Some(0), 
+            owner: None,
             market: 0, 
+            skill: 0,
+            lower_skill_level: 0.0,
+            higher_skill_level: 0.0,
             property: Property::new(vec![]), 
             breakdown_table: PopBreakdownTable{ table: vec![], total: 0 }, 
             is_selling: true,
+            mood: 0.0,
             current_sat: TieredValue { tier: 0, value: 0.0 },
             prev_sat: TieredValue { tier: 0, value: 0.0 },
             hypo_change: TieredValue { tier: 0, value: 0.0 },
-            backlog: VecDeque::new()};
+            clutches: vec![],
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new()};
 
+        let demos = make_test_demos();
+
+        test.breakdown_table.insert_pops(
+            PBRow{ species: 0, 
+                species_cohort: None,
+                species_subtype: None,
+                culture: None,
+                culture_generation: None,
+                culture_class: None,
+                ideology: None, 
+                ideology_wave: None, 
+                ideology_faction: None, 
+                count: 5 }
+        );
+        test.breakdown_table.insert_pops(
+            PBRow{ species: 0, 
+                species_cohort: None,
+                species_subtype: None,
+                culture: Some(0),
+                culture_generation: None,
+                culture_class: None,
+                ideology: None, 
+                ideology_wave: None, 
+                ideology_faction: None, 
+                count: 5 }
+        );
+        test.breakdown_table.insert_pops(
+            PBRow{ species: 0, 
+                species_cohort: None,
+                species_subtype: None,
+                culture: None,
+                culture_generation: None,
+                culture_class: None,
+                ideology: Some(0), 
+                ideology_wave: None, 
+                ideology_faction: None, 
+                count: 5 }
+        );
+        test.breakdown_table.insert_pops(
+            PBRow{ species: 0, 
+                species_cohort: None,
+                species_subtype: None,
+                culture: Some(0),
+                culture_generation: None,
+                culture_class: None,
+                ideology: Some(0), 
+                ideology_wave: None, 
+                ideology_faction: None, 
+                count: 5 }
+        );
+
+        test.update_desires(&demos);
+
+        test
+    }
+
+    /// Makes the demographics used by make_test_pop, with one species,
+    /// culture, and ideology, all of id 0.
+    pub fn make_test_demos() -> Demographics {
         let species_desire_1 = Desire { 
             item: Item::Want(2), // food
             start: 0, 
@@ -152,66 +227,13 @@ mod pop_tests {
             vec![ideology_desire_1, ideology_desire_2],
             vec![]).expect("Messed up new.");
 
-        let mut demos = Demographics{ species: HashMap::new(),
-            cultures: HashMap::new(), 
-            ideology: HashMap::new() };
+        let mut demos = Demographics::new();
 
         demos.species.insert(species.id, species);
         demos.cultures.insert(culture.id, culture);
         demos.ideology.insert(ideology.id, ideology);
 
-        test.breakdown_table.insert_pops(
-            PBRow{ species: 0, 
-                species_cohort: None,
-                species_subtype: None,
-                culture: None,
-                culture_generation: None,
-                culture_class: None,
-                ideology: None, 
-                ideology_wave: None, 
-                ideology_faction: None, 
-                count: 5 }
-        );
-        test.breakdown_table.insert_pops(
-            PBRow{ species: 0, 
-                species_cohort: None,
-                species_subtype: None,
-                culture: Some(0),
-                culture_generation: None,
-                culture_class: None,
-                ideology: None, 
-                ideology_wave: None, 
-                ideology_faction: None, 
-                count: 5 }
-        );
-        test.breakdown_table.insert_pops(
-            PBRow{ species: 0, 
-                species_cohort: None,
-                species_subtype: None,
-                culture: None,
-                culture_generation: None,
-                culture_class: None,
-                ideology: Some(0), 
-                ideology_wave: None, 
-                ideology_faction: None, 
-                count: 5 }
-        );
-        test.breakdown_table.insert_pops(
-            PBRow{ species: 0, 
-                species_cohort: None,
-                species_subtype: None,
-                culture: Some(0),
-                culture_generation: None,
-                culture_class: None,
-                ideology: Some(0), 
-                ideology_wave: None, 
-                ideology_faction: None, 
-                count: 5 }
-        );
-
-        test.update_desires(demos);
-
-        test
+        demos
     }
 
     /// preps a pop's property, the property's data, and market prices of those items.
@@ -237,6 +259,8 @@ mod pop_tests {
             product_info: HashMap::new(),
             sale_priority: vec![],
             currencies: vec![],
+            exchange_mode: ExchangeMode::Bargaining,
+            neighbors: HashMap::new(),
             class_info: HashMap::new(),
             want_info: HashMap::new(),
         };// quickly set all prices to 1.0 for ease going forward.
@@ -269,6 +293,15 @@ mod pop_tests {
         (data, market)
     }
 
+    /// Makes all goods free to handle, for tests of deal making which
+    /// don't cover handling costs.
+    pub fn make_handling_free(data: &mut DataManager) {
+        for product in data.products.values_mut() {
+            product.mass = 0.0;
+            product.bulk = 0.0;
+        }
+    }
+
     mod find_class_product_should{
         use std::{thread, time::Duration};
         use super::super::*;
@@ -360,15 +393,22 @@ mod pop_tests {
         let mut test = Pop{ 
             id: 0, 
             job: 0, 
-            firm: 0, 
+            firm: Some(0), 
+            owner: None,
             market: 0, 
+            skill: 0,
+            lower_skill_level: 0.0,
+            higher_skill_level: 0.0,
             property: Property::new(vec![]), 
             breakdown_table: PopBreakdownTable{ table: vec![], total: 0 }, 
             is_selling: true,
+            mood: 0.0,
             current_sat: TieredValue { tier: 0, value: 0.0 },
             prev_sat: TieredValue { tier: 0, value: 0.0 },
             hypo_change: TieredValue { tier: 0, value: 0.0 },
-            backlog: VecDeque::new()};
+            clutches: vec![],
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new()};
 
         let species_desire_1 = Desire{ 
             item: Item::Product(0), 
@@ -445,9 +485,7 @@ mod pop_tests {
             vec![ideology_desire_1, ideology_desire_2],
             vec![]).expect("Messed up new.");
 
-        let mut demos = Demographics{ species: HashMap::new(),
-             cultures: HashMap::new(), 
-            ideology: HashMap::new() };
+        let mut demos = Demographics::new();
 
         demos.species.insert(species.id, species);
         demos.cultures.insert(culture.id, culture);
@@ -502,7 +540,7 @@ mod pop_tests {
                 count: 5 }
         );
 
-        test.update_desires(demos);
+        test.update_desires(&demos);
 
         assert_eq!(test.property.len(), 6);
         // species desire 1 x 20
@@ -532,28 +570,627 @@ mod pop_tests {
 
     }
 
+    #[test]
+    pub fn update_desires_without_losing_satisfaction() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        let food = Item::Want(2);
+        test.property.desires.iter_mut()
+            .find(|x| x.item == food).unwrap().satisfaction = 10.0;
+        test.breakdown_table.insert_pops(PBRow::new(0, None, None,
+            None, None, None, None, None, None, 5));
+
+        test.update_desires(&demos);
+
+        let desire = test.property.desires.iter()
+            .find(|x| x.item == food).expect("Item Not found");
+        assert_eq!(desire.amount, 25.0);
+        assert_eq!(desire.satisfaction, 10.0);
+        assert!(!test.property.is_sifted);
+
+        // removing everyone of the culture removes its desires.
+        test.breakdown_table.table.retain(|x| x.culture.is_none());
+        test.breakdown_table.total = 15;
+        test.update_desires(&demos);
+
+        assert!(test.property.desires.iter()
+            .all(|x| x.item != Item::Product(2)));
+        let desire = test.property.desires.iter()
+            .find(|x| x.item == food).expect("Item Not found");
+        assert_eq!(desire.amount, 15.0);
+        assert_eq!(desire.satisfaction, 10.0);
+    }
+
+    #[test]
+    pub fn split_off_a_share_of_everything_with_a_new_id() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        let mut data = DataManager::new();
+        test.property.property.insert(3, PropertyInfo::new(10.0));
+        test.property.wage_estimate = 40.0;
+        test.property.desires.get_mut(0).unwrap().satisfaction = 10.0;
+        let mut reserved = PropertyInfo::new(10.0);
+        reserved.unreserved = 6.0;
+        reserved.product_reserve = 4.0;
+        test.property.property.insert(4, reserved);
+        let mut eggs = test.breakdown_table.table[0];
+        eggs.count = 10;
+        test.clutches.push(Clutch { row: eggs, egg: 5, days_left: 3.0 });
+        eggs.count = 2;
+        test.clutches.push(Clutch { row: eggs, egg: 5, days_left: 1.0 });
+
+        let result = test.split(0.2, data.new_pop_id(), &demos);
+        let other = test.split(0.25, data.new_pop_id(), &demos);
+
+        assert_eq!(result.id, 0);
+        assert_eq!(other.id, 1);
+        assert_eq!(result.count(), 4);
+        assert_eq!(other.count(), 4);
+        assert_eq!(test.count(), 12);
+        assert_eq!(result.market, test.market);
+        assert_eq!(result.firm, None);
+        assert_eq!(result.property.property[&3].total_property, 2.0);
+        assert_eq!(test.property.property[&3].total_property, 6.0);
+        // reserves are kept, what's taken comes out of unreserved.
+        assert_eq!(test.property.property[&4].total_property, 6.0);
+        assert_eq!(test.property.property[&4].product_reserve, 4.0);
+        assert_eq!(test.property.property[&4].unreserved, 2.0);
+        // they take their share of the clutches, hatching the same day.
+        assert_eq!(result.clutches.len(), 1);
+        assert_eq!(result.clutches[0].row.count, 2);
+        assert_eq!(result.clutches[0].days_left, 3.0);
+        assert_eq!(other.clutches.len(), 2);
+        assert_eq!(other.clutches[0].row.count, 2);
+        assert_eq!(other.clutches[1].row.count, 1);
+        assert_eq!(test.clutches.iter().map(|x| x.row.count).collect::<Vec<_>>(),
+            vec![6, 1]);
+        assert_eq!(result.property.wage_estimate, 8.0);
+        let food = Item::Want(2);
+        let leaving = result.property.desires.iter()
+            .find(|x| x.item == food).expect("Item Not found");
+        assert_eq!(leaving.amount, 4.0);
+        assert_eq!(leaving.satisfaction, 2.0);
+        let staying = test.property.desires.iter()
+            .find(|x| x.item == food).expect("Item Not found");
+        assert_eq!(staying.amount, 12.0);
+        assert_eq!(staying.satisfaction, 6.0);
+    }
+
+    #[test]
+    pub fn split_off_specific_rows() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        let mut data = DataManager::new();
+        let cultured = PBRow::new(0, None, None, Some(0), None, None, 
+            None, None, None, 20);
+
+        let result = test.split_rows(&[cultured], data.new_pop_id(), &demos);
+
+        // only 5 to take.
+        assert_eq!(result.count(), 5);
+        assert_eq!(test.count(), 15);
+        // ambrosia fruit, 5 of the 10 with a culture left.
+        let ambrosia = Item::Product(2);
+        assert_eq!(result.property.desires.iter()
+            .find(|x| x.item == ambrosia).expect("Item Not found").amount, 5.0);
+        assert_eq!(test.property.desires.iter()
+            .find(|x| x.item == ambrosia).expect("Item Not found").amount, 5.0);
+    }
+
+    #[test]
+    pub fn merge_like_pops_back_together() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        let mut data = DataManager::new();
+        test.property.property.insert(3, PropertyInfo::new(10.0));
+        test.property.desires.get_mut(0).unwrap().satisfaction = 10.0;
+        test.higher_skill_level = 2.0;
+        let mut other = test.split(0.25, data.new_pop_id(), &demos);
+        other.higher_skill_level = 4.0;
+        other.firm = test.firm;
+
+        let mut elsewhere = test.split(0.0, data.new_pop_id(), &demos);
+        elsewhere.market = 5;
+        assert!(test.merge(elsewhere, &demos).is_err());
+
+        test.merge(other, &demos).expect("Failed to merge.");
+        assert_eq!(test.count(), 20);
+        assert_eq!(test.property.property[&3].total_property, 10.0);
+        // 15 at 2, 5 at 4 (a quarter of 20, rounded on the whole pop).
+        assert!((test.higher_skill_level - 2.5).abs() < 0.000001);
+        let food = test.property.desires.iter()
+            .find(|x| x.item == Item::Want(2)).expect("Item Not found");
+        assert_eq!(food.amount, 20.0);
+        assert_eq!(food.satisfaction, 10.0);
+    }
+
+    #[test]
+    pub fn split_migrants_into_new_pop_sharing_desires() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        test.property.desires.get_mut(0).unwrap().satisfaction = 10.0;
+        test.property.property.insert(3, PropertyInfo::new(10.0));
+        test.property.market_satisfaction = 30.0;
+        test.current_sat = TieredValue { tier: 2, value: 1.5 };
+
+        let migrants = test.breakdown_table.split_off(0.2);
+        assert_eq!(migrants.total, 4);
+        assert_eq!(test.count(), 16);
+
+        let result = test.split_migrants(migrants, 11, 3, &demos);
+
+        assert_eq!(result.id, 11);
+        assert_eq!(result.market, 3);
+        assert_eq!(result.job, test.job);
+        assert_eq!(result.firm, None);
+        assert_eq!(result.count(), 4);
+        // food x 20, split 16 / 4
+        let food = Item::Want(2);
+        let staying = test.property.desires.iter()
+            .find(|x| x.item == food).expect("Item Not found");
+        assert_eq!(staying.amount, 16.0);
+        assert_eq!(staying.satisfaction, 8.0);
+        let leaving = result.property.desires.iter()
+            .find(|x| x.item == food).expect("Item Not found");
+        assert_eq!(leaving.amount, 4.0);
+        assert_eq!(leaving.satisfaction, 2.0);
+        // hut x 10, split 8 / 2
+        let hut = Item::Product(14);
+        let staying = test.property.desires.iter()
+            .find(|x| x.item == hut).expect("Item Not found");
+        assert_eq!(staying.amount, 8.0);
+        let leaving = result.property.desires.iter()
+            .find(|x| x.item == hut).expect("Item Not found");
+        assert_eq!(leaving.amount, 2.0);
+        assert!(!test.property.is_sifted);
+        // they take their share of our goods and how satisfied we are.
+        assert_eq!(result.property.property[&3].total_property, 2.0);
+        assert_eq!(test.property.property[&3].total_property, 8.0);
+        assert_eq!(result.property.market_satisfaction, 6.0);
+        assert_eq!(test.property.market_satisfaction, 24.0);
+        assert_eq!(result.current_sat.tier, 2);
+        assert_eq!(result.current_sat.value, 1.5);
+    }
+
+    #[test]
+    pub fn spread_ideologies_to_better_off_followers() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        let mut appeals = HashMap::new();
+        appeals.insert(None, IdeologyAppeal { share: 0.5, satisfaction: 0.0 });
+        appeals.insert(Some(0), IdeologyAppeal { share: 0.5, satisfaction: 10.0 });
+
+        // long enough for everyone to convert.
+        let result = test.spread_ideologies(&demos, &appeals, 100.0, 
+            &mut thread_rng());
+
+        assert_eq!(result, vec![(None, 0, 5), (None, 0, 5)]);
+        assert_eq!(test.count(), 20);
+        assert_eq!(test.breakdown_table.ideology_makeup()[&Some(0)], 20);
+        let hut = test.property.desires.iter()
+            .find(|x| x.item == Item::Product(14)).expect("Item Not found");
+        assert_eq!(hut.amount, 20.0);
+
+        // no one left to convert.
+        let result = test.spread_ideologies(&demos, &appeals, 100.0, 
+            &mut thread_rng());
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    pub fn assimilate_and_convert_toward_market_pressures() {
+        let mut test = make_test_pop();
+        let demos = make_test_demos();
+        let pressures = ConversionPressures {
+            cultures: vec![ConversionPressure { target: 0, share: 0.5, policy: 0.0 }],
+            ideologies: vec![ConversionPressure { target: 0, share: 0.5, policy: 0.0 }],
+        };
+
+        // long enough for everyone to convert.
+        let result = test.convert(&demos, &pressures, 
+            DAYS_PER_YEAR / (CONVERSION_RATE * 0.5), &mut thread_rng());
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.iter().filter(|x| x.0 == ConversionKind::Culture)
+            .map(|x| x.3).sum::<usize>(), 10);
+        assert_eq!(result.iter().filter(|x| x.0 == ConversionKind::Ideology)
+            .map(|x| x.3).sum::<usize>(), 10);
+        assert_eq!(test.count(), 20);
+        assert_eq!(test.breakdown_table.culture_makeup()[&Some(0)], 20);
+        assert_eq!(test.breakdown_table.ideology_makeup()[&Some(0)], 20);
+        let ambrosia = test.property.desires.iter()
+            .find(|x| x.item == Item::Product(2)).expect("Item Not found");
+        assert_eq!(ambrosia.amount, 20.0);
+    }
+
+    #[test]
+    pub fn practice_the_skill_of_their_job() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+        let mut test = make_test_pop();
+        let mut demos = make_test_demos();
+
+        test.practice_skill(&data, &demos, DAYS_PER_YEAR);
+        assert_eq!(test.skill, 0);
+        assert_eq!(test.higher_skill_level, 1.0);
+        assert_eq!(test.lower_skill_level, 0.5);
+        assert_eq!(test.skill_level(), 0.75);
+        assert_eq!(test.skill_efficiency(), 0.75 * SKILL_EFFICIENCY_PER_LEVEL);
+        // diminishing returns as they get better.
+        test.practice_skill(&data, &demos, DAYS_PER_YEAR);
+        assert_eq!(test.higher_skill_level, 1.9);
+        assert_eq!(test.lower_skill_level, 0.95);
+
+        // changing jobs to one with a different skill starts over.
+        test.job = 3;
+        test.practice_skill(&data, &demos, DAYS_PER_YEAR);
+        assert_eq!(test.skill, 2);
+        assert_eq!(test.higher_skill_level, 1.0);
+
+        // a species twice as talented with textiles learns twice as fast.
+        let species = demos.species.get_mut(&0).unwrap();
+        species.tags.push(SpeciesTag::SkillGroupModifier { skill_group: 1, modifier: 2.0 });
+        species.update_data_table();
+        test.skill = 0;
+        test.practice_skill(&data, &demos, DAYS_PER_YEAR);
+        assert_eq!(test.higher_skill_level, 2.0);
+        assert_eq!(test.lower_skill_level, 1.0);
+    }
+
+    #[test]
+    pub fn build_mood_from_trend_expectation_and_comparison() {
+        let mut test = make_test_pop();
+        test.prev_sat = TieredValue { tier: 0, value: 10.0 };
+        test.current_sat = TieredValue { tier: 0, value: 15.0 };
+        test.hypo_change = TieredValue { tier: 0, value: 10.0 };
+        test.property.market_satisfaction = 40.0; // 2 per person
+        let even = MarketAppeal { satisfaction: 2.0, wage: 0.0 };
+        let richer = MarketAppeal { satisfaction: 6.0, wage: 0.0 };
+
+        // half again better off, but only half the gain hoped for.
+        let expected = 0.5 * MOOD_TREND_WEIGHT - 0.5 * MOOD_EXPECTATION_WEIGHT;
+        assert!((test.mood_target(&even) - expected).abs() < 0.000001);
+        // with neighbors 3 times as well off.
+        let expected = expected - 0.5 * MOOD_COMPARISON_WEIGHT;
+        assert!((test.mood_target(&richer) - expected).abs() < 0.000001);
+
+        test.update_mood(&richer);
+        assert!((test.mood - expected * MOOD_ADJUSTMENT_RATE).abs() < 0.000001);
+
+        // nothing to compare to is no change.
+        test.prev_sat = TieredValue { tier: 0, value: 0.0 };
+        test.hypo_change = TieredValue { tier: 0, value: 0.0 };
+        assert_eq!(test.mood_target(&even), 0.0);
+    }
+
+    #[test]
+    pub fn let_mood_drive_unrest_work_and_productivity() {
+        let mut test = make_test_pop();
+        assert!(!test.is_restless());
+        assert!(!test.is_looking_for_work());
+        assert_eq!(test.mood_productivity(), 1.0);
+        assert_eq!(test.migration_willingness(), 1.0);
+
+        test.mood = MOOD_UNREST_THRESHOLD;
+        assert!(test.is_restless());
+        assert!(test.is_looking_for_work());
+        assert_eq!(test.mood_productivity(), 1.0 + MOOD_UNREST_THRESHOLD * MOOD_PRODUCTIVITY_EFFECT);
+        assert_eq!(test.migration_willingness(), 1.0 - MOOD_UNREST_THRESHOLD);
+
+        test.mood = 1.0;
+        assert!(!test.is_restless());
+        assert!(!test.is_looking_for_work());
+        assert_eq!(test.migration_willingness(), 0.0);
+        // ending the day worse off is restless regardless.
+        test.prev_sat = TieredValue { tier: 0, value: 10.0 };
+        assert!(test.is_restless());
+    }
+
+    #[test]
+    pub fn look_for_better_paying_work() {
+        let mut test = make_test_pop();
+        test.property.wage_estimate = 40.0; // 2 per person
+        let opening = JobOpening { firm: 3, index: 0, job: 5, 
+            wage_type: WageType::Daily, wage: 4.0 };
+        let worse = JobOpening { wage: 2.0, ..opening };
+
+        // employed and happy, they stay put.
+        assert_eq!(test.job_switch_fraction(&opening), 0.0);
+        test.mood = MOOD_JOB_SEARCH_THRESHOLD;
+        assert_eq!(test.job_switch_fraction(&opening), JOB_SWITCH_FRACTION);
+        assert_eq!(test.job_switch_fraction(&worse), 0.0);
+        test.firm = None;
+        assert_eq!(test.job_switch_fraction(&opening), 1.0);
+
+        // the unemployed take note of every offer.
+        test.observe_wage_offer(1.0);
+        assert_eq!(test.property.wage_estimate, 40.0 - 20.0 * WAGE_ESTIMATE_ADJUSTMENT);
+        // the employed only better ones.
+        test.firm = Some(0);
+        test.property.wage_estimate = 40.0;
+        test.observe_wage_offer(1.0);
+        assert_eq!(test.property.wage_estimate, 40.0);
+        test.observe_wage_offer(4.0);
+        assert_eq!(test.property.wage_estimate, 40.0 + 40.0 * WAGE_ESTIMATE_ADJUSTMENT);
+
+        test.take_job(&opening);
+        assert_eq!(test.job, 5);
+        assert_eq!(test.firm, Some(3));
+        assert_eq!(test.wage_per_person(), 4.0);
+    }
+
+    #[test]
+    pub fn estimate_wage_from_pay_stubs() {
+        let mut test = make_test_pop();
+        test.property.wage_estimate = 40.0;
+
+        // paid daily, the whole wage counts.
+        test.recieve_pay_stub(60.0, 1);
+        assert_eq!(test.property.todays_wage, 60.0);
+        assert_eq!(test.property.pay_period, 1);
+        assert_eq!(test.property.wage_estimate, 40.0 + 20.0 * WAGE_ESTIMATE_ADJUSTMENT);
+        // a weekly paycheck is spread over the week.
+        test.property.todays_wage = 0.0;
+        test.property.wage_estimate = 40.0;
+        test.recieve_pay_stub(280.0, 7);
+        assert_eq!(test.property.todays_wage, 280.0);
+        assert_eq!(test.property.pay_period, 7);
+        assert_eq!(test.property.wage_estimate, 40.0);
+        // sharing in a loss drags it down.
+        test.recieve_pay_stub(-10.0, 1);
+        assert_eq!(test.property.wage_estimate, 40.0 - 50.0 * WAGE_ESTIMATE_ADJUSTMENT);
+    }
+
+    #[test]
+    pub fn have_drones_ask_their_owner_for_provisions() {
+        let mut test = make_test_pop();
+        let mut demos = make_test_demos();
+        let (data, market) = prepare_data_for_market_actions(&mut test);
+        assert!(!test.is_drone(&demos));
+        let species = demos.species.get_mut(&0).unwrap();
+        species.tags.push(SpeciesTag::Drone);
+        species.update_data_table();
+        assert!(test.is_drone(&demos));
+        // setup message queue.
+        let (mut tx, rx) = barrage::bounded(20);
+        let mut passed_rx = rx.clone();
+
+        Pop::drone_loop(&mut test, &mut passed_rx, &mut tx, &data, &market);
+
+        let expected = test.property.desires.iter()
+            .filter_map(|x| x.total_desire().map(|total| (x.item, total - x.satisfaction)))
+            .collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        for (item, amount) in expected {
+            let msg = rx.try_recv().expect("Unexpected Disconnect?")
+                .expect("Nothing Sent.");
+            assert_eq!(msg, ActorMessage::EmployeeToFirm { 
+                employee: test.actor_info(), firm: ActorInfo::Firm(0), 
+                action: FirmEmployeeAction::RequestProvisions { item, amount } });
+        }
+        assert!(rx.try_recv().expect("Unexpected Disconnect?").is_none());
+
+        // drones owned by an institution ask it instead of their firm.
+        test.owner = Some(ActorInfo::Institution(3));
+        Pop::drone_loop(&mut test, &mut passed_rx, &mut tx, &data, &market);
+        let msg = rx.try_recv().expect("Unexpected Disconnect?")
+            .expect("Nothing Sent.");
+        if let ActorMessage::EmployeeToFirm { firm, .. } = msg {
+            assert_eq!(firm, ActorInfo::Institution(3));
+        } else { panic!("Wrong message sent."); }
+        while rx.try_recv().expect("Unexpected Disconnect?").is_some() {}
+
+        // without an owner there's no one to ask.
+        test.owner = None;
+        test.firm = None;
+        Pop::drone_loop(&mut test, &mut passed_rx, &mut tx, &data, &market);
+        assert!(rx.try_recv().expect("Unexpected Disconnect?").is_none());
+    }
+
+    mod population_change_should {
+        use std::collections::HashSet;
+
+        use rand::thread_rng;
+        use super::*;
+
+        fn growing_pop() -> (Pop, Demographics) {
+            let mut test = make_test_pop();
+            test.breakdown_table = PopBreakdownTable { table: vec![], total: 0 };
+            test.breakdown_table.insert_pops(PBRow::new(1, None, None,
+                None, None, None, None, None, None, 20));
+            test.property.desires = vec![Desire { item: Item::Want(2), 
+                start: 0, end: Some(4), amount: 20.0, satisfaction: 20.0, 
+                step: 1, tags: vec![] }];
+            let species = Species::new(1, "Growing".into(), "".into(),
+                vec![Desire { item: Item::Want(2), start: 0, end: Some(4), 
+                    amount: 1.0, satisfaction: 0.0, step: 1, tags: vec![] }],
+                vec![], vec![], 1.0, 0.5, 0.25).expect("Messed up new.");
+            let mut demos = Demographics::new();
+            demos.species.insert(species.id, species);
+            (test, demos)
+        }
+
+        #[test]
+        pub fn add_births_remove_deaths_and_update_desires() {
+            let (mut test, demos) = growing_pop();
+
+            let (births, deaths) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+
+            assert_eq!(births, 10);
+            assert_eq!(deaths, 5);
+            assert_eq!(test.count(), 25);
+            assert_eq!(test.property.desires[0].amount, 25.0);
+            assert_eq!(test.property.desires[0].satisfaction, 20.0);
+            assert!(!test.property.is_sifted);
+        }
+
+        #[test]
+        pub fn limit_births_to_carriers_with_partners() {
+            let (mut test, mut demos) = growing_pop();
+            let species = demos.species.get_mut(&1).unwrap();
+            species.mortality_rate = 0.0;
+            species.tags.push(SpeciesTag::ReproductiveGroup("f/m".into()));
+            species.add_subtype(Subtype::new("Female".into(), 1.0, 0.0, 0.0, 1.0,
+                vec![SubtypeTag::Sex("f".into())], vec![]).expect("Messed up new."));
+            species.add_subtype(Subtype::new("Male".into(), 1.0, 0.0, 0.0, 1.0,
+                vec![SubtypeTag::Sex("m".into())], vec![]).expect("Messed up new."));
+            test.breakdown_table = PopBreakdownTable { table: vec![], total: 0 };
+            test.breakdown_table.insert_pops(PBRow::new(1, None, Some(0),
+                None, None, None, None, None, None, 40));
+            test.breakdown_table.insert_pops(PBRow::new(1, None, Some(1),
+                None, None, None, None, None, None, 20));
+
+            let (births, _) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+
+            // only half of the females have a partner, the males carry none.
+            assert_eq!(births, 10);
+        }
+
+        #[test]
+        pub fn lay_eggs_which_hatch_if_still_around() {
+            let (mut test, mut demos) = growing_pop();
+            let species = demos.species.get_mut(&1).unwrap();
+            species.mortality_rate = 0.0;
+            species.tags.push(SpeciesTag::Oviparous { egg: 20, incubation: 30 });
+
+            let (births, _) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+            assert_eq!(births, 0);
+            assert_eq!(test.clutches.len(), 1);
+            assert_eq!(test.property.property[&20].total_property, 10.0);
+
+            // no more are laid, and some eggs go missing before they hatch.
+            let species = demos.species.get_mut(&1).unwrap();
+            species.birth_rate = 0.0;
+            species.update_data_table();
+            test.property.property.get_mut(&20).unwrap().remove(4.0);
+            let (births, _) = test.population_change(&demos, 
+                10.0, &mut thread_rng());
+            assert_eq!(births, 0);
+            let (births, _) = test.population_change(&demos, 
+                20.0, &mut thread_rng());
+            assert_eq!(births, 6);
+            assert_eq!(test.property.property[&20].total_property, 0.0);
+            assert!(test.clutches.is_empty());
+        }
+
+        #[test]
+        pub fn raise_deaths_when_holding_toxins() {
+            let (mut test, demos) = growing_pop();
+            test.property.add_desire(&Desire { item: Item::Product(7), 
+                start: 0, end: None, amount: 20.0, satisfaction: 0.0, step: 0,
+                tags: vec![DesireTag::Toxic] });
+            test.property.property.insert(7, PropertyInfo::new(10.0));
+
+            let (_, deaths) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+
+            // half exposed, adding half of TOXIC_MORTALITY_RATE to 0.25.
+            assert_eq!(deaths, 10);
+        }
+
+        #[test]
+        pub fn stop_births_and_raise_deaths_when_starving() {
+            let (mut test, demos) = growing_pop();
+            test.property.desires[0].satisfaction = 0.0;
+
+            let (births, deaths) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+
+            // everyone dies at 1.25 mortality, no one is born.
+            assert_eq!(births, 0);
+            assert_eq!(deaths, 20);
+            assert_eq!(test.count(), 0);
+            assert!(test.breakdown_table.table.is_empty());
+        }
+
+        #[test]
+        pub fn raise_newborns_without_culture_in_civilization_default() {
+            let (mut test, mut demos) = growing_pop();
+            let species = demos.species.get_mut(&1).unwrap();
+            species.mortality_rate = 0.0;
+            species.update_data_table();
+            let culture = Culture::new(0, "Culture".into(), "".into(),
+                0.0, 0.0, 1.0, vec![], vec![]).expect("Messed up new.");
+            demos.cultures.insert(culture.id, culture);
+            let mut civ = Civilization::new(0, "Civ".into(), "".into())
+                .expect("Messed up new.");
+            civ.add_epoch(Epoch::new("Stone".into(), 0, 0.0, HashSet::new(), Some(0))
+                .expect("Messed up new."));
+            civ.current_epoch = Some(0);
+            demos.civilizations.insert(civ.id, civ);
+            demos.assign_market(test.market, Some(0));
+
+            let (births, _) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+
+            assert_eq!(births, 10);
+            assert_eq!(test.breakdown_table.culture_makeup()[&Some(0)], 10);
+            assert_eq!(test.breakdown_table.culture_makeup()[&None], 20);
+        }
+
+        #[test]
+        pub fn age_people_through_cohorts_and_birth_into_the_first() {
+            let (mut test, mut demos) = growing_pop();
+            let species = demos.species.get_mut(&1).unwrap();
+            species.mortality_rate = 0.0;
+            species.add_cohort(Cohort::new("Young".into(), 365, 0.0, 0.0, 1.0,
+                vec![], vec![]).expect("Messed up new."));
+            species.add_cohort(Cohort::new("Old".into(), 365, 0.0, 0.0, 1.0,
+                vec![], vec![]).expect("Messed up new."));
+            test.breakdown_table = PopBreakdownTable { table: vec![], total: 0 };
+            test.breakdown_table.insert_pops(PBRow::new(1, Some(0), None,
+                None, None, None, None, None, None, 10));
+            test.breakdown_table.insert_pops(PBRow::new(1, Some(1), None,
+                None, None, None, None, None, None, 10));
+
+            let (births, deaths) = test.population_change(&demos, 
+                DAYS_PER_YEAR, &mut thread_rng());
+
+            // 10 born young, all the young age up, and all the old die of age.
+            assert_eq!(births, 10);
+            assert_eq!(deaths, 10);
+            assert_eq!(test.count(), 20);
+            let young = test.breakdown_table.table.iter()
+                .find(|x| x.species_cohort == Some(0)).unwrap();
+            assert_eq!(young.count, 10);
+            let old = test.breakdown_table.table.iter()
+                .find(|x| x.species_cohort == Some(1)).unwrap();
+            assert_eq!(old.count, 10);
+        }
+    }
+
     mod standard_sell_should {
         use std::{collections::VecDeque, thread, time::Duration};
         use super::super::*;
-        use super::prepare_data_for_market_actions;
+        use super::{make_handling_free, prepare_data_for_market_actions};
 
         #[test]
         pub fn send_out_of_stock_when_item_not_owned() {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             let pop_info = test.actor_info();
             let (data, history) = prepare_data_for_market_actions(&mut test);
@@ -609,18 +1246,25 @@ mod pop_tests {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             let pop_info = test.actor_info();
             let (data, history) = prepare_data_for_market_actions(&mut test);
@@ -678,18 +1322,25 @@ mod pop_tests {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             let pop_info = test.actor_info();
             let (data, history) = prepare_data_for_market_actions(&mut test);
@@ -746,18 +1397,25 @@ mod pop_tests {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             let pop_info = test.actor_info();
             let (data, history) = prepare_data_for_market_actions(&mut test);
@@ -837,18 +1495,25 @@ mod pop_tests {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             let pop_info = test.actor_info();
             let (data, mut history) = prepare_data_for_market_actions(&mut test);
@@ -943,21 +1608,29 @@ mod pop_tests {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             history.product_info.get_mut(&5).unwrap().price = 0.05;
             // create simple desire to test against.
             test.property.desires.push(Desire::new(Item::Product(1), 0, 
@@ -1047,6 +1720,395 @@ mod pop_tests {
         // TODO When returning change is possible, add test here and update previous test.
     }
 
+    mod salesman_sell_should {
+        use std::{collections::VecDeque, thread, time::Duration};
+        use super::super::*;
+        use super::prepare_data_for_market_actions;
+
+        fn salesman_pop() -> Pop {
+            Pop {
+                id: 0,
+                job: 0,
+                firm: Some(0),
+                owner: None,
+                market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
+                property: Property::new(vec![]),
+                breakdown_table: PopBreakdownTable {
+                    table: vec![],
+                    total: 1,
+                },
+                is_selling: true,
+                mood: 0.0,
+                current_sat: TieredValue { tier: 0, value: 0.0 },
+                prev_sat: TieredValue { tier: 0, value: 0.0 },
+                hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
+                backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
+            }
+        }
+
+        #[test]
+        pub fn return_none_when_no_buyer_is_found() {
+            let mut test = salesman_pop();
+            let pop_info = test.actor_info();
+            let (data, history) = prepare_data_for_market_actions(&mut test);
+            // create simple desire to test against.
+            test.property.desires.push(Desire::new(Item::Product(1), 0, 
+                None, 1.0, 0.0, 
+                1, vec![]).expect("whoops"));
+            test.property.add_property(10, 10.0, &data);
+            // setup message queue
+            let (tx, rx) = barrage::bounded(10);
+            let mut passed_rx = rx.clone();
+            let passed_tx = tx.clone();
+
+            let handle = thread::spawn(move || {
+                let result = test.salesman_sell(&mut passed_rx, &passed_tx, &data, 
+                    &history, 10);
+                (test, result)
+            });
+
+            // should ask the market for a buyer.
+            if let ActorMessage::FindBuyer { product, sender } = rx.recv().expect("Broke.") {
+                assert_eq!(product, 10);
+                assert_eq!(sender, pop_info);
+            } else {
+                assert!(false, "FindBuyer not recieved.");
+            }
+            tx.send(ActorMessage::BuyerNotFound { product: 10, seller: pop_info })
+                .expect("Broke.");
+
+            thread::sleep(Duration::from_millis(100));
+            if !handle.is_finished() { assert!(false); }
+            let (test, result) = handle.join().unwrap();
+
+            assert_eq!(result, None);
+            assert_eq!(test.property.property.get(&10).unwrap().total_property, 10.0);
+        }
+
+        #[test]
+        pub fn open_deal_with_the_buyer_found() {
+            let mut test = salesman_pop();
+            let pop_info = test.actor_info();
+            let (data, history) = prepare_data_for_market_actions(&mut test);
+            // we don't have the product, so the deal should close as out of stock.
+            // setup message queue
+            let (tx, rx) = barrage::bounded(10);
+            let mut passed_rx = rx.clone();
+            let passed_tx = tx.clone();
+            let test_buyer = ActorInfo::Pop(1);
+
+            let handle = thread::spawn(move || {
+                let result = test.salesman_sell(&mut passed_rx, &passed_tx, &data, 
+                    &history, 10);
+                (test, result)
+            });
+
+            if let ActorMessage::FindBuyer { .. } = rx.recv().expect("Broke.") {
+            } else {
+                assert!(false, "FindBuyer not recieved.");
+            }
+            tx.send(ActorMessage::FoundBuyer { seller: pop_info, buyer: test_buyer, 
+                product: 10 }).expect("Broke.");
+
+            // the seller opens the deal with the buyer.
+            let start = std::time::SystemTime::now();
+            loop {
+                if let Some(msg) = rx.try_recv().expect("Broke.") {
+                    if let ActorMessage::NotInStock { buyer, seller, product } = msg {
+                        assert_eq!(buyer, test_buyer);
+                        assert_eq!(seller, pop_info);
+                        assert_eq!(product, 10);
+                        break;
+                    }
+                }
+                let now = std::time::SystemTime::now();
+                if now.duration_since(start).expect("Bad Time!") 
+                    > Duration::from_secs(3) {
+                    assert!(false, "Timed Out.");
+                }
+            }
+
+            thread::sleep(Duration::from_millis(100));
+            if !handle.is_finished() { assert!(false); }
+            let (_, result) = handle.join().unwrap();
+
+            assert_eq!(result, Some(test_buyer));
+        }
+
+        #[test]
+        pub fn wait_until_idle_to_hear_out_salesmen() {
+            let mut test = salesman_pop();
+            let pop_info = test.actor_info();
+            let (data, history) = prepare_data_for_market_actions(&mut test);
+            // setup message queue
+            let (tx, rx) = barrage::bounded(10);
+            let mut passed_rx = rx.clone();
+            let passed_tx = tx.clone();
+            let salesman = ActorInfo::Firm(5);
+
+            let handle = thread::spawn(move || {
+                let result = test.salesman_sell(&mut passed_rx, &passed_tx, &data, 
+                    &history, 10);
+                (test, result, passed_rx, passed_tx, data, history)
+            });
+
+            if let ActorMessage::FindBuyer { .. } = rx.recv().expect("Broke.") {
+            } else {
+                assert!(false, "FindBuyer not recieved.");
+            }
+            // a salesman comes to us while we're still waiting on our own buyer.
+            tx.send(ActorMessage::FoundBuyer { seller: salesman, buyer: pop_info, 
+                product: 3 }).expect("Broke.");
+            tx.send(ActorMessage::BuyerNotFound { product: 10, seller: pop_info })
+                .expect("Broke.");
+
+            // we don't stop to deal with them mid trip.
+            thread::sleep(Duration::from_millis(100));
+            if !handle.is_finished() { assert!(false, "Dealt with salesman while busy."); }
+            let (mut test, result, mut passed_rx, passed_tx, data, history) = 
+                handle.join().unwrap();
+            assert_eq!(result, None);
+            assert_eq!(test.salesmen, VecDeque::from([salesman]));
+
+            // once idle, we hear them out.
+            let handle = thread::spawn(move || {
+                test.hear_out_salesmen(&mut passed_rx, &passed_tx, &data, &history);
+                test
+            });
+            tx.send(ActorMessage::NotInStock { buyer: pop_info, seller: salesman, 
+                product: 3 }).expect("Broke.");
+
+            thread::sleep(Duration::from_millis(100));
+            if !handle.is_finished() { assert!(false, "Salesman not heard out."); }
+            let test = handle.join().unwrap();
+            assert!(test.salesmen.is_empty());
+        }
+    }
+
+    mod order_book_buy_should {
+        use std::{collections::VecDeque, thread, time::Duration};
+        use super::super::*;
+        use super::prepare_data_for_market_actions;
+
+        #[test]
+        pub fn hold_bid_funds_and_record_fills_at_the_trade_price() {
+            let mut test = Pop {
+                id: 0,
+                job: 0,
+                firm: None,
+                owner: None,
+                market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
+                property: Property::new(vec![Desire::new(Item::Product(6), 
+                    0, Some(5), 1.0, 0.0, 5, vec![]).unwrap()]),
+                breakdown_table: PopBreakdownTable {
+                    table: vec![],
+                    total: 1,
+                },
+                is_selling: true,
+                mood: 0.0,
+                current_sat: TieredValue { tier: 0, value: 0.0 },
+                prev_sat: TieredValue { tier: 0, value: 0.0 },
+                hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
+                backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
+            };
+            let pop_info = test.actor_info();
+            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            history.exchange_mode = ExchangeMode::OrderBook { currency: 2 };
+            test.property.add_property(2, 100.0, &data);
+            test.property.add_property(SHOPPING_TIME_PRODUCT_ID, 100.0, &data);
+            // setup message queue
+            let (tx, rx) = barrage::bounded(10);
+            let mut passed_rx = rx.clone();
+            let passed_tx = tx.clone();
+
+            let handle = thread::spawn(move || {
+                let result = test.order_book_buy(&mut passed_rx, &passed_tx, 
+                    &data, &history, 6, 2.0);
+                (test, result)
+            });
+
+            if let ActorMessage::BidOrder { sender, product, quantity, price } 
+            = rx.recv().expect("Broke.") {
+                assert_eq!(sender, pop_info);
+                assert_eq!(product, 6);
+                assert_eq!(quantity, 2.0);
+                assert_eq!(price, 10.0);
+            } else {
+                assert!(false, "BidOrder not recieved.");
+            }
+            // filled below our bid, then closed.
+            tx.send(ActorMessage::OrderFilled { buyer: pop_info, 
+                seller: ActorInfo::Pop(1), product: 6, quantity: 2.0, 
+                currency: 2, price: 8.0 }).expect("Broke.");
+            tx.send(ActorMessage::BidClosed { buyer: pop_info, product: 6, 
+                filled: 2.0 }).expect("Broke.");
+
+            thread::sleep(Duration::from_millis(100));
+            if !handle.is_finished() { assert!(false); }
+            let (test, result) = handle.join().unwrap();
+
+            assert!(matches!(result, BuyResult::Successful));
+            // the unspent part of the bid is released.
+            assert!(test.property.held.is_empty());
+            assert_eq!(test.property.property[&2].total_property, 84.0);
+            assert_eq!(test.property.property[&6].total_property, 2.0);
+            assert_eq!(test.property.property[&6].amv_cost, 16.0);
+        }
+    }
+
+    mod auction_should {
+        use std::{collections::VecDeque, thread};
+        use political_economy_core::objects::environmental_objects::market::AuctionRecord;
+        use super::super::*;
+        use super::prepare_data_for_market_actions;
+
+        fn auction_pop(id: usize, desires: Vec<Desire>) -> Pop {
+            Pop {
+                id,
+                job: 0,
+                firm: None,
+                owner: None,
+                market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
+                property: Property::new(desires),
+                breakdown_table: PopBreakdownTable {
+                    table: vec![],
+                    total: 1,
+                },
+                is_selling: true,
+                mood: 0.0,
+                current_sat: TieredValue { tier: 0, value: 0.0 },
+                prev_sat: TieredValue { tier: 0, value: 0.0 },
+                hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
+                backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
+            }
+        }
+
+        #[test]
+        pub fn sell_surplus_to_the_best_bidder_and_release_the_rest() {
+            let mut organizer = auction_pop(0, vec![Desire::new(Item::Product(3), 
+                0, Some(5), 1.0, 0.0, 5, vec![]).unwrap()]);
+            let (data, mut history) = prepare_data_for_market_actions(&mut organizer);
+            organizer.property.add_property(6, 20.0, &data);
+            organizer.property.add_property(SHOPPING_TIME_PRODUCT_ID, 100.0, &data);
+            let mut bidders = vec![];
+            for id in 1..3 {
+                let mut bidder = auction_pop(id, vec![Desire::new(Item::Product(6), 
+                    0, Some(95), 1.0, 0.0, 5, vec![]).unwrap()]);
+                bidder.property.add_property(2, 300.0, &data);
+                bidder.property.add_property(SHOPPING_TIME_PRODUCT_ID, 100.0, &data);
+                bidders.push(bidder);
+            }
+            // it doesn't sell well, so the surplus is worth auctioning off 
+            // at the market price.
+            assert!(organizer.surplus_auction(6, 20.0, &history).is_none());
+            let info = history.product_info.get_mut(&6).unwrap();
+            info.offered = 30.0;
+            info.sold = 10.0;
+            let terms = organizer.surplus_auction(6, 20.0, &history)
+                .expect("Should Auction.");
+            assert_eq!(terms, AuctionTerms { kind: AuctionKind::Auction, 
+                product: 6, quantity: 20.0, currency: 2, reserve: 10.0 });
+            assert!(organizer.surplus_auction(6, 0.5, &history).is_none());
+            assert!(organizer.surplus_auction(2, 20.0, &history).is_none());
+
+            let (tx, rx) = barrage::bounded(100);
+            thread::scope(|scope| {
+                let mut handles = vec![];
+                for mut bidder in bidders.drain(..) {
+                    let mut passed_rx = rx.clone();
+                    let passed_tx = tx.clone();
+                    let (data, history) = (&data, &history);
+                    handles.push(scope.spawn(move || {
+                        bidder.active_wait(&mut passed_rx, &passed_tx, data, history, 
+                            &vec![ActorMessage::AllFinished]);
+                        bidder
+                    }));
+                }
+                let mut passed_rx = rx.clone();
+                let passed_tx = tx.clone();
+                let (data_ref, history_ref) = (&data, &history);
+                let organizer_handle = scope.spawn(move || {
+                    let auction = organizer.open_auction(&mut passed_rx, &passed_tx, 
+                        data_ref, history_ref, terms).expect("Did not open.");
+                    organizer.active_wait(&mut passed_rx, &passed_tx, data_ref, history_ref, 
+                        &vec![ActorMessage::StartDay]);
+                    let result = organizer.close_auction(&mut passed_rx, &passed_tx, 
+                        data_ref, history_ref, auction);
+                    (organizer, result)
+                });
+
+                // play the market.
+                let mut record = None;
+                loop {
+                    match rx.recv().expect("Broke.") {
+                        ActorMessage::OpenAuction { organizer, kind, product, 
+                        quantity, currency, reserve } => {
+                            record = Some(AuctionRecord::new(0, organizer, kind, 
+                                product, quantity, currency, reserve));
+                            tx.send(ActorMessage::AuctionOpened { auction: 0, organizer, 
+                                kind, product, quantity, currency, reserve }).expect("Broke.");
+                        },
+                        ActorMessage::AuctionBid { auction, bidder, price } => {
+                            assert_eq!(auction, 0);
+                            let record = record.as_mut().unwrap();
+                            record.add_bid(bidder, price);
+                            if record.bids.len() == 2 {
+                                tx.send(ActorMessage::StartDay).expect("Broke.");
+                            }
+                        },
+                        ActorMessage::CloseAuction { auction, organizer } => {
+                            let record = record.take().unwrap();
+                            let (winner, price) = record.winning_bid().unwrap();
+                            tx.send(ActorMessage::AuctionClosed { auction, organizer, 
+                                winner: Some(winner), kind: record.kind, 
+                                product: record.product, quantity: record.quantity, 
+                                currency: record.currency, price }).expect("Broke.");
+                            break;
+                        },
+                        _ => ()
+                    }
+                }
+
+                let (organizer, result) = organizer_handle.join().unwrap();
+                tx.send(ActorMessage::AllFinished).expect("Broke.");
+                let bidders = handles.into_iter()
+                    .map(|x| x.join().unwrap())
+                    .collect::<Vec<_>>();
+
+                let (winner, price) = result.expect("No Winner.");
+                assert_eq!(price, 10.0);
+                assert!(organizer.property.held.is_empty());
+                assert_eq!(organizer.property.property[&6].total_property, 0.0);
+                assert_eq!(organizer.property.property[&2].total_property, 200.0);
+                for bidder in bidders {
+                    assert!(bidder.property.held.is_empty());
+                    if bidder.actor_info() == winner {
+                        assert_eq!(bidder.property.property[&6].total_property, 20.0);
+                        assert_eq!(bidder.property.property[&2].total_property, 100.0);
+                    } else {
+                        assert_eq!(bidder.property.property[&6].total_property, 0.0);
+                        assert_eq!(bidder.property.property[&2].total_property, 300.0);
+                    }
+                }
+            });
+        }
+    }
+
     mod msg_tests {
         use std::{thread, time::Duration, collections::{HashMap, HashSet}};
         use super::super::*;
@@ -1805,6 +2867,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -1900,6 +2964,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -1996,6 +3062,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -2090,6 +3158,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -2167,7 +3237,7 @@ mod pop_tests {
     mod standard_buy_should {
         use std::{collections::VecDeque, thread, time::Duration};
         use super::super::*;
-        use super::{make_test_pop, prepare_data_for_market_actions};
+        use super::{make_handling_free, make_test_pop, prepare_data_for_market_actions};
 
         #[test]
         pub fn return_not_successful_when_not_in_stock() {
@@ -2215,7 +3285,8 @@ mod pop_tests {
         pub fn correctly_attempt_to_buy_input_value_when_its_higher() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in pop's property and sift their desires.
             // we have 20 extra food than we need (20*5=100.0 units)
             // this covers all food and leave excess for trading
@@ -2344,7 +3415,8 @@ mod pop_tests {
         pub fn return_success_when_able_to_buy_single_offer_no_change() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in pop's property and sift their desires.
             // we have 20 extra food than we need (20*5=100.0 units)
             // this covers all food and leave excess for trading
@@ -2473,7 +3545,8 @@ mod pop_tests {
         pub fn correctly_release_class_desire_for_buy_offer() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // swap out infinite clothes for the class desire instead.
             test.property.desires.get_mut(4).unwrap()
                 .item = Item::Class(6);
@@ -2626,7 +3699,8 @@ mod pop_tests {
         pub fn correctly_release_want_desire_for_buy_offer() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // swap out infinite clothes for the want desire instead.
             test.property.desires.get_mut(4).unwrap()
                 .item = Item::Want(2);
@@ -2868,25 +3942,33 @@ mod pop_tests {
             let mut test = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable {
                     table: vec![],
                     total: 1,
                 },
                 is_selling: false,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
             // to ease getting the sweet spot, remove clothing desire.
             test.property.desires.push(
                 Desire { item: Item::Product(15), start: 0, end: None, amount: 1.0, satisfaction: 0.0, step: 1, tags: vec![] }
             );
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in our property to exchange. 10 shirts, AMV value of 20.0 total
             test.property.add_property(6, 2.0, &data);
             let val = test.property.property.entry(15)
@@ -2976,7 +4058,8 @@ mod pop_tests {
         pub fn should_return_success_when_able_to_buy_single_offer_with_positive_change() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in pop's property and sift their desires.
             // we have 20 extra food than we need (20*5=100.0 units)
             // this covers all food and leave excess for trading
@@ -3114,7 +4197,8 @@ mod pop_tests {
         pub fn should_return_rejection_when_change_returned_with_negative_outcome() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             test.property.desires.clear();
             test.property.desires.push(Desire::new(Item::Product(2), 
                 0, None, 40.0, 
@@ -3232,7 +4316,8 @@ mod pop_tests {
         pub fn should_return_not_successful_when_close_deal_recieved_after_offer() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in pop's property and sift their desires.
             // we have 20 extra food than we need (20*5=100.0 units)
             // this covers all food and leave excess for trading
@@ -3365,7 +4450,8 @@ mod pop_tests {
         pub fn close_gracefully_when_close_deal_recieved() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in pop's property and sift their desires.
             // we have 20 extra food than we need (20*5=100.0 units)
             // this covers all food and leave excess for trading
@@ -3496,7 +4582,7 @@ mod pop_tests {
         use std::{thread, time::Duration};
         use  super::super::*;
 
-        use super::{make_test_pop, prepare_data_for_market_actions};
+        use super::{make_handling_free, make_test_pop, prepare_data_for_market_actions};
 
         #[test]
         pub fn return_cancel_buy_when_market_price_is_too_high() {
@@ -3663,7 +4749,8 @@ mod pop_tests {
         pub fn successfully_reach_buy_and_return_as_normal() {
             let mut test = make_test_pop();
             let pop_info = test.actor_info();
-            let (data, mut history) = prepare_data_for_market_actions(&mut test);
+            let (mut data, mut history) = prepare_data_for_market_actions(&mut test);
+            make_handling_free(&mut data);
             // add in pop's property and sift their desires.
             // we have 20 extra food than we need (20*5=100.0 units)
             // this covers all food and leave excess for trading
@@ -3819,15 +4906,22 @@ mod pop_tests {
             let mut result = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable { table: vec![], total: 0 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
 
             // don't care about pop breakdown. it's not actually being used.
@@ -3942,6 +5036,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -4011,6 +5107,9 @@ mod pop_tests {
                     Some(*unsat_desires.last().unwrap())).unwrap());
             }
 
+            // the food bought and the cotton given both need handling, which takes time too.
+            let handling = data.products.get(&2).unwrap().handling_cost(1.0)
+                + data.products.get(&3).unwrap().handling_cost(1.0);
             // setup property split
             let handle = thread::spawn(move || {
                 Pop::shopping_loop(&mut test, &mut passed_rx, &mut passed_tx, &data, 
@@ -4107,8 +5206,8 @@ mod pop_tests {
             assert_eq!(cotton_info.spent, 1.0);
             // huts were untouched
             assert_eq!(hut_info.total_property, 3.0);
-            // time was spent for shopping
-            assert_eq!(time_info.total_property, (1.1 * test.standard_shop_time_cost())-test.standard_shop_time_cost());
+            // time was spent for shopping and handling
+            assert_eq!(time_info.total_property, (1.1 * test.standard_shop_time_cost())-test.standard_shop_time_cost() - handling);
         }
 
         #[test]
@@ -4139,6 +5238,9 @@ mod pop_tests {
             let mut passed_tx = tx.clone();
 
             // get loop running
+            // the food bought and the clothes given both need handling, which takes time too.
+            let handling = data.products.get(&2).unwrap().handling_cost(1.0)
+                + data.products.get(&6).unwrap().handling_cost(1.0);
             let handle = thread::spawn(move || {
                 Pop::shopping_loop(&mut test, &mut passed_rx, &mut passed_tx, &data, 
                     &history);
@@ -4234,8 +5336,8 @@ mod pop_tests {
             assert_eq!(cotton_info.spent, 0.0);
             // huts were untouched
             assert_eq!(hut_info.total_property, 10.0);
-            // time was spent for shopping
-            assert_eq!(time_info.total_property, (100.0 * test.standard_shop_time_cost())-test.standard_shop_time_cost());
+            // time was spent for shopping and handling
+            assert_eq!(time_info.total_property, (100.0 * test.standard_shop_time_cost())-test.standard_shop_time_cost() - handling);
         }
 
         #[test]
@@ -4402,15 +5504,22 @@ mod pop_tests {
             let mut result = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable { table: vec![], total: 0 },
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
 
             // don't care about pop breakdown. it's not actually being used.
@@ -4525,6 +5634,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -4856,6 +5967,8 @@ mod pop_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -4898,15 +6011,22 @@ mod pop_tests {
             let mut pop = Pop {
                 id: 0,
                 job: 0,
-                firm: 0,
+                firm: Some(0),
+                owner: None,
                 market: 0,
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]),
                 breakdown_table: PopBreakdownTable { table: vec![], total: 1 },
                 is_selling: false,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0},
                 prev_sat: TieredValue { tier: 0, value: 0.0},
                 hypo_change: TieredValue { tier: 0, value: 0.0},
+                clutches: vec![],
                 backlog: VecDeque::new(),
+                salesmen: VecDeque::new(),
             };
 
             let (data, history) = prepare_data_for_market_actions(&mut pop);
@@ -5312,6 +6432,8 @@ mod pop_tests {
                 want_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
             };
 
             (history, manager)
@@ -1,9 +1,11 @@
 use political_economy_core::objects::{
     actor_objects::{
+        desire::DesireTag,
         pop::Pop,
         property::Property,
         property::TieredValue,
         property::DesireCoord,
+        property::Hold,
         desire::Desire,
         property_info::PropertyInfo,
     },
@@ -22,6 +24,7 @@ use political_economy_core::objects::{
         ideology::Ideology,
     },
     environmental_objects::market::{
+        ExchangeMode,
         MarketHistory, 
         ProductInfo
     },
@@ -55,15 +58,22 @@ mod property_tests {
         let mut test = Pop{ 
             id: 10, 
             job: 0, 
-            firm: 0, 
+            firm: Some(0), 
+            owner: None,
             market: 0, 
+            skill: 0,
+            lower_skill_level: 0.0,
+            higher_skill_level: 0.0,
             property: Property::new(vec![]), 
             breakdown_table: PopBreakdownTable{ table: vec![], total: 0 }, 
             is_selling: true,
+            mood: 0.0,
             current_sat: TieredValue { tier: 0, value: 0.0 },
             prev_sat: TieredValue { tier: 0, value: 0.0 },
             hypo_change: TieredValue { tier: 0, value: 0.0 },
-            backlog: VecDeque::new()};
+            clutches: vec![],
+            backlog: VecDeque::new(),
+            salesmen: VecDeque::new()};
 
         let species_desire_1 = Desire{ 
             item: Item::Want(2), // food
@@ -148,9 +158,7 @@ mod property_tests {
             vec![ideology_desire_1, ideology_desire_2],
             vec![]).expect("Messed up new.");
 
-        let mut demos = Demographics{ species: HashMap::new(),
-             cultures: HashMap::new(), 
-            ideology: HashMap::new() };
+        let mut demos = Demographics::new();
 
         demos.species.insert(species.id, species);
         demos.cultures.insert(culture.id, culture);
@@ -205,7 +213,7 @@ mod property_tests {
                 count: 5 }
         );
 
-        test.update_desires(demos);
+        test.update_desires(&demos);
 
         test
     }
@@ -233,6 +241,8 @@ mod property_tests {
             product_info: HashMap::new(),
             sale_priority: vec![],
             currencies: vec![],
+            exchange_mode: ExchangeMode::Bargaining,
+            neighbors: HashMap::new(),
             class_info: HashMap::new(),
             want_info: HashMap::new(),
         };
@@ -618,7 +628,9 @@ mod property_tests {
                 sale_priority: vec![], 
                 want_info: HashMap::new(),
                 class_info: HashMap::new(),
-                currencies: vec![] };
+                currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new() };
             let result = test.satisfaction_from_amv(1.0, &market); // first
             assert_eq!(result.tier, 0);
             assert!(0.999 < result.value);
@@ -3749,6 +3761,8 @@ mod property_tests {
                 product_info,
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 want_info: HashMap::new(),
                 class_info: HashMap::new(),
             };
@@ -3805,6 +3819,8 @@ mod property_tests {
                 product_info,
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 want_info: HashMap::new(),
                 class_info: HashMap::new(),
             };
@@ -6050,15 +6066,22 @@ mod property_tests {
             let mut test = Pop { 
                 id: 10, 
                 job: 0, 
-                firm: 0, 
+                firm: Some(0), 
+                owner: None,
                 market: 0, 
+                skill: 0,
+                lower_skill_level: 0.0,
+                higher_skill_level: 0.0,
                 property: Property::new(vec![]), 
                 breakdown_table: PopBreakdownTable{ table: vec![], total: 0 }, 
                 is_selling: true,
+                mood: 0.0,
                 current_sat: TieredValue { tier: 0, value: 0.0 },
                 prev_sat: TieredValue { tier: 0, value: 0.0 },
                 hypo_change: TieredValue { tier: 0, value: 0.0 },
-                backlog: VecDeque::new()};
+                clutches: vec![],
+                backlog: VecDeque::new(),
+                salesmen: VecDeque::new()};
 
             let species_desire_1 = Desire { 
                 item: Item::Want(2), // food
@@ -6143,9 +6166,7 @@ mod property_tests {
                 vec![ideology_desire_1, ideology_desire_2],
                 vec![]).expect("Messed up new.");
 
-            let mut demos = Demographics{ species: HashMap::new(),
-                cultures: HashMap::new(), 
-                ideology: HashMap::new() };
+            let mut demos = Demographics::new();
 
             demos.species.insert(species.id, species);
             demos.cultures.insert(culture.id, culture);
@@ -6200,7 +6221,7 @@ mod property_tests {
                     count: 5 }
             );
 
-            test.update_desires(demos);
+            test.update_desires(&demos);
 
             test
         }
@@ -6228,6 +6249,8 @@ mod property_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -6412,6 +6435,8 @@ mod property_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec!(),
                 currencies: vec!(),
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -6431,7 +6456,7 @@ mod property_tests {
             test.property.insert(0, PropertyInfo::new(3.0));
             test.property.insert(1, PropertyInfo::new(5.0));
             // get total result and see if it gets out with existing property.
-            let result1 = test.get_shopping_time(2.5, &data, 
+            let result1 = test.get_shopping_time(2.5, &data, 0.0, 0, 
                 &market, None);
             assert_eq!(result1, 2.5); // should get back our full target
             assert_eq!(test.property.get(&1)
@@ -6442,7 +6467,7 @@ mod property_tests {
             
             // if it was remove, do it again, testing that it only returns what is
             // available.
-            let capped_result = test.get_shopping_time(5.0, &data, &market, None);
+            let capped_result = test.get_shopping_time(5.0, &data, 0.0, 0, &market, None);
             assert_eq!(capped_result, 2.5); // should get back our what is available, nothing more.
             assert_eq!(test.property.get(&1)
                 .unwrap().total_property, 0.0); // should still have 2.5 remaining
@@ -6549,6 +6574,8 @@ mod property_tests {
                 product_info: HashMap::new(),
                 sale_priority: vec!(),
                 currencies: vec!(),
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
                 class_info: HashMap::new(),
                 want_info: HashMap::new(),
             };
@@ -6570,14 +6597,14 @@ mod property_tests {
             test.sift_up_to(&DesireCoord { tier: 2, idx: 1 }, &data);
             //test.property.insert(1, PropertyInfo::new(5.0));
             // get total result and see if it gets out with existing property.
-            let result1 = test.get_shopping_time(2.0, &data, 
+            let result1 = test.get_shopping_time(2.0, &data, 0.0, 0, 
                 &market, None);
             assert_eq!(result1, 2.0); // should get back our full target
             // of the 5 started, 2 are sifted, 2 is consumed for shopping time.
             assert_eq!(test.property[&0].total_property, 3.0);
             
             // do it again, overdrawing and being limited correctly.
-            let capped_result = test.get_shopping_time(5.0, &data, &market, None);
+            let capped_result = test.get_shopping_time(5.0, &data, 0.0, 0, &market, None);
             assert_eq!(capped_result, 1.0); // should get back our what is available, nothing more.
             // 2 were reserved, so 2 should remain.
             assert_eq!(test.property[&0].total_property, 2.0);
@@ -8543,6 +8570,8 @@ mod property_tests {
                 want_info: HashMap::new(),
                 sale_priority: vec![],
                 currencies: vec![],
+                exchange_mode: ExchangeMode::Bargaining,
+                neighbors: HashMap::new(),
             };
             
             test.consume_goods(&data, &history);
@@ -8586,4 +8615,230 @@ mod property_tests {
             assert!(!test.process_plan.contains_key(&1));
         }
     }
+
+    mod pay_handling_cost_should {
+        use std::collections::HashMap;
+        use super::*;
+
+        #[test]
+        pub fn pay_from_handling_time_product_first() {
+            let mut pop = make_test_pop();
+            let (mut data, history) = prepare_data_for_market_actions(&mut pop);
+            data.handling_time_product = Some(6);
+            let mut test = Property::new(vec![]);
+            test.unsafe_add_property(6, 1.0);
+
+            let mut exchange = HashMap::new();
+            exchange.insert(2, 2.0);
+            exchange.insert(3, -1.0);
+            // both what we recieve and what we give up are handled.
+            let cost = data.products.get(&2).unwrap().handling_cost(2.0) +
+                data.products.get(&3).unwrap().handling_cost(1.0);
+
+            let result = test.pay_handling_cost(&exchange, &data, 0.0, 0, &history);
+
+            assert_eq!(result, 0.0);
+            assert_eq!(test.property[&6].total_property, 1.0 - cost);
+            assert_eq!(test.property[&6].spent, cost);
+        }
+
+        #[test]
+        pub fn return_unpaid_cost_when_no_time_is_available() {
+            let mut pop = make_test_pop();
+            let (data, history) = prepare_data_for_market_actions(&mut pop);
+            let mut test = Property::new(vec![]);
+
+            let mut exchange = HashMap::new();
+            exchange.insert(2, 2.0);
+            exchange.insert(3, -1.0);
+            // both what we recieve and what we give up are handled.
+            let cost = data.products.get(&2).unwrap().handling_cost(2.0) +
+                data.products.get(&3).unwrap().handling_cost(1.0);
+
+            let result = test.pay_handling_cost(&exchange, &data, 0.0, 0, &history);
+
+            assert!(cost > 0.0);
+            assert_eq!(result, cost);
+        }
+
+        #[test]
+        pub fn charge_for_goods_given_as_well_as_recieved() {
+            let mut pop = make_test_pop();
+            let (mut data, history) = prepare_data_for_market_actions(&mut pop);
+            data.handling_time_product = Some(6);
+            let mut test = Property::new(vec![]);
+            test.unsafe_add_property(6, 1.0);
+
+            let mut exchange = HashMap::new();
+            exchange.insert(2, -2.0);
+            let cost = data.products.get(&2).unwrap().handling_cost(2.0);
+            assert_eq!(Property::handling_cost(&exchange, &data), cost);
+
+            let result = test.pay_handling_cost(&exchange, &data, 0.0, 0, &history);
+
+            assert!(cost > 0.0);
+            assert_eq!(result, 0.0);
+            assert_eq!(test.property[&6].spent, cost);
+        }
+
+        #[test]
+        pub fn share_only_what_can_be_handled() {
+            let mut pop = make_test_pop();
+            let (mut data, _) = prepare_data_for_market_actions(&mut pop);
+            data.handling_time_product = Some(6);
+            let mut test = Property::new(vec![]);
+
+            let mut exchange = HashMap::new();
+            exchange.insert(2, 2.0);
+            exchange.insert(3, -1.0);
+            let cost = Property::handling_cost(&exchange, &data);
+            assert_eq!(test.handleable_share(&exchange, &data, 0.0, 0), 0.0);
+
+            test.unsafe_add_property(6, cost / 2.0);
+            assert!((test.handleable_share(&exchange, &data, 0.0, 0) - 0.5).abs() < 1e-9);
+            test.unsafe_add_property(6, cost);
+            assert_eq!(test.handleable_share(&exchange, &data, 0.0, 0), 1.0);
+        }
+    }
+
+    #[test]
+    pub fn measure_unmet_tier_zero_desires() {
+        let mut test = Property::new(vec![]);
+        assert_eq!(test.tier_zero_shortfall(), 0.0);
+
+        test.desires.push(Desire { item: Item::Want(2), start: 0, end: Some(4),
+            amount: 2.0, satisfaction: 1.0, step: 1, tags: vec![] });
+        test.desires.push(Desire { item: Item::Product(3), start: 0, end: None,
+            amount: 2.0, satisfaction: 0.0, step: 0, tags: vec![] });
+        // higher tiers don't count.
+        test.desires.push(Desire { item: Item::Product(4), start: 5, end: None,
+            amount: 10.0, satisfaction: 0.0, step: 0, tags: vec![] });
+        assert_eq!(test.tier_zero_shortfall(), 0.75);
+
+        // satisfaction above the first tier doesn't cover others.
+        test.desires.get_mut(0).unwrap().satisfaction = 8.0;
+        assert_eq!(test.tier_zero_shortfall(), 0.5);
+    }
+
+    #[test]
+    pub fn set_aside_desires_not_needed_today() {
+        let periodic = Desire { item: Item::Product(3), start: 0, end: None,
+            amount: 2.0, satisfaction: 2.0, step: 0, 
+            tags: vec![DesireTag::Periodic { offset: 0, cycle: 2 }] };
+        let daily = Desire { item: Item::Product(4), start: 0, end: None,
+            amount: 1.0, satisfaction: 1.0, step: 0, tags: vec![] };
+        let mut test = Property::new(vec![periodic.clone(), daily.clone()]);
+        test.is_sifted = true;
+        let mut rng = rand::thread_rng();
+
+        test.activate_desires(0, &mut rng);
+        assert_eq!(test.desires.len(), 2);
+        assert!(test.is_sifted);
+
+        test.activate_desires(1, &mut rng);
+        assert_eq!(test.desires.len(), 1);
+        assert_eq!(test.dormant_desires.len(), 1);
+        assert_eq!(test.desires[0].satisfaction, 0.0);
+        assert!(!test.is_sifted);
+        // changes to dormant desires go to them.
+        test.add_desire(&periodic);
+        assert_eq!(test.dormant_desires[0].amount, 4.0);
+        test.remove_desire(&periodic);
+        assert_eq!(test.dormant_desires[0].amount, 2.0);
+
+        test.activate_desires(2, &mut rng);
+        assert_eq!(test.desires.len(), 2);
+        assert!(test.dormant_desires.is_empty());
+    }
+
+    #[test]
+    pub fn cap_satisfaction_when_desires_shrink() {
+        let food = Desire { item: Item::Product(3), start: 0, end: None,
+            amount: 10.0, satisfaction: 8.0, step: 0, tags: vec![] };
+        let mut test = Property::new(vec![food.clone()]);
+
+        // half the people leave, so does their desire.
+        let mut leaving = food.clone();
+        leaving.amount = 5.0;
+        test.remove_desire(&leaving);
+        assert_eq!(test.desires[0].amount, 5.0);
+        assert_eq!(test.desires[0].satisfaction, 5.0);
+
+        // replacing with a smaller desire caps it too.
+        let mut fewer = food.clone();
+        fewer.amount = 2.0;
+        fewer.satisfaction = 0.0;
+        test.replace_desires(vec![fewer]);
+        assert_eq!(test.desires[0].amount, 2.0);
+        assert_eq!(test.desires[0].satisfaction, 2.0);
+    }
+
+    #[test]
+    pub fn provide_only_what_is_available() {
+        let data = DataManager::new();
+        let mut test = Property::new(vec![]);
+        test.unsafe_add_property(3, 4.0);
+        test.add_want(2, &5.0);
+
+        let given = test.provide(&data, Item::Product(3), 6.0);
+        assert_eq!(given, vec![(Item::Product(3), 4.0)]);
+        assert_eq!(test.property.get(&3).unwrap().total_property, 0.0);
+        assert!(test.provide(&data, Item::Product(3), 1.0).is_empty());
+
+        let given = test.provide(&data, Item::Want(2), 3.0);
+        assert_eq!(given, vec![(Item::Want(2), 3.0)]);
+        assert_eq!(test.want_store.get(&2).unwrap().expendable(), 2.0);
+    }
+
+    #[test]
+    pub fn keep_toxic_desires_out_of_value_but_measure_toxicity() {
+        let toxin = Desire { item: Item::Product(3), start: 0, end: None,
+            amount: 4.0, satisfaction: 0.0, step: 0, tags: vec![DesireTag::Toxic] };
+        let food = Desire { item: Item::Product(4), start: 0, end: None,
+            amount: 1.0, satisfaction: 0.0, step: 0, tags: vec![] };
+        let mut test = Property::new(vec![toxin.clone(), food]);
+        assert_eq!(test.desires.len(), 1);
+        assert_eq!(test.toxic_desires.len(), 1);
+        assert_eq!(test.toxicity(), 0.0);
+
+        test.property.insert(3, PropertyInfo::new(1.0));
+        assert_eq!(test.toxicity(), 0.25);
+        test.property.insert(3, PropertyInfo::new(10.0));
+        assert_eq!(test.toxicity(), 1.0);
+
+        test.add_desire(&toxin);
+        assert_eq!(test.toxic_desires[0].amount, 8.0);
+        assert_eq!(test.desires.len(), 1);
+    }
+
+    #[test]
+    pub fn hold_goods_out_of_property_until_released() {
+        let mut pop = make_test_pop();
+        let (data, _) = prepare_data_for_market_actions(&mut pop);
+        let food = Desire::new(Item::Product(3), 0, Some(5), 
+            1.0, 0.0, 5, vec![]).unwrap();
+        let mut test = Property::new(vec![food]);
+        test.add_property(3, 5.0, &data);
+
+        // only what isn't reserved for our desires can be held.
+        let held = test.hold(Hold::Ask, 3, 10.0, &data);
+        assert_eq!(held, 3.0);
+        assert_eq!(test.property[&3].total_property, 2.0);
+        assert_eq!(test.held[&(Hold::Ask, 3)], 3.0);
+        assert_eq!(test.hold(Hold::Auction(0), 3, 1.0, &data), 0.0);
+
+        // releasing returns up to what is held.
+        assert_eq!(test.release(Hold::Ask, 3, 1.0, &data), 1.0);
+        assert_eq!(test.property[&3].total_property, 3.0);
+        assert_eq!(test.release(Hold::Bid(3), 3, 1.0, &data), 0.0);
+        test.hold(Hold::Bid(4), 3, 1.0, &data);
+        test.release_hold(Hold::Ask, &data);
+        assert_eq!(test.property[&3].total_property, 4.0);
+        assert!(!test.held.contains_key(&(Hold::Ask, 3)));
+
+        test.release_all(&data);
+        assert_eq!(test.property[&3].total_property, 5.0);
+        assert!(test.held.is_empty());
+        assert_eq!(test.desires[0].satisfaction, 2.0);
+    }
 }
@@ -0,0 +1,44 @@
+mod reproduction_tests {
+    use std::collections::HashMap;
+
+    use political_economy_core::objects::demographic_objects::reproduction::ReproductiveGroup;
+
+    fn roles(group: &ReproductiveGroup) -> Vec<String> {
+        group.roles.iter().map(|x| x.concat()).collect()
+    }
+
+    #[test]
+    pub fn parse_groups_roles_and_either_or_sexes() {
+        let test = ReproductiveGroup::parse("f/m").expect("Failed to parse.");
+        assert_eq!(test.len(), 1);
+        assert_eq!(roles(&test[0]), vec!["f", "m"]);
+
+        let test = ReproductiveGroup::parse("f/lm").expect("Failed to parse.");
+        assert_eq!(test[0].roles, vec![vec!["f".to_string()], 
+            vec!["l".to_string(), "m".to_string()]]);
+
+        let test = ReproductiveGroup::parse("(h)(f/m)").expect("Failed to parse.");
+        assert_eq!(test.len(), 2);
+        assert_eq!(roles(&test[0]), vec!["h"]);
+        assert_eq!(roles(&test[1]), vec!["f", "m"]);
+
+        let test = ReproductiveGroup::parse("(a/b/c/d)").expect("Failed to parse.");
+        assert_eq!(roles(&test[0]), vec!["a", "b", "c", "d"]);
+        assert_eq!(test[0].carriers(), ["a".to_string()]);
+
+        assert!(ReproductiveGroup::parse("").is_err());
+        assert!(ReproductiveGroup::parse("f//m").is_err());
+        assert!(ReproductiveGroup::parse("(f/m").is_err());
+        assert!(ReproductiveGroup::parse("(f/m)x").is_err());
+    }
+
+    #[test]
+    pub fn pair_up_to_the_smallest_role() {
+        let test = ReproductiveGroup::parse("f/lm").expect("Failed to parse.");
+        let sexes = HashMap::from([("f".to_string(), 10), 
+            ("l".to_string(), 3), ("m".to_string(), 4)]);
+
+        assert_eq!(test[0].pairings(&sexes), 7);
+        assert_eq!(test[0].pairings(&HashMap::new()), 0);
+    }
+}
@@ -0,0 +1,85 @@
+mod shipment_tests {
+    use political_economy_core::{
+        data_manager::DataManager,
+        objects::{
+            data_objects::product::{Product, ProductTag},
+            environmental_objects::{market::MarketConnection, shipment::Shipment}
+        }
+    };
+
+    fn prepare_data() -> DataManager {
+        let mut data = DataManager::new();
+        let goods = Product::new(0, "Goods".to_string(), "".to_string(),
+            "".to_string(), "".to_string(), 0, 2.0, 1.0, None, true,
+            vec![], None, None).unwrap();
+        let land = Product::new(1, "Land".to_string(), "".to_string(),
+            "".to_string(), "".to_string(), 0, 1.0, 1.0, None, true,
+            vec![ProductTag::Fixed], None, None).unwrap();
+        let service = Product::new(2, "Service".to_string(), "".to_string(),
+            "".to_string(), "".to_string(), 0, 0.0, 0.0, None, true,
+            vec![ProductTag::Service], None, None).unwrap();
+        data.products.insert(0, goods);
+        data.products.insert(1, land);
+        data.products.insert(2, service);
+        data
+    }
+
+    #[test]
+    pub fn refuse_fixed_service_missing_and_empty_shipments() {
+        let data = prepare_data();
+        let connection = MarketConnection::Land(30.0);
+
+        assert!(Shipment::new(&data, &connection, 0, 0, 1, 1, 1.0).is_err());
+        assert!(Shipment::new(&data, &connection, 0, 0, 1, 2, 1.0).is_err());
+        assert!(Shipment::new(&data, &connection, 0, 0, 1, 3, 1.0).is_err());
+        assert!(Shipment::new(&data, &connection, 0, 0, 1, 0, 0.0).is_err());
+    }
+
+    #[test]
+    pub fn calculate_cost_and_travel_days_by_connection() {
+        let data = prepare_data();
+        // 10 units, mass 20 and bulk 10 => 0.02 + 0.05 = 0.07 per distance.
+        let land = Shipment::new(&data, &MarketConnection::Land(60.0),
+            0, 0, 1, 0, 10.0).unwrap();
+        assert!((land.cost - 4.2).abs() < 0.00001);
+        assert_eq!(land.days_remaining, 2);
+
+        let sea = Shipment::new(&data, &MarketConnection::Sea(60.0),
+            0, 0, 1, 0, 10.0).unwrap();
+        assert!((sea.cost - 1.05).abs() < 0.00001);
+        assert_eq!(sea.days_remaining, 1);
+
+        let air = Shipment::new(&data, &MarketConnection::Air(60.0),
+            0, 0, 1, 0, 10.0).unwrap();
+        assert!((air.cost - 21.0).abs() < 0.00001);
+        assert_eq!(air.days_remaining, 1);
+
+        let space = Shipment::new(&data, &MarketConnection::Space(3000.0, 10.0),
+            0, 0, 1, 0, 10.0).unwrap();
+        assert!((space.cost - 0.7).abs() < 0.00001);
+        assert_eq!(space.days_remaining, 10);
+    }
+
+    #[test]
+    pub fn limit_portal_throughput() {
+        let data = prepare_data();
+        let portal = MarketConnection::Portal(1.0, 10.0, 10.0);
+
+        let test = Shipment::new(&data, &portal, 0, 0, 1, 0, 5.0).unwrap();
+        assert_eq!(test.days_remaining, 0);
+        // 6 units is 12 mass, over the limit.
+        assert!(Shipment::new(&data, &portal, 0, 0, 1, 0, 6.0).is_err());
+    }
+
+    #[test]
+    pub fn arrive_after_travel_days_pass() {
+        let data = prepare_data();
+        let mut test = Shipment::new(&data, &MarketConnection::Land(45.0),
+            0, 0, 1, 0, 1.0).unwrap();
+        assert_eq!(test.days_remaining, 2);
+
+        assert!(!test.advance_day());
+        assert!(!test.advance_day());
+        assert!(test.advance_day());
+    }
+}
@@ -0,0 +1,163 @@
+mod skill_tests {
+    use std::collections::HashMap;
+
+    use political_economy_core::{
+        constants::{DISCERNMENT_PRODUCT_ID, SHOPPING_TIME_PROC_ID,
+            SHOPPING_TIME_PRODUCT_ID, SKILL_EFFICIENCY_PER_LEVEL, TIME_PRODUCT_ID},
+        data_manager::DataManager,
+        demographics::Demographics,
+        objects::{
+            actor_objects::{desire::Desire, property::Property},
+            data_objects::{
+                item::Item,
+                process::{ProcessPart, ProcessSectionTag},
+                skill::{Skill, SkillGroup}
+            },
+            demographic_objects::{
+                pop_breakdown_table::PBRow,
+                species::{Species, SpeciesTag}
+            },
+            environmental_objects::market::{ExchangeMode, MarketHistory}
+        }
+    };
+
+    #[test]
+    pub fn connect_skills_and_groups_both_ways() {
+        let mut skill = Skill::new(0, "Farming".into(), "".into(), None)
+            .expect("Messed up new.");
+        let mut group = SkillGroup::new(0, "Agriculture".into(), "".into())
+            .expect("Messed up new.");
+
+        group.add_skill(&mut skill);
+        group.add_skill(&mut skill);
+
+        assert_eq!(group.skills, vec![0]);
+        assert_eq!(skill.groups, vec![0]);
+        assert!(Skill::new(1, "".into(), "".into(), None).is_err());
+        assert!(SkillGroup::new(1, "".into(), "".into()).is_err());
+    }
+
+    #[test]
+    pub fn load_a_skill_for_every_test_job() {
+        let mut data = DataManager::new();
+        data.load_test_data().expect("Failed to load data.");
+
+        for job in data.jobs.values() {
+            assert!(data.skills.contains_key(&job.skill));
+        }
+        for group in data.skill_groups.values() {
+            for skill in group.skills.iter() {
+                assert!(data.skills[skill].groups.contains(&group.id));
+            }
+        }
+        assert_eq!(data.new_skill_id(), 10);
+        assert_eq!(data.new_skill_group_id(), 4);
+    }
+
+    #[test]
+    pub fn combine_species_skill_and_group_modifiers() {
+        let mut demos = Demographics::new();
+        let species = Species::new(0, "Species".into(), "".into(), vec![],
+            vec![SpeciesTag::SkillModifier { skill: 0, modifier: 2.0 },
+                SpeciesTag::SkillGroupModifier { skill_group: 1, modifier: 0.5 }],
+            vec![], 1.0, 0.0, 0.0).expect("Messed up new.");
+        demos.species.insert(species.id, species);
+        let row = PBRow::new(0, None, None, None, None, None,
+            None, None, None, 10);
+        let mut skill = Skill::new(0, "Farming".into(), "".into(), None)
+            .expect("Messed up new.");
+        let other = Skill::new(1, "Weaving".into(), "".into(), None)
+            .expect("Messed up new.");
+
+        assert_eq!(demos.skill_learning_rate(&row, &skill), 2.0);
+        assert_eq!(demos.skill_learning_rate(&row, &other), 1.0);
+        skill.groups.push(1);
+        assert_eq!(demos.skill_learning_rate(&row, &skill), 1.0);
+    }
+
+    /// Makes the required items, with shopping simplified to take 1 Time 
+    /// and 1 Discernment as capital for 1 Shopping Time.
+    fn make_shopping_data() -> DataManager {
+        let mut data = DataManager::new();
+        data.required_items();
+        let shopping = data.processes.get_mut(&SHOPPING_TIME_PROC_ID).unwrap();
+        shopping.process_parts = vec![
+            ProcessPart { item: Item::Product(TIME_PRODUCT_ID), amount: 1.0,
+                part_tags: vec![], part: ProcessSectionTag::Input },
+            ProcessPart { item: Item::Product(DISCERNMENT_PRODUCT_ID), amount: 1.0,
+                part_tags: vec![], part: ProcessSectionTag::Capital },
+            ProcessPart { item: Item::Product(SHOPPING_TIME_PRODUCT_ID), amount: 1.0,
+                part_tags: vec![], part: ProcessSectionTag::Output },
+        ];
+        data.products.get_mut(&SHOPPING_TIME_PRODUCT_ID).unwrap()
+            .processes.insert(SHOPPING_TIME_PROC_ID);
+        data
+    }
+
+    #[test]
+    pub fn increase_process_outputs_with_efficiency() {
+        let data = make_shopping_data();
+        let process = data.processes.get(&SHOPPING_TIME_PROC_ID).unwrap();
+        let available = HashMap::from([(TIME_PRODUCT_ID, 10.0),
+            (DISCERNMENT_PRODUCT_ID, 10.0)]);
+
+        let normal = process.do_process(&available, &HashMap::new(),
+            0.0, None, false, &data);
+        let boosted = process.do_process(&available, &HashMap::new(),
+            0.5, None, false, &data);
+
+        assert_eq!(normal.iterations, boosted.iterations);
+        assert_eq!(normal.input_output_products[&TIME_PRODUCT_ID],
+            boosted.input_output_products[&TIME_PRODUCT_ID]);
+        assert_eq!(normal.input_output_products[&SHOPPING_TIME_PRODUCT_ID] * 1.5,
+            boosted.input_output_products[&SHOPPING_TIME_PRODUCT_ID]);
+    }
+
+    #[test]
+    pub fn get_more_shopping_time_with_discernment() {
+        let mut data = make_shopping_data();
+        data.skills.insert(0, Skill::new(0, "Discernment".into(), "".into(),
+            Some(DISCERNMENT_PRODUCT_ID)).expect("Messed up new."));
+        data.skills.insert(1, Skill::new(1, "Farming".into(), "".into(),
+            None).expect("Messed up new."));
+        let mut test = Property::new(vec![Desire { item: Item::Want(1),
+            start: 0, end: None, amount: 1.0, satisfaction: 0.0, step: 1,
+            tags: vec![] }]);
+        test.add_property(TIME_PRODUCT_ID, 10.0, &data);
+        test.add_property(DISCERNMENT_PRODUCT_ID, 10.0, &data);
+
+        let unskilled = test.available_shopping_time(&data, 0.0, 0);
+        let skilled = test.available_shopping_time(&data, 2.0, 0);
+        let wrong_skill = test.available_shopping_time(&data, 2.0, 1);
+
+        assert_eq!(unskilled, 10.0);
+        assert_eq!(skilled, 10.0 * (1.0 + 2.0 * SKILL_EFFICIENCY_PER_LEVEL));
+        assert_eq!(wrong_skill, unskilled);
+    }
+
+    #[test]
+    pub fn get_and_handle_with_more_shopping_time_with_discernment() {
+        let mut data = make_shopping_data();
+        data.skills.insert(0, Skill::new(0, "Discernment".into(), "".into(),
+            Some(DISCERNMENT_PRODUCT_ID)).expect("Messed up new."));
+        let market = MarketHistory { product_info: HashMap::new(), 
+            class_info: HashMap::new(), want_info: HashMap::new(), 
+            sale_priority: vec![], currencies: vec![], 
+            exchange_mode: ExchangeMode::Bargaining, neighbors: HashMap::new() };
+        let mut test = Property::new(vec![Desire { item: Item::Want(1),
+            start: 0, end: None, amount: 1.0, satisfaction: 0.0, step: 1,
+            tags: vec![] }]);
+        test.add_property(TIME_PRODUCT_ID, 10.0, &data);
+        test.add_property(DISCERNMENT_PRODUCT_ID, 10.0, &data);
+        let skilled = 10.0 * (1.0 + 2.0 * SKILL_EFFICIENCY_PER_LEVEL);
+
+        assert_eq!(test.available_handling_time(&data, 0.0, 0), 10.0);
+        assert_eq!(test.available_handling_time(&data, 2.0, 0), skilled);
+
+        // the skilled get what they need from less time.
+        let got = test.get_shopping_time(10.0, &data, 2.0, 0, &market, None);
+        assert!((got - 10.0).abs() < 1e-9);
+        let spent = 10.0 - test.property[&TIME_PRODUCT_ID].total_property;
+        assert!((spent - 10.0 * 10.0 / skilled).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,127 @@
+mod species_tests {
+    use std::collections::HashMap;
+
+    use political_economy_core::objects::{
+        actor_objects::desire::Desire,
+        data_objects::item::Item,
+        demographic_objects::species::{Cohort, CohortTag, Species, SpeciesTag, 
+            Subtype, SubtypeTag}
+    };
+
+    fn food(amount: f64) -> Desire {
+        Desire { item: Item::Want(2), start: 0, end: Some(4), amount,
+            satisfaction: 0.0, step: 1, tags: vec![] }
+    }
+
+    fn toys(amount: f64) -> Desire {
+        Desire { item: Item::Product(5), start: 10, end: None, amount,
+            satisfaction: 0.0, step: 0, tags: vec![] }
+    }
+
+    fn make_species() -> Species {
+        let mut test = Species::new(0, "Species".into(), "".into(),
+            vec![food(1.0)], 
+            vec![SpeciesTag::SkillModifier { skill: 1, modifier: 2.0 }], 
+            vec![], 1.0, 0.03, 0.02).expect("Messed up new.");
+        test.add_cohort(Cohort::new("Child".into(), 3650, -0.03, 0.01, 0.5,
+            vec![CohortTag::SkillModifier { skill: 1, modifier: 0.5 }], 
+            vec![food(-0.5), toys(1.0)]).expect("Messed up new."));
+        test.add_cohort(Cohort::new("Adult".into(), 18250, 0.0, 0.0, 1.0,
+            vec![], vec![]).expect("Messed up new."));
+        test.add_subtype(Subtype::new("Female".into(), 1.0, 0.02, 0.0, 1.0,
+            vec![], vec![]).expect("Messed up new."));
+        test.add_subtype(Subtype::new("Male".into(), 3.0, -0.05, 0.01, 1.2,
+            vec![], vec![food(0.5)]).expect("Messed up new."));
+        test
+    }
+
+    #[test]
+    pub fn build_data_table_for_every_combination() {
+        let test = make_species();
+
+        assert_eq!(test.data_table.len(), 9);
+        let base = test.get_subentry(None, None).unwrap();
+        assert_eq!(base.productivity, 1.0);
+        assert_eq!(base.birth_rate, 0.03);
+        assert_eq!(base.desires.len(), 1);
+        assert_eq!(base.skill_modifiers[&1], 2.0);
+
+        let child_male = test.get_subentry(Some(0), Some(1)).unwrap();
+        assert_eq!(child_male.productivity, 0.6);
+        // 0.03 - 0.03 - 0.05 capped at 0.
+        assert_eq!(child_male.birth_rate, 0.0);
+        assert!((child_male.mortality_rate - 0.04).abs() < 0.00001);
+        assert_eq!(child_male.skill_modifiers[&1], 1.0);
+        // food 1.0 - 0.5 + 0.5, plus toys.
+        assert_eq!(child_male.desires.len(), 2);
+        assert_eq!(child_male.desires.iter()
+            .find(|x| x.item == Item::Want(2)).unwrap().amount, 1.0);
+
+        assert!(test.get_subentry(Some(2), None).is_err());
+        assert!(test.get_subentry(None, Some(2)).is_err());
+    }
+
+    #[test]
+    pub fn mark_drones_from_any_level() {
+        let mut test = make_species();
+        assert!(test.data_table.iter().all(|x| !x.is_drone));
+        test.add_cohort(Cohort::new("Worker".into(), 10, 0.0, 0.0, 1.0,
+            vec![CohortTag::Drone], vec![]).expect("Messed up new."));
+
+        assert!(!test.get_subentry(Some(1), Some(0)).unwrap().is_drone);
+        assert!(test.get_subentry(Some(2), Some(0)).unwrap().is_drone);
+        assert!(test.get_subentry(Some(2), None).unwrap().is_drone);
+
+        test.tags.push(SpeciesTag::Drone);
+        test.update_data_table();
+        assert!(test.data_table.iter().all(|x| x.is_drone));
+    }
+
+    #[test]
+    pub fn remove_desires_reduced_to_nothing() {
+        let mut test = Species::new(0, "Species".into(), "".into(),
+            vec![food(1.0)], vec![], vec![], 1.0, 0.03, 0.02)
+            .expect("Messed up new.");
+        test.add_cohort(Cohort::new("Sleeper".into(), 10, 0.0, 0.0, 1.0,
+            vec![], vec![food(-2.0)]).expect("Messed up new."));
+
+        assert!(test.get_subentry(Some(0), None).unwrap().desires.is_empty());
+    }
+
+    #[test]
+    pub fn divide_births_by_subtype_weight() {
+        let test = make_species();
+
+        assert_eq!(test.birth_cohort(), Some(0));
+        let result = test.birth_subtypes();
+        assert_eq!(result, vec![(Some(0), 0.25), (Some(1), 0.75)]);
+
+        let plain = Species::new(1, "Plain".into(), "".into(),
+            vec![], vec![], vec![], 1.0, 0.0, 0.0).expect("Messed up new.");
+        assert_eq!(plain.birth_cohort(), None);
+        assert_eq!(plain.birth_subtypes(), vec![(None, 1.0)]);
+    }
+
+    #[test]
+    pub fn limit_breeding_to_carriers_with_partners() {
+        let mut test = Species::new(0, "Species".into(), "".into(), vec![],
+            vec![SpeciesTag::ReproductiveGroup("(h)(f/m)".into())],
+            vec![], 1.0, 0.03, 0.02).expect("Messed up new.");
+        for sex in ["f", "m", "h"] {
+            test.add_subtype(Subtype::new(sex.into(), 1.0, 0.0, 0.0, 1.0,
+                vec![SubtypeTag::Sex(sex.into())], vec![]).expect("Messed up new."));
+        }
+        let fertile = HashMap::from([(Some(0), 10), (Some(1), 5), (Some(2), 4)]);
+
+        let shares = test.breeding_shares(&fertile).unwrap();
+
+        assert_eq!(shares[&Some(0)], 0.5);
+        assert_eq!(shares[&Some(1)], 0.0);
+        assert_eq!(shares[&Some(2)], 1.0);
+        assert_eq!(test.subtype_sex(Some(1)), Some("m"));
+        assert!(make_species().breeding_shares(&fertile).is_none());
+        assert!(Species::new(1, "Bad".into(), "".into(), vec![],
+            vec![SpeciesTag::ReproductiveGroup("f//m".into())],
+            vec![], 1.0, 0.03, 0.02).is_err());
+    }
+}